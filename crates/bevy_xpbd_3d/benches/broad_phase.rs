@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use benches_common_3d::bench_app;
+use bevy::prelude::*;
+use bevy_xpbd_3d::math::*;
+use bevy_xpbd_3d::prelude::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+// Tall stacks of cubes share the same x range, which is the worst case for sweep and prune
+fn setup_stacks(app: &mut App, backend: BroadPhaseBackend, stacks: u32, height: u32) {
+    app.insert_resource(BroadPhaseConfig { backend });
+
+    app.add_systems(Startup, move |mut commands: Commands| {
+        commands.spawn((
+            RigidBody::Static,
+            Position(-2.0 * Vector::Y),
+            Collider::cuboid(100.0, 1.0, 100.0),
+        ));
+
+        for z in 0..stacks {
+            for y in 0..height {
+                commands.spawn((
+                    RigidBody::Dynamic,
+                    Position(Vector::new(0.0, y as Scalar * 1.05, z as Scalar * 2.0)),
+                    Collider::cuboid(1.0, 1.0, 1.0),
+                ));
+            }
+        }
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let backends = [
+        ("sweep and prune", BroadPhaseBackend::SweepAndPrune),
+        ("dynamic AABB tree", BroadPhaseBackend::DynamicAabbTree),
+        (
+            "uniform grid",
+            BroadPhaseBackend::UniformGrid { cell_size: 2.0 },
+        ),
+    ];
+
+    for (name, backend) in backends {
+        c.bench_function(&format!("stacks 5x20, {name}, 30 steps"), |b| {
+            bench_app(b, 30, |app| setup_stacks(app, backend, 5, 20))
+        });
+
+        c.bench_function(&format!("stacks 10x50, {name}, 30 steps"), |b| {
+            bench_app(b, 30, |app| setup_stacks(app, backend, 10, 50))
+        });
+    }
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(20));
+    targets = criterion_benchmark
+);
+criterion_main!(benches);
@@ -4,10 +4,11 @@ use crate::prelude::*;
 #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
 use bevy::render::mesh::{Indices, VertexAttributeValues};
 use bevy::{prelude::*, utils::HashSet};
-use derive_more::From;
 use parry::{
     bounding_volume::Aabb,
-    shape::{SharedShape, TypedShape},
+    either::Either,
+    query::point::PointCompositeShapeProjBestFirstVisitor,
+    shape::{RoundShape, SharedShape, TypedShape},
 };
 
 /// Flags used for the preprocessing of a triangle mesh collider.
@@ -41,6 +42,14 @@ pub type TriMeshFlags = parry::shape::TriMeshFlags;
 /// - [`CollidingEntities`]
 /// - [`ColliderMassProperties`]
 ///
+/// ## Multiple colliders
+///
+/// Colliders can also be added to descendants of a [rigid body](RigidBody) entity, each with its own
+/// `Transform` offset relative to the body. The mass properties of these colliders are added to the body,
+/// and contacts against them move the body, while collision events still report the collider entities.
+///
+/// See [`ColliderParent`] for more information and an example.
+///
 /// ## Collision layers
 ///
 /// You can use collsion layers to configure which entities can collide with each other.
@@ -74,18 +83,59 @@ pub type TriMeshFlags = parry::shape::TriMeshFlags;
 /// using these shapes, you can simply use `Collider::from(SharedShape::some_method())`.
 ///
 /// To get a reference to the internal [`SharedShape`], you can use the [`get_shape`](#method.get_shape) method.
-#[derive(Clone, Component, Deref, DerefMut, From)]
-pub struct Collider(SharedShape);
+///
+/// ## Scale
+///
+/// Colliders are scaled based on the scale of their `GlobalTransform`. Non-uniform scale is supported for most shapes,
+/// but balls, capsules, cylinders and cones are approximated by convex hulls when scaled non-uniformly,
+/// and the border radius of rounded shapes is scaled by the largest scale component.
+///
+/// The scaled shape is returned by [`get_shape`](#method.get_shape), and the original unscaled shape can be accessed with
+/// [`get_unscaled_shape`](#method.get_unscaled_shape).
+///
+/// Because the scaled shape is derived from the unscaled shape, `Collider` no longer dereferences mutably to
+/// its [`SharedShape`]. Use [`set_shape`](#method.set_shape) to change the shape of a collider.
+#[derive(Clone, Component)]
+pub struct Collider {
+    /// The unscaled shape of the collider.
+    shape: SharedShape,
+    /// The shape of the collider scaled by `scale`.
+    scaled_shape: SharedShape,
+    /// The global scale of the collider.
+    scale: Vector,
+    /// True if the unscaled shape may have been modified through `get_shape_mut`,
+    /// and the scaled shape needs to be updated.
+    shape_modified: bool,
+}
+
+impl From<SharedShape> for Collider {
+    fn from(shape: SharedShape) -> Self {
+        Self {
+            scaled_shape: shape.clone(),
+            shape,
+            scale: Vector::ONE,
+            shape_modified: false,
+        }
+    }
+}
+
+impl std::ops::Deref for Collider {
+    type Target = SharedShape;
+
+    fn deref(&self) -> &Self::Target {
+        &self.scaled_shape
+    }
+}
 
 impl Default for Collider {
     fn default() -> Self {
         #[cfg(feature = "2d")]
         {
-            Self(SharedShape::cuboid(0.5, 0.5))
+            Self::from(SharedShape::cuboid(0.5, 0.5))
         }
         #[cfg(feature = "3d")]
         {
-            Self(SharedShape::cuboid(0.5, 0.5, 0.5))
+            Self::from(SharedShape::cuboid(0.5, 0.5, 0.5))
         }
     }
 }
@@ -127,14 +177,77 @@ impl fmt::Debug for Collider {
 }
 
 impl Collider {
-    /// Returns the raw shape of the collider. The shapes are provided by [`parry`].
+    /// Returns the raw shape of the collider, scaled by the collider's [scale](#method.scale).
+    /// The shapes are provided by [`parry`].
     pub fn get_shape(&self) -> &SharedShape {
-        &self.0
+        &self.scaled_shape
     }
 
-    /// Returns a mutable reference to the raw shape of the collider. The shapes are provided by [`parry`].
+    /// Returns the raw shape of the collider without any scaling applied. The shapes are provided by [`parry`].
+    pub fn get_unscaled_shape(&self) -> &SharedShape {
+        &self.shape
+    }
+
+    /// Returns a mutable reference to the raw unscaled shape of the collider. The shapes are provided by [`parry`].
+    ///
+    /// The scaled shape returned by [`get_shape`](#method.get_shape) is only updated from the modified shape
+    /// when the collider is prepared at the start of the next physics frame.
+    #[deprecated(
+        note = "use `Collider::set_shape` instead, which also updates the scaled shape right away"
+    )]
     pub fn get_shape_mut(&mut self) -> &mut SharedShape {
-        &mut self.0
+        self.shape_modified = true;
+        &mut self.shape
+    }
+
+    /// Sets the unscaled shape of the collider. The collider's current [scale](#method.scale) is applied to the new shape.
+    pub fn set_shape(&mut self, shape: SharedShape) {
+        self.shape = shape;
+        self.shape_modified = false;
+        let scale = self.scale;
+        // Force the shape to be rescaled
+        self.scale = Vector::ONE;
+        self.scaled_shape = self.shape.clone();
+        self.set_scale(scale, 10);
+    }
+
+    /// Returns true if the unscaled shape may have been modified through the deprecated `get_shape_mut` method
+    /// after the scaled shape was last updated.
+    pub(crate) fn is_shape_modified(&self) -> bool {
+        self.shape_modified
+    }
+
+    /// Returns the global scale of the collider.
+    pub fn scale(&self) -> Vector {
+        self.scale
+    }
+
+    /// Sets the scale of the collider, scaling its shape.
+    ///
+    /// Shapes that can't be represented exactly with a non-uniform scale, like balls and capsules,
+    /// are approximated by convex hulls using `num_subdivisions` subdivisions.
+    ///
+    /// If the shape can't be scaled, for example because one of the scale components is zero,
+    /// the previous scale is kept and a warning is logged.
+    pub fn set_scale(&mut self, scale: Vector, num_subdivisions: u32) {
+        if scale == self.scale {
+            return;
+        }
+
+        if scale == Vector::ONE {
+            // Trivial case
+            self.scaled_shape = self.shape.clone();
+            self.scale = scale;
+            return;
+        }
+
+        match scale_shape(&self.shape, scale, num_subdivisions) {
+            Some(scaled_shape) => {
+                self.scaled_shape = scaled_shape;
+                self.scale = scale;
+            }
+            None => warn!("failed to scale collider with shape {:?} by {scale}", self),
+        }
     }
 
     /// Computes the [Axis-Aligned Bounding Box](ColliderAabb) of the collider.
@@ -155,6 +268,29 @@ impl Collider {
         )
     }
 
+    /// Finds the index of the sub-shape of a compound collider that is closest to the given world-space `point`,
+    /// for example the hit point of a ray or shape cast. The index matches the order of the shapes
+    /// given to [`Collider::compound`].
+    ///
+    /// `position` and `rotation` are the [`Position`] and [`Rotation`] of the collider entity.
+    /// Returns `None` if the collider isn't a compound shape.
+    pub fn compound_sub_shape_at(
+        &self,
+        position: impl Into<Position>,
+        rotation: impl Into<Rotation>,
+        point: Vector,
+    ) -> Option<usize> {
+        let compound = self.get_shape().as_compound()?;
+        let isometry = utils::make_isometry(*position.into(), &rotation.into());
+        let local_point = isometry.inverse_transform_point(&point.into());
+        let mut visitor =
+            PointCompositeShapeProjBestFirstVisitor::new(compound, &local_point, false);
+        compound
+            .qbvh()
+            .traverse_best_first(&mut visitor)
+            .map(|(_, (_, sub_shape))| sub_shape as usize)
+    }
+
     /// Creates a collider with a compound shape defined by a given vector of colliders with a position and a rotation.
     ///
     /// Especially for dynamic rigid bodies, compound shape colliders should be preferred over triangle meshes and polylines,
@@ -359,6 +495,121 @@ impl Collider {
     }
 }
 
+/// Scales the given shape by a possibly non-uniform `scale`.
+///
+/// Shapes that can't be represented with a non-uniform scale are approximated by convex hulls
+/// with `num_subdivisions` subdivisions. Returns `None` if the shape couldn't be scaled.
+fn scale_shape(shape: &SharedShape, scale: Vector, num_subdivisions: u32) -> Option<SharedShape> {
+    let scale_vec: parry::math::Vector<Scalar> = scale.into();
+    // Rounded borders can't be scaled non-uniformly, so they are scaled by the largest scale component
+    let border_scale = scale.abs().max_element();
+
+    let scaled_shape = match shape.as_typed_shape() {
+        TypedShape::Ball(ball) => match ball.scaled(&scale_vec, num_subdivisions)? {
+            Either::Left(ball) => SharedShape::new(ball),
+            Either::Right(hull) => SharedShape::new(hull),
+        },
+        TypedShape::Cuboid(cuboid) => SharedShape::new(cuboid.scaled(&scale_vec)),
+        TypedShape::RoundCuboid(round_cuboid) => SharedShape::new(RoundShape {
+            inner_shape: round_cuboid.inner_shape.scaled(&scale_vec),
+            border_radius: round_cuboid.border_radius * border_scale,
+        }),
+        TypedShape::Capsule(capsule) => match capsule.scaled(&scale_vec, num_subdivisions)? {
+            Either::Left(capsule) => SharedShape::new(capsule),
+            Either::Right(hull) => SharedShape::new(hull),
+        },
+        TypedShape::Segment(segment) => SharedShape::new(segment.scaled(&scale_vec)),
+        TypedShape::Triangle(triangle) => SharedShape::new(triangle.scaled(&scale_vec)),
+        TypedShape::RoundTriangle(round_triangle) => SharedShape::new(RoundShape {
+            inner_shape: round_triangle.inner_shape.scaled(&scale_vec),
+            border_radius: round_triangle.border_radius * border_scale,
+        }),
+        TypedShape::TriMesh(trimesh) => SharedShape::new(trimesh.clone().scaled(&scale_vec)),
+        TypedShape::Polyline(polyline) => SharedShape::new(polyline.clone().scaled(&scale_vec)),
+        TypedShape::HalfSpace(half_space) => SharedShape::new(half_space.scaled(&scale_vec)?),
+        TypedShape::HeightField(heightfield) => {
+            SharedShape::new(heightfield.clone().scaled(&scale_vec))
+        }
+        TypedShape::Compound(compound) => {
+            // Scale the offsets and shapes of the sub-shapes in their local space.
+            // This is only exact if the sub-shapes aren't rotated relative to a non-uniform scale.
+            let shapes = compound
+                .shapes()
+                .iter()
+                .map(|(isometry, shape)| {
+                    let mut isometry = *isometry;
+                    isometry.translation.vector.component_mul_assign(&scale_vec);
+                    Some((isometry, scale_shape(shape, scale, num_subdivisions)?))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            SharedShape::compound(shapes)
+        }
+        TypedShape::Custom(_) => return None,
+        #[cfg(feature = "3d")]
+        TypedShape::ConvexPolyhedron(polyhedron) => {
+            SharedShape::new(polyhedron.clone().scaled(&scale_vec)?)
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::Cylinder(cylinder) => match cylinder.scaled(&scale_vec, num_subdivisions)? {
+            Either::Left(cylinder) => SharedShape::new(cylinder),
+            Either::Right(hull) => SharedShape::new(hull),
+        },
+        #[cfg(feature = "3d")]
+        TypedShape::Cone(cone) => match cone.scaled(&scale_vec, num_subdivisions)? {
+            Either::Left(cone) => SharedShape::new(cone),
+            Either::Right(hull) => SharedShape::new(hull),
+        },
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCylinder(round_cylinder) => {
+            let border_radius = round_cylinder.border_radius * border_scale;
+            match round_cylinder
+                .inner_shape
+                .scaled(&scale_vec, num_subdivisions)?
+            {
+                Either::Left(inner_shape) => SharedShape::new(RoundShape {
+                    inner_shape,
+                    border_radius,
+                }),
+                Either::Right(inner_shape) => SharedShape::new(RoundShape {
+                    inner_shape,
+                    border_radius,
+                }),
+            }
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::RoundCone(round_cone) => {
+            let border_radius = round_cone.border_radius * border_scale;
+            match round_cone
+                .inner_shape
+                .scaled(&scale_vec, num_subdivisions)?
+            {
+                Either::Left(inner_shape) => SharedShape::new(RoundShape {
+                    inner_shape,
+                    border_radius,
+                }),
+                Either::Right(inner_shape) => SharedShape::new(RoundShape {
+                    inner_shape,
+                    border_radius,
+                }),
+            }
+        }
+        #[cfg(feature = "3d")]
+        TypedShape::RoundConvexPolyhedron(round_polyhedron) => SharedShape::new(RoundShape {
+            inner_shape: round_polyhedron.inner_shape.clone().scaled(&scale_vec)?,
+            border_radius: round_polyhedron.border_radius * border_scale,
+        }),
+        #[cfg(feature = "2d")]
+        TypedShape::ConvexPolygon(polygon) => SharedShape::new(polygon.clone().scaled(&scale_vec)?),
+        #[cfg(feature = "2d")]
+        TypedShape::RoundConvexPolygon(round_polygon) => SharedShape::new(RoundShape {
+            inner_shape: round_polygon.inner_shape.clone().scaled(&scale_vec)?,
+            border_radius: round_polygon.border_radius * border_scale,
+        }),
+    };
+
+    Some(scaled_shape)
+}
+
 #[cfg(all(feature = "3d", feature = "collider-from-mesh"))]
 type VerticesIndices = (Vec<nalgebra::Point3<Scalar>>, Vec<[u32; 3]>);
 
@@ -414,6 +665,94 @@ fn extract_mesh_vertices_indices(mesh: &Mesh) -> Option<VerticesIndices> {
 #[derive(Reflect, Clone, Component, Debug, Default, PartialEq, Eq)]
 pub struct Sensor;
 
+/// The [rigid body](RigidBody) entity that a [`Collider`] is attached to.
+///
+/// Colliders can be attached to rigid bodies by adding them to the same entity,
+/// or by adding them to descendants of the rigid body entity. This can be used
+/// to create bodies with multiple colliders, each with its own [`Transform`] offset,
+/// material properties and collision events.
+///
+/// This component is added and updated automatically. Colliders that aren't attached to
+/// any rigid body don't have a [`ColliderParent`] and act like static bodies.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // Spawn a dynamic body with a collider on the body itself
+///     // and two child colliders offset from the body
+///     commands
+///         .spawn((RigidBody::Dynamic, Collider::ball(0.5)))
+///         .with_children(|children| {
+///             children.spawn((
+///                 Collider::ball(0.5),
+///                 TransformBundle::from_transform(Transform::from_xyz(-1.0, 0.0, 0.0)),
+///             ));
+///             children.spawn((
+///                 Collider::ball(0.5),
+///                 TransformBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+///             ));
+///         });
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq, Eq)]
+pub struct ColliderParent(pub(crate) Entity);
+
+impl ColliderParent {
+    /// Returns the [rigid body](RigidBody) entity that the collider is attached to.
+    pub const fn get(&self) -> Entity {
+        self.0
+    }
+}
+
+/// The translation and rotation of a [`Collider`] relative to the [rigid body](RigidBody)
+/// it is attached to, computed from the `Transform` hierarchy between the collider and the body.
+/// The translation includes the global scale of the body, so it is in world units.
+///
+/// For colliders on the same entity as the rigid body, this is the identity transform.
+///
+/// This component is added and updated automatically for colliders with a [`ColliderParent`].
+#[derive(Reflect, Clone, Copy, Component, Debug, Default)]
+#[reflect(Component)]
+pub struct ColliderTransform {
+    /// The translation of the collider in the local space of the rigid body.
+    pub translation: Vector,
+    /// The rotation of the collider in the local space of the rigid body.
+    pub rotation: Rotation,
+}
+
+impl ColliderTransform {
+    /// Transforms a point from the local space of the collider to the local space of the rigid body.
+    pub fn transform_point(&self, point: Vector) -> Vector {
+        self.translation + self.rotation.rotate(point)
+    }
+
+    /// Rotates a vector from the local space of the collider to the local space of the rigid body.
+    pub fn transform_vector(&self, vector: Vector) -> Vector {
+        self.rotation.rotate(vector)
+    }
+
+    /// Computes the global position and rotation of the collider
+    /// from the position and rotation of the rigid body it is attached to.
+    pub fn global_pose(
+        &self,
+        body_position: Vector,
+        body_rotation: &Rotation,
+    ) -> (Vector, Rotation) {
+        (
+            body_position + body_rotation.rotate(self.translation),
+            body_rotation.mul(self.rotation),
+        )
+    }
+}
+
 /// The Axis-Aligned Bounding Box of a collider.
 #[derive(Clone, Copy, Component, Debug, Deref, DerefMut, PartialEq)]
 pub struct ColliderAabb(pub Aabb);
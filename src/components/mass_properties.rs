@@ -58,7 +58,6 @@ impl Inertia {
 
     /// In 2D this does nothing, but it is there for convenience so that you don't have to handle 2D and 3D separately.
     #[cfg(feature = "2d")]
-    #[allow(dead_code)]
     pub(crate) fn rotated(&self, _rot: &Rotation) -> Self {
         *self
     }
@@ -268,6 +267,17 @@ impl ColliderMassProperties {
     }
 }
 
+impl ColliderMassProperties {
+    /// Transforms the center of mass and inertia from the local space of a collider
+    /// to the local space of the [rigid body](RigidBody) that the collider is attached to.
+    pub fn transformed_by(mut self, transform: &ColliderTransform) -> Self {
+        self.center_of_mass.0 = transform.transform_point(self.center_of_mass.0);
+        self.inertia = self.inertia.rotated(&transform.rotation);
+        self.inverse_inertia = self.inverse_inertia.rotated(&transform.rotation);
+        self
+    }
+}
+
 impl Default for ColliderMassProperties {
     fn default() -> Self {
         Self::ZERO
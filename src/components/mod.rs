@@ -156,6 +156,45 @@ pub struct TimeSleeping(pub Scalar);
 #[reflect(Component)]
 pub struct SleepingDisabled;
 
+/// Enables swept **Continuous Collision Detection** (CCD) for a [rigid body](RigidBody).
+///
+/// Normally, collisions are only detected at the discrete positions that a body has at each substep.
+/// Small and fast bodies like projectiles can move past thin objects between two substeps,
+/// which is known as *tunnelling*.
+///
+/// With [`SweptCcd`], the body's [colliders](Collider) are swept from its [`PreviousPosition`] and [`PreviousRotation`]
+/// to its new position and rotation during each substep. If the sweep hits something, the motion of the body
+/// is clamped at the first time of impact so that the contact can be resolved by the [solver].
+///
+/// CCD is more expensive than normal collision detection, so it should only be enabled for bodies that need it.
+/// It is only used for dynamic bodies that move more than a fraction of their own size during a substep.
+///
+/// See [`CcdPlugin`] for more information.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // Spawn a fast projectile that doesn't tunnel through thin walls
+///     commands.spawn((
+///         RigidBody::Dynamic,
+///         Collider::ball(0.05),
+///         LinearVelocity(Vec3::X * 500.0),
+///         SweptCcd,
+///     ));
+/// }
+/// ```
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, Eq, From)]
+#[reflect(Component)]
+pub struct SweptCcd;
+
 /// The position of a body.
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq, From)]
 #[reflect(Component)]
@@ -192,6 +231,13 @@ impl LinearVelocity {
 #[reflect(Component)]
 pub(crate) struct PreSolveLinearVelocity(pub Vector);
 
+/// Radians per second
+#[cfg(feature = "2d")]
+pub(crate) type AngularValue = Scalar;
+/// Rotation axis scaled by the angular speed in radians per second
+#[cfg(feature = "3d")]
+pub(crate) type AngularValue = Vector;
+
 /// The angular velocity of a body in radians. Positive values will result in counterclockwise rotation.
 #[cfg(feature = "2d")]
 #[derive(Reflect, Clone, Copy, Component, Debug, Default, PartialEq, From)]
@@ -33,7 +33,7 @@ pub(crate) type RotationValue = Quaternion;
 /// }
 /// ```
 #[cfg(feature = "2d")]
-#[derive(Reflect, Clone, Copy, Component, Debug)]
+#[derive(Reflect, Clone, Copy, Component, Debug, PartialEq)]
 #[reflect(Component)]
 pub struct Rotation {
     /// The cosine of the rotation angle in radians.
@@ -57,7 +57,7 @@ pub struct Rotation {
 /// }
 /// ```
 #[cfg(feature = "3d")]
-#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut)]
+#[derive(Reflect, Clone, Copy, Component, Debug, Default, Deref, DerefMut, PartialEq)]
 #[reflect(Component)]
 pub struct Rotation(pub Quaternion);
 
@@ -152,6 +152,11 @@ impl Rotation {
     pub fn inverse(&self) -> Self {
         Self(self.0.inverse())
     }
+
+    /// Multiplies the rotation by another rotation. The resulting rotation applies `rhs` first.
+    pub fn mul(&self, rhs: Self) -> Self {
+        Self(self.0 * rhs.0)
+    }
 }
 
 #[cfg(feature = "2d")]
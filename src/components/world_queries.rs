@@ -87,20 +87,66 @@ pub(crate) struct ColliderQuery {
 
 impl<'w> AddAssign<ColliderMassProperties> for MassPropertiesQueryItem<'w> {
     fn add_assign(&mut self, rhs: ColliderMassProperties) {
-        self.mass.0 += rhs.mass.0;
+        let mass1 = self.mass.0;
+        let mass2 = rhs.mass.0;
+        let new_mass = mass1 + mass2;
+
+        // The new center of mass is the mass-weighted average of the centers of mass
+        let new_center_of_mass = if new_mass > Scalar::EPSILON {
+            (self.center_of_mass.0 * mass1 + rhs.center_of_mass.0 * mass2) / new_mass
+        } else {
+            self.center_of_mass.0 + rhs.center_of_mass.0
+        };
+
+        // Shift both inertias to the new center of mass using the parallel axis theorem
+        self.inertia.0 = self.inertia.0
+            + parallel_axis_shift(mass1, new_center_of_mass - self.center_of_mass.0)
+            + rhs.inertia.0
+            + parallel_axis_shift(mass2, new_center_of_mass - rhs.center_of_mass.0);
+
+        self.mass.0 = new_mass;
         self.inverse_mass.0 = 1.0 / self.mass.0;
-        self.inertia.0 += rhs.inertia.0;
         self.inverse_inertia.0 = self.inertia.inverse().0;
-        self.center_of_mass.0 += rhs.center_of_mass.0;
+        self.center_of_mass.0 = new_center_of_mass;
     }
 }
 
 impl<'w> SubAssign<ColliderMassProperties> for MassPropertiesQueryItem<'w> {
     fn sub_assign(&mut self, rhs: ColliderMassProperties) {
-        self.mass.0 -= rhs.mass.0;
+        let mass2 = rhs.mass.0;
+        let new_mass = self.mass.0 - mass2;
+
+        let new_center_of_mass = if new_mass > Scalar::EPSILON {
+            (self.center_of_mass.0 * self.mass.0 - rhs.center_of_mass.0 * mass2) / new_mass
+        } else {
+            self.center_of_mass.0 - rhs.center_of_mass.0
+        };
+
+        // Remove the collider's inertia around the current center of mass,
+        // and shift the remaining inertia to the new center of mass
+        self.inertia.0 = self.inertia.0
+            - rhs.inertia.0
+            - parallel_axis_shift(mass2, self.center_of_mass.0 - rhs.center_of_mass.0)
+            - parallel_axis_shift(new_mass, self.center_of_mass.0 - new_center_of_mass);
+
+        self.mass.0 = new_mass;
         self.inverse_mass.0 = 1.0 / self.mass.0;
-        self.inertia.0 -= rhs.inertia.0;
         self.inverse_inertia.0 = self.inertia.inverse().0;
-        self.center_of_mass.0 -= rhs.center_of_mass.0;
+        self.center_of_mass.0 = new_center_of_mass;
     }
 }
+
+/// Computes the inertia that needs to be added to an inertia around a body's center of mass
+/// to get the inertia around a point that is `offset` away from the center of mass.
+#[cfg(feature = "2d")]
+fn parallel_axis_shift(mass: Scalar, offset: Vector) -> Scalar {
+    mass * offset.length_squared()
+}
+
+/// Computes the inertia tensor that needs to be added to an inertia tensor around a body's center of mass
+/// to get the inertia tensor around a point that is `offset` away from the center of mass.
+#[cfg(feature = "3d")]
+fn parallel_axis_shift(mass: Scalar, offset: Vector) -> Matrix3 {
+    mass * (Matrix3::from_diagonal(Vector::splat(offset.length_squared()))
+        - Matrix3::from_cols(offset * offset.x, offset * offset.y, offset * offset.z))
+}
@@ -0,0 +1,688 @@
+//! [`D6Joint`] component.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// The number of translational degrees of freedom.
+#[cfg(feature = "2d")]
+const LINEAR_DOF: usize = 2;
+/// The number of translational degrees of freedom.
+#[cfg(feature = "3d")]
+const LINEAR_DOF: usize = 3;
+
+/// The number of rotational degrees of freedom.
+#[cfg(feature = "2d")]
+const ANGULAR_DOF: usize = 1;
+/// The number of rotational degrees of freedom.
+#[cfg(feature = "3d")]
+const ANGULAR_DOF: usize = 3;
+
+/// The local axes of the first body that translation is measured along.
+#[cfg(feature = "2d")]
+const LINEAR_AXES: [Vector; LINEAR_DOF] = [Vector::X, Vector::Y];
+/// The local axes of the first body that translation is measured along.
+#[cfg(feature = "3d")]
+const LINEAR_AXES: [Vector; LINEAR_DOF] = [Vector::X, Vector::Y, Vector::Z];
+
+/// The local axes of the first body that rotation is measured around.
+#[cfg(feature = "2d")]
+const ANGULAR_AXES: [Vector3; ANGULAR_DOF] = [Vector3::Z];
+/// The local axes of the first body that rotation is measured around.
+#[cfg(feature = "3d")]
+const ANGULAR_AXES: [Vector3; ANGULAR_DOF] = [Vector3::X, Vector3::Y, Vector3::Z];
+
+/// An axis of a [`D6Joint`], relative to the first body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum D6Axis {
+    /// The x-axis of the first body.
+    X,
+    /// The y-axis of the first body.
+    Y,
+    /// The z-axis of the first body.
+    #[cfg(feature = "3d")]
+    Z,
+}
+
+/// The relative motion that a [`D6Joint`] allows along or around one of its axes.
+///
+/// For translational axes, the limits are given as a [`DistanceLimit`], and for rotational axes
+/// they are given as an [`AngleLimit`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AxisMotion<Limit> {
+    /// No relative motion is allowed.
+    #[default]
+    Locked,
+    /// Relative motion is allowed inside the given limits.
+    Limited(Limit),
+    /// Relative motion is not restricted.
+    Free,
+}
+
+/// A D6 joint is a configurable joint where each of the six degrees of freedom can be independently
+/// [locked, limited or free](AxisMotion). In 2D, the joint has two translational axes and one rotational axis.
+///
+/// The axes are the local axes of the first body, and translation is measured between the attachment points
+/// of the bodies. By default, all axes are locked, which makes the joint behave like a [`FixedJoint`].
+///
+/// In 3D, if more than one rotational axis is unlocked, rotation around the x-axis is measured as *twist*,
+/// and rotation around the y- and z-axes is measured as *swing* of the x-axis. Swing angles should then stay
+/// below 90 degrees, as larger angles are ambiguous.
+///
+/// Each axis can also have a [drive](JointMotor) that drives the relative motion along or around it
+/// towards a target velocity or position.
+///
+/// D6 joints can be useful for things like ragdolls, vehicle suspensions and other mechanisms
+/// that the other joints can't express.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// # use bevy_xpbd_3d::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     let chassis = commands.spawn(RigidBody::Dynamic).id();
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // A suspension that lets the wheel move up and down and spin freely
+///     let joint = D6Joint::new(chassis, wheel)
+///         .with_linear_motion(D6Axis::Y, AxisMotion::Limited(DistanceLimit::new(-0.5, 0.0)))
+///         .with_linear_drive(D6Axis::Y, JointMotor::position(-0.25).with_compliance(0.001));
+///     #[cfg(feature = "2d")]
+///     let joint = joint.with_angular_motion(AxisMotion::Free);
+///     #[cfg(feature = "3d")]
+///     let joint = joint.with_angular_motion(D6Axis::X, AxisMotion::Free);
+///
+///     commands.spawn(joint);
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct D6Joint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The relative translation allowed along the local axes of the first body.
+    pub linear_motion: [AxisMotion<DistanceLimit>; LINEAR_DOF],
+    /// The relative rotation allowed around the local axes of the first body.
+    ///
+    /// In 2D, this only contains the rotation around the z-axis.
+    pub angular_motion: [AxisMotion<AngleLimit>; ANGULAR_DOF],
+    /// Drives that drive the relative translation along the local axes of the first body.
+    pub linear_drives: [Option<JointMotor>; LINEAR_DOF],
+    /// Drives that drive the relative rotation around the local axes of the first body.
+    pub angular_drives: [Option<JointMotor>; ANGULAR_DOF],
+    /// Linear damping applied by the joint.
+    pub damping_linear: Scalar,
+    /// Angular damping applied by the joint.
+    pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
+    /// Lagrange multiplier for the positional correction.
+    pub position_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
+    pub align_lagrange: Scalar,
+    /// Lagrange multipliers for the angular corrections around each axis.
+    pub angular_lagranges: [Scalar; ANGULAR_DOF],
+    /// Lagrange multipliers for the positional corrections caused by the linear drives.
+    pub linear_drive_lagranges: [Scalar; LINEAR_DOF],
+    /// Lagrange multipliers for the angular corrections caused by the angular drives.
+    pub angular_drive_lagranges: [Scalar; ANGULAR_DOF],
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the joint when limiting the relative translation of the bodies.
+    pub force: Vector,
+    /// The torque exerted by the joint when limiting the relative rotation of the bodies.
+    pub torque: Torque,
+    /// The force exerted by the linear drives.
+    pub drive_force: Vector,
+    /// The torque exerted by the angular drives.
+    pub drive_torque: Torque,
+}
+
+impl XpbdConstraint<2> for D6Joint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.position_lagrange = 0.0;
+        self.align_lagrange = 0.0;
+        self.angular_lagranges = [0.0; ANGULAR_DOF];
+        self.linear_drive_lagranges = [0.0; LINEAR_DOF];
+        self.angular_drive_lagranges = [0.0; ANGULAR_DOF];
+        self.force = Vector::ZERO;
+        self.torque = Torque::ZERO;
+        self.drive_force = Vector::ZERO;
+        self.drive_torque = Torque::ZERO;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+        // The forces and torques are only updated when their Lagrange multipliers change.
+        // Otherwise, the constraints were already satisfied by earlier iterations of the substep,
+        // and the values computed from the accumulated multipliers are kept.
+
+        // Lock or limit the relative rotation around each axis
+        let lagranges = (self.align_lagrange, self.angular_lagranges);
+        let torque = self.constrain_angles(body1, body2, dt);
+        if lagranges != (self.align_lagrange, self.angular_lagranges) {
+            self.torque = torque;
+        }
+
+        // Drive the relative motion along and around each axis
+        let lagranges = self.angular_drive_lagranges;
+        let torque = (0..ANGULAR_DOF).fold(Torque::ZERO, |torque, i| {
+            torque + self.apply_angular_drive(body1, body2, i, dt)
+        });
+        if lagranges != self.angular_drive_lagranges {
+            self.drive_torque = torque;
+        }
+        let lagranges = self.linear_drive_lagranges;
+        let force = (0..LINEAR_DOF).fold(Vector::ZERO, |force, i| {
+            force + self.apply_linear_drive(body1, body2, i, dt)
+        });
+        if lagranges != self.linear_drive_lagranges {
+            self.drive_force = force;
+        }
+
+        // Lock or limit the relative translation along each axis
+        let lagrange = self.position_lagrange;
+        let force = self.constrain_positions(body1, body2, dt);
+        if lagrange != self.position_lagrange {
+            self.force = force;
+        }
+    }
+}
+
+impl Joint for D6Joint {
+    fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            linear_motion: [AxisMotion::Locked; LINEAR_DOF],
+            angular_motion: [AxisMotion::Locked; ANGULAR_DOF],
+            linear_drives: [None; LINEAR_DOF],
+            angular_drives: [None; ANGULAR_DOF],
+            damping_linear: 1.0,
+            damping_angular: 1.0,
+            break_force: None,
+            break_torque: None,
+            position_lagrange: 0.0,
+            align_lagrange: 0.0,
+            angular_lagranges: [0.0; ANGULAR_DOF],
+            linear_drive_lagranges: [0.0; LINEAR_DOF],
+            angular_drive_lagranges: [0.0; ANGULAR_DOF],
+            compliance: 0.0,
+            force: Vector::ZERO,
+            torque: Torque::ZERO,
+            drive_force: Vector::ZERO,
+            drive_torque: Torque::ZERO,
+        }
+    }
+
+    fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    fn local_anchor_1(&self) -> Vector {
+        self.local_anchor1
+    }
+
+    fn local_anchor_2(&self) -> Vector {
+        self.local_anchor2
+    }
+
+    fn damping_linear(&self) -> Scalar {
+        self.damping_linear
+    }
+
+    fn damping_angular(&self) -> Scalar {
+        self.damping_angular
+    }
+}
+
+impl BreakableJoint for D6Joint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force + self.drive_force
+    }
+
+    fn torque(&self) -> Torque {
+        self.torque + self.drive_torque
+    }
+}
+
+impl D6Joint {
+    /// Sets the relative translation allowed along the given axis.
+    pub fn with_linear_motion(mut self, axis: D6Axis, motion: AxisMotion<DistanceLimit>) -> Self {
+        self.linear_motion[axis as usize] = motion;
+        self
+    }
+
+    /// Sets the relative rotation allowed around the z-axis.
+    #[cfg(feature = "2d")]
+    pub fn with_angular_motion(mut self, motion: AxisMotion<AngleLimit>) -> Self {
+        self.angular_motion[0] = motion;
+        self
+    }
+
+    /// Sets the relative rotation allowed around the given axis.
+    #[cfg(feature = "3d")]
+    pub fn with_angular_motion(mut self, axis: D6Axis, motion: AxisMotion<AngleLimit>) -> Self {
+        self.angular_motion[axis as usize] = motion;
+        self
+    }
+
+    /// Sets the [drive](JointMotor) that drives the relative translation along the given axis.
+    pub fn with_linear_drive(mut self, axis: D6Axis, drive: JointMotor) -> Self {
+        self.linear_drives[axis as usize] = Some(drive);
+        self
+    }
+
+    /// Sets the [drive](JointMotor) that drives the relative rotation around the z-axis.
+    #[cfg(feature = "2d")]
+    pub fn with_angular_drive(mut self, drive: JointMotor) -> Self {
+        self.angular_drives[0] = Some(drive);
+        self
+    }
+
+    /// Sets the [drive](JointMotor) that drives the relative rotation around the given axis.
+    #[cfg(feature = "3d")]
+    pub fn with_angular_drive(mut self, axis: D6Axis, drive: JointMotor) -> Self {
+        self.angular_drives[axis as usize] = Some(drive);
+        self
+    }
+
+    /// Returns the number of rotational axes that aren't locked.
+    #[cfg(feature = "3d")]
+    fn unlocked_angular_axes(&self) -> usize {
+        self.angular_motion
+            .iter()
+            .filter(|motion| !matches!(motion, AxisMotion::Locked))
+            .count()
+    }
+
+    /// Returns the world-space axis with the given index and the relative rotation
+    /// of the bodies around it in radians.
+    fn compute_angle(&self, rot1: &Rotation, rot2: &Rotation, index: usize) -> (Vector3, Scalar) {
+        let axis = ANGULAR_AXES[index];
+
+        #[cfg(feature = "3d")]
+        if self.unlocked_angular_axes() > 1 {
+            return if index == 0 {
+                // Twist around the average x-axis of the bodies, measured from the y-axis
+                let n = (rot1.rotate_vec3(axis) + rot2.rotate_vec3(axis)).normalize_or_zero();
+                let b1 = rot1.rotate_vec3(Vector3::Y).reject_from(n);
+                let b2 = rot2.rotate_vec3(Vector3::Y).reject_from(n);
+                (n, b1.cross(b2).dot(n).atan2(b1.dot(b2)))
+            } else {
+                // Swing of the x-axis around the y- or z-axis of the first body
+                let n = rot1.rotate_vec3(axis);
+                let a1 = rot1.rotate_vec3(Vector3::X);
+                let a2 = rot2.rotate_vec3(Vector3::X);
+                (n, a1.cross(a2).dot(n).atan2(a1.dot(a2)))
+            };
+        }
+
+        // The angle is measured from the next axis, like the angle of a revolute joint
+        let reference_axis = Vector3::new(axis.z, axis.x, axis.y);
+
+        let n = rot1.rotate_vec3(axis);
+        let a1 = rot1.rotate_vec3(reference_axis);
+        let a2 = rot2.rotate_vec3(reference_axis);
+        (n, a1.cross(a2).dot(n).atan2(a1.dot(a2)))
+    }
+
+    /// Returns the world-space positions of the attachment points and their offsets from the bodies.
+    fn compute_anchors(
+        &self,
+        body1: &RigidBodyQueryItem,
+        body2: &RigidBodyQueryItem,
+    ) -> (Vector, Vector, Vector, Vector) {
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        (
+            body1.position.0 + body1.accumulated_translation.0 + world_r1,
+            body2.position.0 + body2.accumulated_translation.0 + world_r2,
+            world_r1,
+            world_r2,
+        )
+    }
+
+    /// Locks or limits the relative rotation of the bodies around each axis.
+    ///
+    /// Returns the torque exerted by this constraint.
+    fn constrain_angles(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        // If at most one axis is unlocked, the bodies are aligned like in a fixed or revolute joint
+        #[cfg(feature = "3d")]
+        if self.unlocked_angular_axes() <= 1 {
+            let free_axis = self
+                .angular_motion
+                .iter()
+                .position(|motion| !matches!(motion, AxisMotion::Locked));
+            let delta_q = match free_axis {
+                Some(index) => {
+                    let a1 = body1.rotation.rotate_vec3(ANGULAR_AXES[index]);
+                    let a2 = body2.rotation.rotate_vec3(ANGULAR_AXES[index]);
+                    a1.cross(a2)
+                }
+                None => 2.0 * (body1.rotation.0 * body2.rotation.inverse().0).xyz(),
+            };
+
+            let mut lagrange = self.align_lagrange;
+            let torque =
+                self.align_orientation(body1, body2, delta_q, &mut lagrange, self.compliance, dt);
+            self.align_lagrange = lagrange;
+
+            return match free_axis {
+                Some(index) => torque + self.constrain_angle(body1, body2, index, dt),
+                None => torque,
+            };
+        }
+
+        (0..ANGULAR_DOF).fold(Torque::ZERO, |torque, i| {
+            torque + self.constrain_angle(body1, body2, i, dt)
+        })
+    }
+
+    /// Locks or limits the relative rotation of the bodies around the axis with the given index.
+    ///
+    /// Returns the torque exerted by this constraint.
+    fn constrain_angle(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        index: usize,
+        dt: Scalar,
+    ) -> Torque {
+        let (n, angle) = self.compute_angle(&body1.rotation, &body2.rotation, index);
+
+        let c = match self.angular_motion[index] {
+            AxisMotion::Free => return Torque::ZERO,
+            AxisMotion::Locked => angle,
+            AxisMotion::Limited(limit) => angle - angle.clamp(limit.alpha, limit.beta),
+        };
+
+        let mut lagrange = self.angular_lagranges[index];
+        let torque = self.apply_angular_constraint(
+            body1,
+            body2,
+            n,
+            c,
+            &mut lagrange,
+            self.compliance,
+            None,
+            dt,
+        );
+        self.angular_lagranges[index] = lagrange;
+        torque
+    }
+
+    /// Drives the relative rotation of the bodies around the axis with the given index.
+    ///
+    /// Returns the torque exerted by the drive.
+    fn apply_angular_drive(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        index: usize,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(drive) = self.angular_drives[index] else {
+            return Torque::ZERO;
+        };
+
+        let (n, angle) = self.compute_angle(&body1.rotation, &body2.rotation, index);
+        let (_, previous_angle) = self.compute_angle(
+            &body1.previous_rotation.0,
+            &body2.previous_rotation.0,
+            index,
+        );
+
+        // Wrap the error to [-PI, PI] so that the drive takes the shortest way around
+        let mut c = drive.compute_error(angle, previous_angle, dt);
+        c = (c + PI).rem_euclid(2.0 * PI) - PI;
+
+        let mut lagrange = self.angular_drive_lagranges[index];
+        let torque = self.apply_angular_constraint(
+            body1,
+            body2,
+            n,
+            c,
+            &mut lagrange,
+            drive.compliance,
+            Some(drive),
+            dt,
+        );
+        self.angular_drive_lagranges[index] = lagrange;
+        torque
+    }
+
+    /// Applies an angular correction around `n` that reduces the rotation error `c`.
+    /// If a drive is given, the correction is limited by its maximum torque.
+    ///
+    /// Returns the torque exerted by the correction.
+    #[allow(clippy::too_many_arguments)]
+    fn apply_angular_constraint(
+        &self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        n: Vector3,
+        c: Scalar,
+        lagrange: &mut Scalar,
+        compliance: Scalar,
+        drive: Option<JointMotor>,
+        dt: Scalar,
+    ) -> Torque {
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, n);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, n);
+
+        // Constraint gradients and inverse masses
+        let gradients = {
+            #[cfg(feature = "2d")]
+            {
+                [Vector::Y * n.z, Vector::NEG_Y * n.z]
+            }
+            #[cfg(feature = "3d")]
+            {
+                [n, -n]
+            }
+        };
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let mut delta_lagrange =
+            self.compute_lagrange_update(*lagrange, c, &gradients, &w, compliance, dt);
+        if let Some(drive) = drive {
+            delta_lagrange = drive.clamp_lagrange_update(*lagrange, delta_lagrange, dt);
+        }
+        *lagrange += delta_lagrange;
+
+        // Apply angular correction
+        self.apply_angular_correction(body1, body2, delta_lagrange, n);
+
+        // Return constraint torque
+        self.compute_torque(*lagrange, n, dt)
+    }
+
+    /// Drives the relative translation of the bodies along the axis with the given index.
+    ///
+    /// Returns the force exerted by the drive.
+    fn apply_linear_drive(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        index: usize,
+        dt: Scalar,
+    ) -> Vector {
+        let Some(drive) = self.linear_drives[index] else {
+            return Vector::ZERO;
+        };
+
+        let (p1, p2, world_r1, world_r2) = self.compute_anchors(body1, body2);
+        let axis1 = body1.rotation.rotate(LINEAR_AXES[index]);
+
+        let previous_rot1 = body1.previous_rotation.0;
+        let previous_rot2 = body2.previous_rotation.0;
+        let previous_axis1 = previous_rot1.rotate(LINEAR_AXES[index]);
+        let previous_offset = body2.previous_position.0 + previous_rot2.rotate(self.local_anchor2)
+            - (body1.previous_position.0 + previous_rot1.rotate(self.local_anchor1));
+
+        let c = drive.compute_error(
+            (p2 - p1).dot(axis1),
+            previous_offset.dot(previous_axis1),
+            dt,
+        );
+
+        // The gradient of the error with respect to the position of the first body
+        let dir = -axis1;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update, limited by the maximum force of the drive
+        let lagrange = self.linear_drive_lagranges[index];
+        let delta_lagrange =
+            self.compute_lagrange_update(lagrange, c, &gradients, &w, drive.compliance, dt);
+        let delta_lagrange = drive.clamp_lagrange_update(lagrange, delta_lagrange, dt);
+        self.linear_drive_lagranges[index] += delta_lagrange;
+
+        // Apply positional correction towards the target
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return drive force
+        self.compute_force(self.linear_drive_lagranges[index], dir, dt)
+    }
+
+    /// Locks or limits the relative translation of the bodies along each axis.
+    ///
+    /// Returns the force exerted by this constraint.
+    fn constrain_positions(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let (p1, p2, world_r1, world_r2) = self.compute_anchors(body1, body2);
+
+        let mut delta_x = Vector::ZERO;
+
+        for (motion, axis) in self.linear_motion.iter().zip(LINEAR_AXES) {
+            let limit = match motion {
+                AxisMotion::Free => continue,
+                AxisMotion::Locked => DistanceLimit::ZERO,
+                AxisMotion::Limited(limit) => *limit,
+            };
+            delta_x += limit.compute_correction_along_axis(p1, p2, body1.rotation.rotate(axis));
+        }
+
+        let magnitude = delta_x.length();
+
+        if magnitude <= Scalar::EPSILON {
+            return Vector::ZERO;
+        }
+
+        let dir = delta_x / magnitude;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange = self.compute_lagrange_update(
+            self.position_lagrange,
+            magnitude,
+            &gradients,
+            &w,
+            self.compliance,
+            dt,
+        );
+        self.position_lagrange += delta_lagrange;
+
+        // Apply positional correction to move the bodies inside the limits
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return constraint force
+        self.compute_force(self.position_lagrange, dir, dt)
+    }
+}
+
+impl PositionConstraint for D6Joint {}
+
+impl AngularConstraint for D6Joint {}
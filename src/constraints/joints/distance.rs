@@ -24,6 +24,10 @@ pub struct DistanceJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
     /// Lagrange multiplier for the positional correction.
     pub lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
@@ -39,10 +43,18 @@ impl XpbdConstraint<2> for DistanceJoint {
 
     fn clear_lagrange_multipliers(&mut self) {
         self.lagrange = 0.0;
+        self.force = Vector::ZERO;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
-        self.force = self.constrain_length(bodies, dt);
+        // The force is only updated when the Lagrange multiplier changes. Otherwise, the distance
+        // was already satisfied by earlier iterations of the substep, and the force computed from
+        // the accumulated multiplier is kept.
+        let lagrange = self.lagrange;
+        let force = self.constrain_length(bodies, dt);
+        if lagrange != self.lagrange {
+            self.force = force;
+        }
     }
 }
 
@@ -57,6 +69,8 @@ impl Joint for DistanceJoint {
             length_limits: None,
             damping_linear: 0.0,
             damping_angular: 0.0,
+            break_force: None,
+            break_torque: None,
             lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
@@ -112,6 +126,34 @@ impl Joint for DistanceJoint {
     }
 }
 
+impl BreakableJoint for DistanceJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+}
+
 impl DistanceJoint {
     /// Constrains the distance the bodies with no constraint on their rotation.
     ///
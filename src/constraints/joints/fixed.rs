@@ -21,6 +21,10 @@ pub struct FixedJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
@@ -41,21 +45,29 @@ impl XpbdConstraint<2> for FixedJoint {
     fn clear_lagrange_multipliers(&mut self) {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
+        self.align_torque = Torque::ZERO;
+        self.force = Vector::ZERO;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
+        // The forces and torques are only updated when their Lagrange multipliers change.
+        // Otherwise, the constraints were already satisfied by earlier iterations of the substep,
+        // and the values computed from the accumulated multipliers are kept.
 
         // Align orientation
         let dq = self.get_delta_q(&body1.rotation, &body2.rotation);
         let mut lagrange = self.align_lagrange;
-        self.align_torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        let torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        if lagrange != self.align_lagrange {
+            self.align_torque = torque;
+        }
         self.align_lagrange = lagrange;
 
         // Align position of local attachment points
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        let force = self.align_position(
             body1,
             body2,
             self.local_anchor1,
@@ -64,6 +76,9 @@ impl XpbdConstraint<2> for FixedJoint {
             compliance,
             dt,
         );
+        if lagrange != self.position_lagrange {
+            self.force = force;
+        }
         self.position_lagrange = lagrange;
     }
 }
@@ -77,6 +92,8 @@ impl Joint for FixedJoint {
             local_anchor2: Vector::ZERO,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            break_force: None,
+            break_torque: None,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             compliance: 0.0,
@@ -137,6 +154,38 @@ impl Joint for FixedJoint {
     }
 }
 
+impl BreakableJoint for FixedJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque
+    }
+}
+
 impl FixedJoint {
     #[cfg(feature = "2d")]
     fn get_delta_q(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
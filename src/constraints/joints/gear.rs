@@ -0,0 +1,274 @@
+//! [`GearJoint`] component.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A gear joint couples the rotation of two bodies so that `angle1 + ratio * angle2` stays constant,
+/// where the angles are the total rotations of the bodies around their axes since the joint was created.
+///
+/// Unlike other joints, a gear joint doesn't restrict the movement of the bodies by itself. The bodies are
+/// normally also attached to something else with [revolute joints](RevoluteJoint), and the gear joint makes them
+/// rotate together like meshing gears. A positive `ratio` makes the bodies rotate in opposite directions.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::{math::*, prelude::*};
+/// # #[cfg(feature = "3d")]
+/// # use bevy_xpbd_3d::{math::*, prelude::*};
+/// #
+/// fn setup(mut commands: Commands) {
+///     let frame = commands.spawn(RigidBody::Static).id();
+///     let small_gear = commands.spawn(RigidBody::Dynamic).id();
+///     let large_gear = commands.spawn(RigidBody::Dynamic).id();
+///
+///     commands.spawn(RevoluteJoint::new(frame, small_gear));
+///     commands.spawn(
+///         RevoluteJoint::new(frame, large_gear).with_local_anchor_1(Vector::X * 3.0),
+///     );
+///
+///     // The large gear has twice the radius, so it rotates at half the speed of the small gear
+///     commands.spawn(GearJoint::new(small_gear, large_gear).with_ratio(2.0));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct GearJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// The local axis that the first body rotates around.
+    ///
+    /// In 2D this should always be the Z axis.
+    #[cfg(feature = "2d")]
+    pub(crate) local_axis1: Vector3,
+    /// The local axis that the first body rotates around.
+    #[cfg(feature = "3d")]
+    pub local_axis1: Vector,
+    /// The local axis that the second body rotates around.
+    ///
+    /// In 2D this should always be the Z axis.
+    #[cfg(feature = "2d")]
+    pub(crate) local_axis2: Vector3,
+    /// The local axis that the second body rotates around.
+    #[cfg(feature = "3d")]
+    pub local_axis2: Vector,
+    /// The gear ratio. The rotation of the second body multiplied by the ratio is coupled
+    /// to the opposite of the rotation of the first body.
+    pub ratio: Scalar,
+    /// The total rotation of the first body around its axis since the joint was created
+    /// or attached to different entities.
+    pub angle1: Scalar,
+    /// The total rotation of the second body around its axis since the joint was created
+    /// or attached to different entities.
+    pub angle2: Scalar,
+    /// The rotations of the bodies when the angles were last updated.
+    pub(crate) last_rotations: Option<[Rotation; 2]>,
+    /// The entities that the angles were last updated for.
+    pub(crate) last_entities: [Entity; 2],
+    /// Angular damping applied by the joint to the coupled rotation of the bodies.
+    pub damping_angular: Scalar,
+    /// Lagrange multiplier for the angular correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit radians / (Newton * meter).
+    pub compliance: Scalar,
+    /// The torque exerted by the joint on the first body.
+    pub torque: Torque,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
+}
+
+impl XpbdConstraint<2> for GearJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+        self.torque = Torque::ZERO;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        let [n1, n2] = self.world_axes(&body1.rotation, &body2.rotation);
+        self.update_angles(&body1.rotation, &body2.rotation);
+
+        let c = self.angle1 + self.ratio * self.angle2;
+
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, n1);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, n2);
+
+        // Constraint gradients and inverse masses
+        let gradients = {
+            #[cfg(feature = "2d")]
+            {
+                [Vector::Y * n1.z, Vector::Y * self.ratio * n2.z]
+            }
+            #[cfg(feature = "3d")]
+            {
+                [n1, self.ratio * n2]
+            }
+        };
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange =
+            self.compute_lagrange_update(self.lagrange, c, &gradients, &w, self.compliance, dt);
+        self.lagrange += delta_lagrange;
+
+        // Rotate the bodies along the gradients
+        if delta_lagrange.abs() > Scalar::EPSILON {
+            let inv_inertia1 = body1.effective_world_inv_inertia();
+            let inv_inertia2 = body2.effective_world_inv_inertia();
+
+            #[cfg(feature = "2d")]
+            let (p1, p2) = (delta_lagrange * n1.z, delta_lagrange * self.ratio * n2.z);
+            #[cfg(feature = "3d")]
+            let (p1, p2) = (delta_lagrange * n1, delta_lagrange * self.ratio * n2);
+
+            if body1.rb.is_dynamic() {
+                let rot1 = *body1.rotation;
+                *body1.rotation +=
+                    <Self as AngularConstraint>::get_delta_rot(rot1, inv_inertia1, p1);
+            }
+            if body2.rb.is_dynamic() {
+                let rot2 = *body2.rotation;
+                *body2.rotation +=
+                    <Self as AngularConstraint>::get_delta_rot(rot2, inv_inertia2, p2);
+            }
+        }
+
+        self.torque = self.compute_torque(self.lagrange, n1, dt);
+    }
+}
+
+/// Gear joints only exert torque, so they can only be broken by their `break_torque`.
+impl BreakableJoint for GearJoint {
+    /// Gear joints don't exert any force, so the break force is ignored.
+    fn with_break_force(self, _break_force: Scalar) -> Self {
+        self
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        None
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn torque(&self) -> Torque {
+        self.torque
+    }
+}
+
+impl GearJoint {
+    /// Creates a new gear joint between two entities with a ratio of 1.
+    pub fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            #[cfg(feature = "2d")]
+            local_axis1: Vector3::Z,
+            #[cfg(feature = "3d")]
+            local_axis1: Vector::Z,
+            #[cfg(feature = "2d")]
+            local_axis2: Vector3::Z,
+            #[cfg(feature = "3d")]
+            local_axis2: Vector::Z,
+            ratio: 1.0,
+            angle1: 0.0,
+            angle2: 0.0,
+            last_rotations: None,
+            last_entities: [entity1, entity2],
+            damping_angular: 0.0,
+            lagrange: 0.0,
+            compliance: 0.0,
+            torque: Torque::ZERO,
+            break_torque: None,
+        }
+    }
+
+    /// Sets the gear ratio.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the local axes that the first and second body rotate around.
+    #[cfg(feature = "3d")]
+    pub fn with_local_axes(self, axis1: Vector, axis2: Vector) -> Self {
+        Self {
+            local_axis1: axis1,
+            local_axis2: axis2,
+            ..self
+        }
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, radians / (Newton * meter)).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Sets the angular velocity damping caused by the joint.
+    pub fn with_angular_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_angular: damping,
+            ..self
+        }
+    }
+
+    /// Returns the world-space axes that the bodies rotate around.
+    pub(crate) fn world_axes(&self, rot1: &Rotation, rot2: &Rotation) -> [Vector3; 2] {
+        [
+            rot1.rotate_vec3(self.local_axis1),
+            rot2.rotate_vec3(self.local_axis2),
+        ]
+    }
+
+    /// Adds the rotation of the bodies around their axes since the last update to `angle1` and `angle2`.
+    ///
+    /// If the joint was attached to different entities, the angles are measured from the current rotations again.
+    fn update_angles(&mut self, rot1: &Rotation, rot2: &Rotation) {
+        if self.last_entities != self.entities() {
+            self.angle1 = 0.0;
+            self.angle2 = 0.0;
+            self.last_rotations = None;
+            self.last_entities = self.entities();
+        }
+
+        if let Some([last_rot1, last_rot2]) = self.last_rotations {
+            let [n1, n2] = self.world_axes(rot1, rot2);
+            self.angle1 += Self::rotation_around_axis(rot1, &last_rot1, n1);
+            self.angle2 += Self::rotation_around_axis(rot2, &last_rot2, n2);
+        }
+        self.last_rotations = Some([*rot1, *rot2]);
+    }
+
+    /// Returns the angle that a body has rotated around `axis` from `last_rot` to `rot`.
+    #[cfg(feature = "2d")]
+    fn rotation_around_axis(rot: &Rotation, last_rot: &Rotation, axis: Vector3) -> Scalar {
+        (*rot - *last_rot).as_radians() * axis.z
+    }
+
+    /// Returns the angle that a body has rotated around `axis` from `last_rot` to `rot`.
+    #[cfg(feature = "3d")]
+    fn rotation_around_axis(rot: &Rotation, last_rot: &Rotation, axis: Vector) -> Scalar {
+        let delta_rot = rot.0 * last_rot.0.inverse();
+        let angle = 2.0 * delta_rot.xyz().dot(axis).atan2(delta_rot.w);
+        // Wrap the angle to [-PI, PI]
+        (angle + PI).rem_euclid(2.0 * PI) - PI
+    }
+}
+
+impl AngularConstraint for GearJoint {}
@@ -20,6 +20,7 @@
 //! | [`PrismaticJoint`] | 1 Translation             | 1 Translation               |
 //! | [`RevoluteJoint`]  | 1 Rotation                | 1 Rotation                  |
 //! | [`SphericalJoint`] | 1 Rotation                | 3 Rotations                 |
+//! | [`D6Joint`]        | Configurable              | Configurable                |
 //!
 //! ## Using joints
 //!
@@ -56,19 +57,53 @@
 //! You can use `with_local_anchor_1` and `with_local_anchor_2` to set the attachment positions on the first
 //! and second entity respectively.
 //!
+//! ### Attaching to the world
+//!
+//! To pin a body to a fixed point in the world, attach the joint to the entity of the [`WorldBody`] resource
+//! instead of spawning a static helper entity. The world body is at the origin and has no rotation,
+//! so its local anchor is the attachment point in world space.
+//!
+//! The entities and anchors of a joint are public fields, so a joint can be moved between the world
+//! and another body at runtime by changing them, without respawning the joint.
+//!
 //! ### Damping
 //!
 //! You can configure the linear and angular damping caused by joints using the `with_linear_velocity_damping` and
 //! `with_angular_velocity_damping` methods. Increasing the damping values will cause the velocities
 //! of the connected entities to decrease faster.
 //!
+//! ### Motors
+//!
+//! [Revolute joints](RevoluteJoint) and [prismatic joints](PrismaticJoint) can drive the relative motion of
+//! the attached bodies around or along their free axis using a [`JointMotor`]. A motor can either target
+//! a velocity or a position, and it has its own compliance and a maximum force or torque.
+//!
+//! ### Breaking
+//!
+//! Joints can break when the force or torque that they exert during a substep exceeds a threshold.
+//! The thresholds are set with the `with_break_force` and `with_break_torque` methods of the [`BreakableJoint`] trait.
+//! When a joint breaks, its component is removed and a [`JointBroken`] event is sent.
+//!
 //! ### Other configuration
 //!
+//! If none of the joints above restrict the degrees of freedom that you need, you can use a [`D6Joint`],
+//! where each translational and rotational axis can be independently locked, limited or free.
+//!
 //! Different joints may have different configuration options. Many joints allow you to change the axis of allowed
 //! translation or rotation, and they may have distance or angle limits along these axes.
 //!
 //! Take a look at the documentation and methods of each joint to see all of the configuration options.
 //!
+//! ## Coupling joints
+//!
+//! Some joints couple the motion of bodies instead of restricting it. A [`GearJoint`] links the rotation of
+//! two bodies by a ratio, and a [`PulleyJoint`] keeps the total length of a rope running over two pulley points constant.
+//!
+//! Coupling joints don't implement the [`Joint`] trait, but they have the same `with_compliance` method and
+//! the velocity damping methods that make sense for them. They can break like other joints,
+//! as they implement the [`BreakableJoint`] trait, but a gear joint only exerts torque and a pulley joint
+//! only exerts force, so the other threshold is ignored.
+//!
 //! ## Custom joints
 //!
 //! Joints are [constraints] that implement [`Joint`] and [`XpbdConstraint`].
@@ -83,15 +118,21 @@
 //! [See the code implementations](https://github.com/Jondolf/bevy_xpbd/tree/main/src/constraints/joints)
 //! of the implemented joints to get a better idea of how to create joints.
 
+mod d6;
 mod distance;
 mod fixed;
+mod gear;
 mod prismatic;
+mod pulley;
 mod revolute;
 mod spherical;
 
+pub use d6::*;
 pub use distance::*;
 pub use fixed::*;
+pub use gear::*;
 pub use prismatic::*;
+pub use pulley::*;
 pub use revolute::*;
 pub use spherical::*;
 
@@ -232,6 +273,234 @@ pub trait Joint: Component + PositionConstraint + AngularConstraint {
     }
 }
 
+/// A trait for [joints] that break when the force or torque that they exert exceeds a threshold.
+///
+/// Breakable joints need to be registered with [`break_joints`] in the [`SubstepSchedule`](crate::SubstepSchedule),
+/// which is done for the built-in joints by the [`SolverPlugin`].
+///
+/// Unlike [`Joint`], the trait is also implemented by coupling joints like [`GearJoint`] and [`PulleyJoint`].
+pub trait BreakableJoint: Component + XpbdConstraint<2> {
+    /// Sets the force that breaks the joint when exceeded.
+    fn with_break_force(self, break_force: Scalar) -> Self;
+
+    /// Sets the torque that breaks the joint when exceeded.
+    fn with_break_torque(self, break_torque: Scalar) -> Self;
+
+    /// Returns the force that breaks the joint when exceeded, or `None` if the force can't break the joint.
+    fn break_force(&self) -> Option<Scalar>;
+
+    /// Returns the torque that breaks the joint when exceeded, or `None` if the torque can't break the joint.
+    fn break_torque(&self) -> Option<Scalar>;
+
+    /// Returns the total force exerted by the joint during the last substep.
+    ///
+    /// Joints that don't keep track of their force return zero by default.
+    fn force(&self) -> Vector {
+        Vector::ZERO
+    }
+
+    /// Returns the total torque exerted by the joint during the last substep.
+    ///
+    /// Joints that don't keep track of their torque return zero by default.
+    fn torque(&self) -> Torque {
+        Torque::ZERO
+    }
+
+    /// Returns true if the force or torque exerted by the joint exceeds its `break_force` or `break_torque`.
+    fn is_broken(&self) -> bool {
+        #[cfg(feature = "2d")]
+        let torque = self.torque().abs();
+        #[cfg(feature = "3d")]
+        let torque = self.torque().length();
+
+        self.break_force()
+            .is_some_and(|break_force| self.force().length() > break_force)
+            || self
+                .break_torque()
+                .is_some_and(|break_torque| torque > break_torque)
+    }
+}
+
+/// An event that is sent when a joint breaks because the force or torque exerted by it
+/// exceeded its `break_force` or `break_torque`. The joint component is removed from the `joint` entity.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct JointBroken {
+    /// The entity of the joint.
+    pub joint: Entity,
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// The force exerted by the joint when it broke.
+    pub force: Vector,
+    /// The torque exerted by the joint when it broke.
+    pub torque: Torque,
+}
+
+/// A resource containing a static body that represents the world frame.
+///
+/// The world body is at the origin and has no rotation, so the local anchor of a joint attached to it
+/// is a point in world space. It has no collider and is treated as having infinite mass like other
+/// static bodies. You shouldn't move the world body or add components to it.
+///
+/// The world body must not be despawned. If it is, a new world body is spawned at the start of the next
+/// physics step and stored in the resource, but joints attached to the old entity are not moved to it.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::{math::*, prelude::*};
+/// # #[cfg(feature = "3d")]
+/// # use bevy_xpbd_3d::{math::*, prelude::*};
+/// #
+/// fn setup(mut commands: Commands, world_body: Res<WorldBody>) {
+///     let body = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Pin the body to the world point (0, 5)
+///     commands.spawn(
+///         SphericalJoint::new(body, world_body.entity()).with_local_anchor_2(Vector::Y * 5.0),
+///     );
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldBody(Entity);
+
+impl WorldBody {
+    /// Returns the entity of the world body.
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+impl WorldBody {
+    /// Returns the components of a world body.
+    fn bundle() -> impl Bundle {
+        (
+            RigidBody::Static,
+            Position::default(),
+            Rotation::default(),
+            Name::new("World body"),
+        )
+    }
+}
+
+impl FromWorld for WorldBody {
+    fn from_world(world: &mut World) -> Self {
+        Self(world.spawn(Self::bundle()).id())
+    }
+}
+
+/// Spawns a new [`WorldBody`] if the previous one was despawned or is no longer a rigid body.
+pub(crate) fn recreate_world_body(
+    mut commands: Commands,
+    mut world_body: ResMut<WorldBody>,
+    bodies: Query<(), With<RigidBody>>,
+) {
+    if !bodies.contains(world_body.0) {
+        world_body.0 = commands.spawn(WorldBody::bundle()).id();
+    }
+}
+
+/// A motor that drives the relative motion of the bodies attached to a [`RevoluteJoint`] or a [`PrismaticJoint`]
+/// around or along the joint's free axis.
+///
+/// For revolute joints, velocities and positions are angles in radians, and the motor applies a torque.
+/// For prismatic joints, they are distances along the free axis, and the motor applies a force.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// # use bevy_xpbd_3d::prelude::*;
+/// #
+/// fn setup(mut commands: Commands) {
+///     let wheel = commands.spawn(RigidBody::Dynamic).id();
+///     let body = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Spin the wheel at 5 radians per second with a maximum torque of 100
+///     commands.spawn(
+///         RevoluteJoint::new(body, wheel)
+///             .with_motor(JointMotor::velocity(5.0).with_max_force(100.0)),
+///     );
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JointMotor {
+    /// The velocity or position that the motor drives the joint towards.
+    pub target: MotorTarget,
+    /// The motor's compliance, the inverse of stiffness. With a compliance of 0, the motor reaches
+    /// its target as fast as `max_force` allows.
+    pub compliance: Scalar,
+    /// The maximum force or torque that the motor can apply. By default this is infinite.
+    pub max_force: Scalar,
+}
+
+/// The target of a [`JointMotor`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MotorTarget {
+    /// The motor drives the relative velocity of the bodies towards the given velocity.
+    Velocity(Scalar),
+    /// The motor drives the relative position of the bodies towards the given position.
+    Position(Scalar),
+}
+
+impl JointMotor {
+    /// Creates a new `JointMotor` that drives the joint towards a target velocity.
+    pub fn velocity(target_velocity: Scalar) -> Self {
+        Self {
+            target: MotorTarget::Velocity(target_velocity),
+            compliance: 0.0,
+            max_force: Scalar::MAX,
+        }
+    }
+
+    /// Creates a new `JointMotor` that drives the joint towards a target position.
+    pub fn position(target_position: Scalar) -> Self {
+        Self {
+            target: MotorTarget::Position(target_position),
+            compliance: 0.0,
+            max_force: Scalar::MAX,
+        }
+    }
+
+    /// Sets the motor's compliance (inverse of stiffness).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Sets the maximum force or torque that the motor can apply.
+    pub fn with_max_force(self, max_force: Scalar) -> Self {
+        Self { max_force, ..self }
+    }
+
+    /// Returns how far the joint is from the motor's target, given the current and previous
+    /// relative positions of the bodies along the joint's free axis.
+    fn compute_error(&self, position: Scalar, previous_position: Scalar, dt: Scalar) -> Scalar {
+        match self.target {
+            MotorTarget::Velocity(velocity) => position - previous_position - velocity * dt,
+            MotorTarget::Position(target_position) => position - target_position,
+        }
+    }
+
+    /// Clamps a Lagrange multiplier update so that the force or torque of the motor
+    /// doesn't exceed `max_force`.
+    fn clamp_lagrange_update(
+        &self,
+        lagrange: Scalar,
+        delta_lagrange: Scalar,
+        dt: Scalar,
+    ) -> Scalar {
+        // The force is the Lagrange multiplier divided by dt^2
+        let max_lagrange = self.max_force * dt.powi(2);
+        (lagrange + delta_lagrange).clamp(-max_lagrange, max_lagrange) - lagrange
+    }
+}
+
 /// A limit that indicates that the distance between two points should be between `min` and `max`.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct DistanceLimit {
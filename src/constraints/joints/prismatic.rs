@@ -20,20 +20,30 @@ pub struct PrismaticJoint {
     pub free_axis: Vector,
     /// The extents of the allowed relative translation along the free axis.
     pub free_axis_limits: Option<DistanceLimit>,
+    /// A motor that drives the relative translation of the bodies along the free axis.
+    pub motor: Option<JointMotor>,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
     pub align_lagrange: Scalar,
+    /// Lagrange multiplier for the positional correction caused by the motor.
+    pub motor_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
     pub force: Vector,
     /// The torque exerted by the joint when aligning the bodies.
     pub align_torque: Torque,
+    /// The force exerted by the motor.
+    pub motor_force: Vector,
 }
 
 impl XpbdConstraint<2> for PrismaticJoint {
@@ -44,20 +54,41 @@ impl XpbdConstraint<2> for PrismaticJoint {
     fn clear_lagrange_multipliers(&mut self) {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
+        self.align_torque = Torque::ZERO;
+        self.motor_force = Vector::ZERO;
+        self.force = Vector::ZERO;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
+        // The forces and torques are only updated when their Lagrange multipliers change.
+        // Otherwise, the constraints were already satisfied by earlier iterations of the substep,
+        // and the values computed from the accumulated multipliers are kept.
 
         // Align orientations
         let dq = self.get_delta_q(&body1.rotation, &body2.rotation);
         let mut lagrange = self.align_lagrange;
-        self.align_torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        let torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        if lagrange != self.align_lagrange {
+            self.align_torque = torque;
+        }
         self.align_lagrange = lagrange;
 
+        // Drive the translation along the free axis with the motor
+        let lagrange = self.motor_lagrange;
+        let force = self.apply_motor(body1, body2, dt);
+        if lagrange != self.motor_lagrange {
+            self.motor_force = force;
+        }
+
         // Constrain the relative positions of the bodies, only allowing translation along one free axis
-        self.force = self.constrain_positions(body1, body2, dt);
+        let lagrange = self.position_lagrange;
+        let force = self.constrain_positions(body1, body2, dt);
+        if lagrange != self.position_lagrange {
+            self.force = force;
+        }
     }
 }
 
@@ -70,16 +101,21 @@ impl Joint for PrismaticJoint {
             local_anchor2: Vector::ZERO,
             free_axis: Vector::X,
             free_axis_limits: None,
+            motor: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            break_force: None,
+            break_torque: None,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
+            motor_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
             align_torque: 0.0,
             #[cfg(feature = "3d")]
             align_torque: Vector::ZERO,
+            motor_force: Vector::ZERO,
         }
     }
 
@@ -132,6 +168,38 @@ impl Joint for PrismaticJoint {
     }
 }
 
+impl BreakableJoint for PrismaticJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force + self.motor_force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque
+    }
+}
+
 impl PrismaticJoint {
     /// Constrains the relative positions of the bodies, only allowing translation along one free axis.
     ///
@@ -218,6 +286,63 @@ impl PrismaticJoint {
         self.compute_force(self.position_lagrange, dir, dt)
     }
 
+    /// Drives the relative translation of the bodies along the free axis with the motor.
+    ///
+    /// Returns the force exerted by the motor.
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Vector {
+        let Some(motor) = self.motor else {
+            return Vector::ZERO;
+        };
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+        let axis1 = body1.rotation.rotate(self.free_axis).normalize_or_zero();
+        let offset = body2.position.0 + body2.accumulated_translation.0 + world_r2
+            - (body1.position.0 + body1.accumulated_translation.0 + world_r1);
+
+        let previous_rot1 = body1.previous_rotation.0;
+        let previous_rot2 = body2.previous_rotation.0;
+        let previous_axis1 = previous_rot1.rotate(self.free_axis).normalize_or_zero();
+        let previous_offset = body2.previous_position.0 + previous_rot2.rotate(self.local_anchor2)
+            - (body1.previous_position.0 + previous_rot1.rotate(self.local_anchor1));
+
+        let c = motor.compute_error(offset.dot(axis1), previous_offset.dot(previous_axis1), dt);
+
+        // The gradient of the error with respect to the position of the first body
+        let dir = -axis1;
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, dir);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, dir);
+
+        // Constraint gradients and inverse masses
+        let gradients = [dir, -dir];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update, limited by the maximum force of the motor
+        let delta_lagrange = self.compute_lagrange_update(
+            self.motor_lagrange,
+            c,
+            &gradients,
+            &w,
+            motor.compliance,
+            dt,
+        );
+        let delta_lagrange = motor.clamp_lagrange_update(self.motor_lagrange, delta_lagrange, dt);
+        self.motor_lagrange += delta_lagrange;
+
+        // Apply positional correction towards the target
+        self.apply_positional_correction(body1, body2, delta_lagrange, dir, world_r1, world_r2);
+
+        // Return motor force
+        self.compute_force(self.motor_lagrange, dir, dt)
+    }
+
     /// Sets the joint's free axis. Relative translations are allowed along this free axis.
     pub fn with_free_axis(self, axis: Vector) -> Self {
         Self {
@@ -226,6 +351,14 @@ impl PrismaticJoint {
         }
     }
 
+    /// Sets the [motor](JointMotor) that drives the relative translation along the joint's free axis.
+    pub fn with_motor(self, motor: JointMotor) -> Self {
+        Self {
+            motor: Some(motor),
+            ..self
+        }
+    }
+
     /// Sets the translational limits along the joint's free axis.
     pub fn with_limits(self, min: Scalar, max: Scalar) -> Self {
         Self {
@@ -0,0 +1,248 @@
+//! [`PulleyJoint`] component.
+
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A pulley joint connects two bodies with an ideal rope that runs over two fixed pulley points,
+/// keeping `length1 + ratio * length2` constant.
+///
+/// `length1` is the distance between the first body's attachment point and `pulley_anchor1`, and `length2`
+/// is the distance between the second body's attachment point and `pulley_anchor2`. The pulley anchors are
+/// given in world space. A `ratio` other than 1 works like a block and tackle, trading distance for force.
+///
+/// ## Example
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::{math::*, prelude::*};
+/// # #[cfg(feature = "3d")]
+/// # use bevy_xpbd_3d::{math::*, prelude::*};
+/// #
+/// fn setup(mut commands: Commands) {
+///     let load = commands.spawn(RigidBody::Dynamic).id();
+///     let counterweight = commands.spawn(RigidBody::Dynamic).id();
+///
+///     // Hang the load and the counterweight from two pulleys that are 4 meters apart
+///     commands.spawn(
+///         PulleyJoint::new(load, counterweight)
+///             .with_pulley_anchors(Vector::Y * 5.0 - Vector::X * 2.0, Vector::Y * 5.0 + Vector::X * 2.0),
+///     );
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct PulleyJoint {
+    /// First entity constrained by the joint.
+    pub entity1: Entity,
+    /// Second entity constrained by the joint.
+    pub entity2: Entity,
+    /// Attachment point on the first body.
+    pub local_anchor1: Vector,
+    /// Attachment point on the second body.
+    pub local_anchor2: Vector,
+    /// The world-space pulley point that the rope of the first body runs over.
+    pub pulley_anchor1: Vector,
+    /// The world-space pulley point that the rope of the second body runs over.
+    pub pulley_anchor2: Vector,
+    /// The pulley ratio. The length of the second rope segment is multiplied by the ratio.
+    pub ratio: Scalar,
+    /// The total length `length1 + ratio * length2` that the joint keeps constant.
+    /// If `None`, it is set to the total length of the initial configuration when the joint is first solved.
+    pub rest_length: Option<Scalar>,
+    /// Linear damping applied by the joint to the movement of the bodies along the rope.
+    pub damping_linear: Scalar,
+    /// Lagrange multiplier for the positional correction.
+    pub lagrange: Scalar,
+    /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
+    pub compliance: Scalar,
+    /// The force exerted by the joint on the first body.
+    pub force: Vector,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+}
+
+impl XpbdConstraint<2> for PulleyJoint {
+    fn entities(&self) -> [Entity; 2] {
+        [self.entity1, self.entity2]
+    }
+
+    fn clear_lagrange_multipliers(&mut self) {
+        self.lagrange = 0.0;
+        self.force = Vector::ZERO;
+    }
+
+    fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
+        let [body1, body2] = bodies;
+
+        let world_r1 = body1.rotation.rotate(self.local_anchor1);
+        let world_r2 = body2.rotation.rotate(self.local_anchor2);
+
+        let Some([(n1, length1), (n2, length2)]) = self.compute_rope_segments(
+            body1.position.0 + body1.accumulated_translation.0 + world_r1,
+            body2.position.0 + body2.accumulated_translation.0 + world_r2,
+        ) else {
+            return;
+        };
+
+        let total_length = length1 + self.ratio * length2;
+        let c = total_length - *self.rest_length.get_or_insert(total_length);
+
+        // Compute generalized inverse masses
+        let w1 = PositionConstraint::compute_generalized_inverse_mass(self, body1, world_r1, n1);
+        let w2 = PositionConstraint::compute_generalized_inverse_mass(self, body2, world_r2, n2);
+
+        // Constraint gradients and inverse masses
+        let gradients = [n1, self.ratio * n2];
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update
+        let delta_lagrange =
+            self.compute_lagrange_update(self.lagrange, c, &gradients, &w, self.compliance, dt);
+        self.lagrange += delta_lagrange;
+
+        // Move the bodies along the gradients
+        if delta_lagrange.abs() > Scalar::EPSILON {
+            let p1 = delta_lagrange * gradients[0];
+            let p2 = delta_lagrange * gradients[1];
+
+            if body1.rb.is_dynamic() {
+                let rot1 = *body1.rotation;
+                let inv_mass1 = body1.effective_inv_mass();
+                let inv_inertia1 = body1.effective_world_inv_inertia();
+                body1.accumulated_translation.0 += p1 * inv_mass1;
+                *body1.rotation +=
+                    <Self as PositionConstraint>::get_delta_rot(rot1, inv_inertia1, world_r1, p1);
+            }
+            if body2.rb.is_dynamic() {
+                let rot2 = *body2.rotation;
+                let inv_mass2 = body2.effective_inv_mass();
+                let inv_inertia2 = body2.effective_world_inv_inertia();
+                body2.accumulated_translation.0 += p2 * inv_mass2;
+                *body2.rotation +=
+                    <Self as PositionConstraint>::get_delta_rot(rot2, inv_inertia2, world_r2, p2);
+            }
+        }
+
+        self.force = self.compute_force(self.lagrange, n1, dt);
+    }
+}
+
+/// Pulley joints only exert force along the rope, so they can only be broken by their `break_force`.
+impl BreakableJoint for PulleyJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    /// Pulley joints don't exert any torque, so the break torque is ignored.
+    fn with_break_torque(self, _break_torque: Scalar) -> Self {
+        self
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        None
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+}
+
+impl PulleyJoint {
+    /// Creates a new pulley joint between two entities with both pulley anchors at the origin and a ratio of 1.
+    pub fn new(entity1: Entity, entity2: Entity) -> Self {
+        Self {
+            entity1,
+            entity2,
+            local_anchor1: Vector::ZERO,
+            local_anchor2: Vector::ZERO,
+            pulley_anchor1: Vector::ZERO,
+            pulley_anchor2: Vector::ZERO,
+            ratio: 1.0,
+            rest_length: None,
+            damping_linear: 0.0,
+            lagrange: 0.0,
+            compliance: 0.0,
+            force: Vector::ZERO,
+            break_force: None,
+        }
+    }
+
+    /// Sets the world-space pulley points that the rope runs over.
+    pub fn with_pulley_anchors(self, anchor1: Vector, anchor2: Vector) -> Self {
+        Self {
+            pulley_anchor1: anchor1,
+            pulley_anchor2: anchor2,
+            ..self
+        }
+    }
+
+    /// Sets the attachment point on the first body.
+    pub fn with_local_anchor_1(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor1: anchor,
+            ..self
+        }
+    }
+
+    /// Sets the attachment point on the second body.
+    pub fn with_local_anchor_2(self, anchor: Vector) -> Self {
+        Self {
+            local_anchor2: anchor,
+            ..self
+        }
+    }
+
+    /// Sets the pulley ratio.
+    pub fn with_ratio(self, ratio: Scalar) -> Self {
+        Self { ratio, ..self }
+    }
+
+    /// Sets the total length `length1 + ratio * length2` that the joint keeps constant.
+    pub fn with_rest_length(self, rest_length: Scalar) -> Self {
+        Self {
+            rest_length: Some(rest_length),
+            ..self
+        }
+    }
+
+    /// Sets the joint's compliance (inverse of stiffness, meters / Newton).
+    pub fn with_compliance(self, compliance: Scalar) -> Self {
+        Self { compliance, ..self }
+    }
+
+    /// Sets the linear velocity damping caused by the joint.
+    pub fn with_linear_velocity_damping(self, damping: Scalar) -> Self {
+        Self {
+            damping_linear: damping,
+            ..self
+        }
+    }
+
+    /// Returns the directions from the pulley anchors to the world-space attachment points `p1` and `p2`
+    /// and the lengths of the rope segments, or `None` if an attachment point is at its pulley anchor.
+    pub(crate) fn compute_rope_segments(
+        &self,
+        p1: Vector,
+        p2: Vector,
+    ) -> Option<[(Vector, Scalar); 2]> {
+        let delta1 = p1 - self.pulley_anchor1;
+        let delta2 = p2 - self.pulley_anchor2;
+        let length1 = delta1.length();
+        let length2 = delta2.length();
+
+        if length1 <= Scalar::EPSILON || length2 <= Scalar::EPSILON {
+            return None;
+        }
+
+        Some([(delta1 / length1, length1), (delta2 / length2, length2)])
+    }
+}
+
+impl PositionConstraint for PulleyJoint {}
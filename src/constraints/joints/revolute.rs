@@ -26,16 +26,24 @@ pub struct RevoluteJoint {
     pub aligned_axis: Vector,
     /// The extents of the allowed relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit: Option<AngleLimit>,
+    /// A motor that drives the relative rotation of the bodies around the `aligned_axis`.
+    pub motor: Option<JointMotor>,
     /// Linear damping applied by the joint.
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the alignment of the bodies.
     pub align_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the angle limits.
     pub angle_limit_lagrange: Scalar,
+    /// Lagrange multiplier for the angular correction caused by the motor.
+    pub motor_lagrange: Scalar,
     /// The joint's compliance, the inverse of stiffness, has the unit meters / Newton.
     pub compliance: Scalar,
     /// The force exerted by the joint.
@@ -44,6 +52,8 @@ pub struct RevoluteJoint {
     pub align_torque: Torque,
     /// The torque exerted by the joint when limiting the relative rotation of the bodies around the `aligned_axis`.
     pub angle_limit_torque: Torque,
+    /// The torque exerted by the motor.
+    pub motor_torque: Torque,
 }
 
 impl XpbdConstraint<2> for RevoluteJoint {
@@ -55,21 +65,32 @@ impl XpbdConstraint<2> for RevoluteJoint {
         self.position_lagrange = 0.0;
         self.align_lagrange = 0.0;
         self.angle_limit_lagrange = 0.0;
+        self.motor_lagrange = 0.0;
+        self.align_torque = Torque::ZERO;
+        self.force = Vector::ZERO;
+        self.motor_torque = Torque::ZERO;
+        self.angle_limit_torque = Torque::ZERO;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
+        // The forces and torques are only updated when their Lagrange multipliers change.
+        // Otherwise, the constraints were already satisfied by earlier iterations of the substep,
+        // and the values computed from the accumulated multipliers are kept.
 
         // Constrain the relative rotation of the bodies, only allowing rotation around one free axis
         let dq = self.get_delta_q(&body1.rotation, &body2.rotation);
         let mut lagrange = self.align_lagrange;
-        self.align_torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        let torque = self.align_orientation(body1, body2, dq, &mut lagrange, compliance, dt);
+        if lagrange != self.align_lagrange {
+            self.align_torque = torque;
+        }
         self.align_lagrange = lagrange;
 
         // Align positions
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        let force = self.align_position(
             body1,
             body2,
             self.local_anchor1,
@@ -78,10 +99,24 @@ impl XpbdConstraint<2> for RevoluteJoint {
             compliance,
             dt,
         );
+        if lagrange != self.position_lagrange {
+            self.force = force;
+        }
         self.position_lagrange = lagrange;
 
+        // Drive the rotation around the free axis with the motor
+        let lagrange = self.motor_lagrange;
+        let torque = self.apply_motor(body1, body2, dt);
+        if lagrange != self.motor_lagrange {
+            self.motor_torque = torque;
+        }
+
         // Apply angle limits when rotating around the free axis
-        self.angle_limit_torque = self.apply_angle_limits(body1, body2, dt);
+        let lagrange = self.angle_limit_lagrange;
+        let torque = self.apply_angle_limits(body1, body2, dt);
+        if lagrange != self.angle_limit_lagrange {
+            self.angle_limit_torque = torque;
+        }
     }
 }
 
@@ -94,11 +129,15 @@ impl Joint for RevoluteJoint {
             local_anchor2: Vector::ZERO,
             aligned_axis: Vector3::Z,
             angle_limit: None,
+            motor: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            break_force: None,
+            break_torque: None,
             position_lagrange: 0.0,
             align_lagrange: 0.0,
             angle_limit_lagrange: 0.0,
+            motor_lagrange: 0.0,
             compliance: 0.0,
             force: Vector::ZERO,
             #[cfg(feature = "2d")]
@@ -109,6 +148,10 @@ impl Joint for RevoluteJoint {
             angle_limit_torque: 0.0,
             #[cfg(feature = "3d")]
             angle_limit_torque: Vector::ZERO,
+            #[cfg(feature = "2d")]
+            motor_torque: 0.0,
+            #[cfg(feature = "3d")]
+            motor_torque: Vector::ZERO,
         }
     }
 
@@ -161,6 +204,38 @@ impl Joint for RevoluteJoint {
     }
 }
 
+impl BreakableJoint for RevoluteJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.align_torque + self.angle_limit_torque + self.motor_torque
+    }
+}
+
 impl RevoluteJoint {
     /// Sets the axis that the bodies should be aligned on.
     #[cfg(feature = "3d")]
@@ -179,6 +254,89 @@ impl RevoluteJoint {
         }
     }
 
+    /// Sets the [motor](JointMotor) that drives the relative rotation around the `aligned_axis`.
+    pub fn with_motor(self, motor: JointMotor) -> Self {
+        Self {
+            motor: Some(motor),
+            ..self
+        }
+    }
+
+    /// Returns the world-space `aligned_axis` of the first body and the relative rotation
+    /// of the bodies around it in radians.
+    fn compute_angle(&self, rot1: &Rotation, rot2: &Rotation) -> (Vector3, Scalar) {
+        // A reference axis perpendicular to the aligned axis, the same one that angle limits are measured from
+        let reference_axis = Vector3::new(
+            self.aligned_axis.z,
+            self.aligned_axis.x,
+            self.aligned_axis.y,
+        )
+        .reject_from_normalized(self.aligned_axis)
+        .normalize_or_zero();
+
+        let n = rot1.rotate_vec3(self.aligned_axis);
+        let a1 = rot1.rotate_vec3(reference_axis);
+        let a2 = rot2.rotate_vec3(reference_axis);
+        (n, a1.cross(a2).dot(n).atan2(a1.dot(a2)))
+    }
+
+    /// Drives the relative rotation of the bodies around the `aligned_axis` with the motor.
+    ///
+    /// Returns the torque exerted by the motor.
+    fn apply_motor(
+        &mut self,
+        body1: &mut RigidBodyQueryItem,
+        body2: &mut RigidBodyQueryItem,
+        dt: Scalar,
+    ) -> Torque {
+        let Some(motor) = self.motor else {
+            return Torque::ZERO;
+        };
+
+        let (n, angle) = self.compute_angle(&body1.rotation, &body2.rotation);
+        let (_, previous_angle) =
+            self.compute_angle(&body1.previous_rotation.0, &body2.previous_rotation.0);
+
+        // Wrap the error to [-PI, PI] so that the motor takes the shortest way around
+        let mut c = motor.compute_error(angle, previous_angle, dt);
+        c = (c + PI).rem_euclid(2.0 * PI) - PI;
+
+        // Compute generalized inverse masses
+        let w1 = AngularConstraint::compute_generalized_inverse_mass(self, body1, n);
+        let w2 = AngularConstraint::compute_generalized_inverse_mass(self, body2, n);
+
+        // Constraint gradients and inverse masses
+        let gradients = {
+            #[cfg(feature = "2d")]
+            {
+                [Vector::Y * n.z, Vector::NEG_Y * n.z]
+            }
+            #[cfg(feature = "3d")]
+            {
+                [n, -n]
+            }
+        };
+        let w = [w1, w2];
+
+        // Compute Lagrange multiplier update, limited by the maximum torque of the motor
+        let delta_lagrange = self.compute_lagrange_update(
+            self.motor_lagrange,
+            c,
+            &gradients,
+            &w,
+            motor.compliance,
+            dt,
+        );
+        let delta_lagrange = motor.clamp_lagrange_update(self.motor_lagrange, delta_lagrange, dt);
+        self.motor_lagrange += delta_lagrange;
+
+        // Apply angular correction towards the target
+        self.apply_angular_correction(body1, body2, delta_lagrange, n);
+
+        // Return motor torque
+        self.compute_torque(self.motor_lagrange, n, dt)
+    }
+
     fn get_delta_q(&self, rot1: &Rotation, rot2: &Rotation) -> Vector3 {
         let a1 = rot1.rotate_vec3(self.aligned_axis);
         let a2 = rot2.rotate_vec3(self.aligned_axis);
@@ -28,6 +28,10 @@ pub struct SphericalJoint {
     pub damping_linear: Scalar,
     /// Angular damping applied by the joint.
     pub damping_angular: Scalar,
+    /// The force that breaks the joint when exceeded. By default the joint can't break.
+    pub break_force: Option<Scalar>,
+    /// The torque that breaks the joint when exceeded. By default the joint can't break.
+    pub break_torque: Option<Scalar>,
     /// Lagrange multiplier for the positional correction.
     pub position_lagrange: Scalar,
     /// Lagrange multiplier for the angular correction caused by the swing limits.
@@ -53,15 +57,21 @@ impl XpbdConstraint<2> for SphericalJoint {
         self.position_lagrange = 0.0;
         self.swing_lagrange = 0.0;
         self.twist_lagrange = 0.0;
+        self.force = Vector::ZERO;
+        self.swing_torque = Torque::ZERO;
+        self.twist_torque = Torque::ZERO;
     }
 
     fn solve(&mut self, bodies: [&mut RigidBodyQueryItem; 2], dt: Scalar) {
         let [body1, body2] = bodies;
         let compliance = self.compliance;
+        // The forces and torques are only updated when their Lagrange multipliers change.
+        // Otherwise, the constraints were already satisfied by earlier iterations of the substep,
+        // and the values computed from the accumulated multipliers are kept.
 
         // Align positions
         let mut lagrange = self.position_lagrange;
-        self.force = self.align_position(
+        let force = self.align_position(
             body1,
             body2,
             self.local_anchor1,
@@ -70,13 +80,24 @@ impl XpbdConstraint<2> for SphericalJoint {
             compliance,
             dt,
         );
+        if lagrange != self.position_lagrange {
+            self.force = force;
+        }
         self.position_lagrange = lagrange;
 
         // Apply swing limits
-        self.swing_torque = self.apply_swing_limits(body1, body2, dt);
+        let lagrange = self.swing_lagrange;
+        let torque = self.apply_swing_limits(body1, body2, dt);
+        if lagrange != self.swing_lagrange {
+            self.swing_torque = torque;
+        }
 
         // Apply twist limits
-        self.twist_torque = self.apply_twist_limits(body1, body2, dt);
+        let lagrange = self.twist_lagrange;
+        let torque = self.apply_twist_limits(body1, body2, dt);
+        if lagrange != self.twist_lagrange {
+            self.twist_torque = torque;
+        }
     }
 }
 
@@ -93,6 +114,8 @@ impl Joint for SphericalJoint {
             twist_limit: None,
             damping_linear: 1.0,
             damping_angular: 1.0,
+            break_force: None,
+            break_torque: None,
             position_lagrange: 0.0,
             swing_lagrange: 0.0,
             twist_lagrange: 0.0,
@@ -158,6 +181,38 @@ impl Joint for SphericalJoint {
     }
 }
 
+impl BreakableJoint for SphericalJoint {
+    fn with_break_force(self, break_force: Scalar) -> Self {
+        Self {
+            break_force: Some(break_force),
+            ..self
+        }
+    }
+
+    fn with_break_torque(self, break_torque: Scalar) -> Self {
+        Self {
+            break_torque: Some(break_torque),
+            ..self
+        }
+    }
+
+    fn break_force(&self) -> Option<Scalar> {
+        self.break_force
+    }
+
+    fn break_torque(&self) -> Option<Scalar> {
+        self.break_torque
+    }
+
+    fn force(&self) -> Vector {
+        self.force
+    }
+
+    fn torque(&self) -> Torque {
+        self.swing_torque + self.twist_torque
+    }
+}
+
 impl SphericalJoint {
     /// Sets the limits of the allowed relative rotation around the `swing_axis`.
     pub fn with_swing_limits(self, min: Scalar, max: Scalar) -> Self {
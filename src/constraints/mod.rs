@@ -21,6 +21,9 @@
 //!     - [`SphericalJoint`]
 //!     - [`RevoluteJoint`]
 //!     - [`PrismaticJoint`]
+//!     - [`D6Joint`]
+//!     - [`GearJoint`]
+//!     - [`PulleyJoint`]
 //!
 //! More constraint types will be added in future releases. If you need more constraints now, consider
 //! [creating your own constraints](custom-constraints).
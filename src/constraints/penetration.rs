@@ -169,7 +169,11 @@ impl PenetrationConstraint {
         let w = [w1, w2];
 
         // Compute combined friction coefficients
-        let static_coefficient = body1.friction.combine(*body2.friction).static_coefficient;
+        let static_coefficient = self
+            .contact
+            .friction
+            .unwrap_or(body1.friction.combine(*body2.friction))
+            .static_coefficient;
 
         // Apply static friction if |delta_x_perp| < mu_s * d
         if sliding_len < static_coefficient * penetration {
@@ -25,6 +25,8 @@
 //!     - Access to [colliding entities](CollidingEntities)
 //!     - [Sensor colliders](Sensor)
 //!     - [Collision layers](CollisionLayers)
+//!     - Custom [collision pair filters](CollisionPairFilter)
+//!     - [Continuous collision detection](SweptCcd) for fast bodies
 //! - Material properties like [restitution](Restitution) and [friction](Friction)
 //! - [Linear damping](LinearDamping) and [angular damping](AngularDamping) for simulating drag
 //! - [Gravity] and [gravity scale](GravityScale)
@@ -502,9 +504,10 @@ pub enum PhysicsSet {
 /// 2. Substeps
 ///     1. Integrate
 ///     2. Narrow phase
-///     3. Solve positional and angular constraints
-///     4. Update velocities
-///     5. Solve velocity constraints (dynamic friction and restitution)
+///     3. Post-process collisions
+///     4. Solve positional and angular constraints
+///     5. Update velocities
+///     6. Solve velocity constraints (dynamic friction and restitution)
 /// 3. Sleeping
 /// 4. Spatial queries
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -531,9 +534,11 @@ pub enum PhysicsStepSet {
 /// System sets for the the steps in the inner substepping loop. These are typically run in the [`SubstepSchedule`].
 ///
 /// 1. Integrate
-/// 2. Solve positional and angular constraints
-/// 3. Update velocities
-/// 4. Solve velocity constraints (dynamic friction and restitution)
+/// 2. Narrow phase
+/// 3. Post-process collisions
+/// 4. Solve positional and angular constraints
+/// 5. Update velocities
+/// 6. Solve velocity constraints (dynamic friction and restitution)
 #[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum SubstepSet {
     /// Responsible for integrating Newton's 2nd law of motion,
@@ -545,6 +550,52 @@ pub enum SubstepSet {
     ///
     /// See [`NarrowPhasePlugin`].
     NarrowPhase,
+    /// Responsible for modifying the contacts computed by the narrow phase before they are
+    /// used by the [solver]. Empty by default.
+    ///
+    /// Systems in this set can access the [`Collisions`] of the current substep mutably, along with
+    /// any components of the colliding entities. This can be used to implement things like
+    /// one-way platforms, conveyor belts or per-contact material properties by:
+    ///
+    /// - Removing contacts or whole collisions, for example based on the contact normal
+    /// - Overriding the [friction](ContactData::friction) or [restitution](ContactData::restitution) of contacts
+    /// - Changing contact normals and penetration depths
+    ///
+    /// Only contacts with [`Contacts::during_current_substep`] set to `true` are solved during the substep.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::{prelude::*, SubstepSchedule, SubstepSet};
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::{prelude::*, SubstepSchedule, SubstepSet};
+    ///
+    /// #[derive(Component)]
+    /// struct Ice;
+    ///
+    /// fn setup(app: &mut App) {
+    ///     app.get_schedule_mut(SubstepSchedule)
+    ///         .expect("add SubstepSchedule first")
+    ///         .add_systems(make_ice_slippery.in_set(SubstepSet::PostProcessCollisions));
+    /// }
+    ///
+    /// // Remove friction from all contacts with ice
+    /// fn make_ice_slippery(mut collisions: ResMut<Collisions>, ice: Query<(), With<Ice>>) {
+    ///     for contacts in collisions.iter_mut() {
+    ///         if !ice.contains(contacts.entity1) && !ice.contains(contacts.entity2) {
+    ///             continue;
+    ///         }
+    ///         for manifold in contacts.manifolds.iter_mut() {
+    ///             for contact in manifold.contacts.iter_mut() {
+    ///                 contact.friction = Some(Friction::ZERO);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    PostProcessCollisions,
     /// The [solver] iterates through [constraints] and solves them.
     ///
     /// **Note**: If you want to [create your own constraints](constraints#custom-constraints),
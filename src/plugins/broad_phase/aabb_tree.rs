@@ -0,0 +1,269 @@
+//! An incremental dynamic AABB tree used by [`BroadPhaseBackend::DynamicAabbTree`].
+
+use super::{can_collide, AabbInterval};
+use crate::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use parry::bounding_volume::{Aabb, BoundingVolume};
+
+/// How much the AABBs of leaves are enlarged relative to their size, so that colliders
+/// can move a bit without having to be reinserted into the tree.
+const AABB_MARGIN_FACTOR: Scalar = 0.1;
+
+/// A node in a [`DynamicAabbTree`].
+#[derive(Clone, Debug)]
+struct TreeNode {
+    /// The enlarged AABB of a leaf, or the AABB enclosing both children of an internal node.
+    aabb: Aabb,
+    parent: Option<usize>,
+    /// The children of an internal node. Leaves don't have children.
+    children: Option<[usize; 2]>,
+    /// The index of the leaf's collider in the [`AabbInterval`]s given to [`DynamicAabbTree::update`].
+    interval: usize,
+}
+
+/// A binary bounding volume hierarchy whose leaves are the colliders in the broad phase.
+///
+/// Leaves are inserted using the surface area heuristic, and their AABBs are enlarged
+/// so that only colliders that have moved significantly need to be reinserted.
+#[derive(Resource, Default)]
+pub(super) struct DynamicAabbTree {
+    nodes: Vec<TreeNode>,
+    free_nodes: Vec<usize>,
+    root: Option<usize>,
+    /// The leaf node of each collider in the tree.
+    leaves: HashMap<Entity, usize>,
+}
+
+impl DynamicAabbTree {
+    /// Removes all nodes from the tree.
+    pub(super) fn clear(&mut self) {
+        if self.root.is_some() {
+            *self = Self::default();
+        }
+    }
+
+    /// Updates the tree to match the given intervals, inserting new colliders, reinserting
+    /// colliders that have moved out of their enlarged AABBs and removing colliders that no longer exist.
+    pub(super) fn update(&mut self, intervals: &[AabbInterval]) {
+        // Remove colliders that no longer exist
+        let entities: HashSet<Entity> = intervals.iter().map(|interval| interval.0).collect();
+        let removed: Vec<usize> = self
+            .leaves
+            .iter()
+            .filter(|(entity, _)| !entities.contains(*entity))
+            .map(|(_, leaf)| *leaf)
+            .collect();
+        self.leaves.retain(|entity, _| entities.contains(entity));
+        for leaf in removed {
+            self.remove_leaf(leaf);
+        }
+
+        for (index, (entity, _, aabb, ..)) in intervals.iter().enumerate() {
+            match self.leaves.get(entity).copied() {
+                Some(leaf) if self.nodes[leaf].aabb.contains(aabb) => {
+                    self.nodes[leaf].interval = index;
+                }
+                Some(leaf) => {
+                    self.remove_leaf(leaf);
+                    let leaf = self.insert_leaf(enlarge_aabb(aabb), index);
+                    self.leaves.insert(*entity, leaf);
+                }
+                None => {
+                    let leaf = self.insert_leaf(enlarge_aabb(aabb), index);
+                    self.leaves.insert(*entity, leaf);
+                }
+            }
+        }
+    }
+
+    /// Collects the pairs of colliders with intersecting AABBs that are allowed to collide.
+    ///
+    /// [`update`](Self::update) must be called with the same intervals first.
+    pub(super) fn collect_pairs(
+        &self,
+        intervals: &[AabbInterval],
+        broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    ) {
+        let Some(root) = self.root else {
+            return;
+        };
+
+        let mut stack = vec![];
+
+        for (index1, interval1) in intervals.iter().enumerate() {
+            let aabb1 = &interval1.2;
+            stack.push(root);
+
+            while let Some(node_index) = stack.pop() {
+                let node = &self.nodes[node_index];
+
+                if !node.aabb.intersects(aabb1) {
+                    continue;
+                }
+
+                if let Some([child1, child2]) = node.children {
+                    stack.push(child1);
+                    stack.push(child2);
+                    continue;
+                }
+
+                // Only report each pair once
+                let index2 = node.interval;
+                if index2 <= index1 {
+                    continue;
+                }
+
+                let interval2 = &intervals[index2];
+                if interval2.2.intersects(aabb1) && can_collide(interval1, interval2) {
+                    broad_collision_pairs.push((interval1.0, interval2.0));
+                }
+            }
+        }
+    }
+
+    fn allocate_node(&mut self, node: TreeNode) -> usize {
+        if let Some(index) = self.free_nodes.pop() {
+            self.nodes[index] = node;
+            index
+        } else {
+            self.nodes.push(node);
+            self.nodes.len() - 1
+        }
+    }
+
+    /// Inserts a new leaf with the given AABB and returns its index.
+    fn insert_leaf(&mut self, aabb: Aabb, interval: usize) -> usize {
+        let leaf = self.allocate_node(TreeNode {
+            aabb,
+            parent: None,
+            children: None,
+            interval,
+        });
+
+        let Some(root) = self.root else {
+            self.root = Some(leaf);
+            return leaf;
+        };
+
+        // Find the best sibling for the new leaf by descending the tree,
+        // choosing the child that causes the smallest increase in surface area.
+        let mut sibling = root;
+        while let Some([child1, child2]) = self.nodes[sibling].children {
+            let area = surface_area(&self.nodes[sibling].aabb);
+            let combined_area = surface_area(&self.nodes[sibling].aabb.merged(&aabb));
+
+            // The cost of creating a new parent for this node and the new leaf
+            let cost = 2.0 * combined_area;
+
+            // The minimum cost of pushing the leaf further down the tree
+            let inheritance_cost = 2.0 * (combined_area - area);
+
+            let child_cost = |child: usize| {
+                let child_aabb = &self.nodes[child].aabb;
+                let merged_area = surface_area(&child_aabb.merged(&aabb));
+                if self.nodes[child].children.is_none() {
+                    merged_area + inheritance_cost
+                } else {
+                    merged_area - surface_area(child_aabb) + inheritance_cost
+                }
+            };
+            let cost1 = child_cost(child1);
+            let cost2 = child_cost(child2);
+
+            if cost < cost1 && cost < cost2 {
+                break;
+            }
+
+            sibling = if cost1 < cost2 { child1 } else { child2 };
+        }
+
+        // Create a new parent for the sibling and the new leaf
+        let old_parent = self.nodes[sibling].parent;
+        let new_parent = self.allocate_node(TreeNode {
+            aabb: self.nodes[sibling].aabb.merged(&aabb),
+            parent: old_parent,
+            children: Some([sibling, leaf]),
+            interval: usize::MAX,
+        });
+        self.nodes[sibling].parent = Some(new_parent);
+        self.nodes[leaf].parent = Some(new_parent);
+
+        if let Some(old_parent) = old_parent {
+            self.replace_child(old_parent, sibling, new_parent);
+            self.refit_ancestors(old_parent);
+        } else {
+            self.root = Some(new_parent);
+        }
+
+        leaf
+    }
+
+    /// Removes the given leaf from the tree, replacing its parent with its sibling.
+    fn remove_leaf(&mut self, leaf: usize) {
+        self.free_nodes.push(leaf);
+
+        let Some(parent) = self.nodes[leaf].parent else {
+            self.root = None;
+            return;
+        };
+
+        let [child1, child2] = self.nodes[parent]
+            .children
+            .expect("internal nodes should have children");
+        let sibling = if child1 == leaf { child2 } else { child1 };
+        let grandparent = self.nodes[parent].parent;
+
+        self.nodes[sibling].parent = grandparent;
+        self.free_nodes.push(parent);
+
+        if let Some(grandparent) = grandparent {
+            self.replace_child(grandparent, parent, sibling);
+            self.refit_ancestors(grandparent);
+        } else {
+            self.root = Some(sibling);
+        }
+    }
+
+    fn replace_child(&mut self, parent: usize, old_child: usize, new_child: usize) {
+        if let Some(children) = self.nodes[parent].children.as_mut() {
+            if children[0] == old_child {
+                children[0] = new_child;
+            } else {
+                children[1] = new_child;
+            }
+        }
+    }
+
+    /// Recomputes the AABBs of the given internal node and all of its ancestors.
+    fn refit_ancestors(&mut self, node: usize) {
+        let mut current = Some(node);
+        while let Some(index) = current {
+            if let Some([child1, child2]) = self.nodes[index].children {
+                self.nodes[index].aabb = self.nodes[child1].aabb.merged(&self.nodes[child2].aabb);
+            }
+            current = self.nodes[index].parent;
+        }
+    }
+}
+
+/// Enlarges an AABB by a margin relative to its size.
+fn enlarge_aabb(aabb: &ColliderAabb) -> Aabb {
+    aabb.loosened(aabb.extents().max() * AABB_MARGIN_FACTOR)
+}
+
+/// The cost of an AABB used for the surface area heuristic. In 2D, this is the half perimeter.
+#[cfg(feature = "2d")]
+fn surface_area(aabb: &Aabb) -> Scalar {
+    let extents = aabb.extents();
+    extents.x + extents.y
+}
+
+/// The cost of an AABB used for the surface area heuristic. This is half of the surface area.
+#[cfg(feature = "3d")]
+fn surface_area(aabb: &Aabb) -> Scalar {
+    let extents = aabb.extents();
+    extents.x * extents.y + extents.y * extents.z + extents.z * extents.x
+}
@@ -0,0 +1,168 @@
+//! A uniform grid stored in a spatial hash map, used by [`BroadPhaseBackend::UniformGrid`].
+
+use super::{can_collide, AabbInterval};
+use crate::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
+use parry::bounding_volume::{Aabb, BoundingVolume};
+
+/// Colliders that overlap more cells than this are not added to the grid,
+/// and are instead checked against all other colliders.
+const MAX_CELLS_PER_COLLIDER: usize = 64;
+
+/// The integer coordinates of a grid cell.
+#[cfg(feature = "2d")]
+type Cell = [i32; 2];
+
+/// The integer coordinates of a grid cell.
+#[cfg(feature = "3d")]
+type Cell = [i32; 3];
+
+/// A uniform grid that stores the indices of the colliders overlapping each cell.
+///
+/// The grid is rebuilt every frame. Cells that were occupied during the previous frame keep their allocations,
+/// and cells that were left empty are removed so that the map doesn't grow with every cell ever visited.
+#[derive(Resource, Default)]
+pub(super) struct SpatialHashGrid {
+    cells: HashMap<Cell, Vec<usize>>,
+    /// Colliders that overlap too many cells to be added to the grid.
+    oversized: Vec<usize>,
+    /// Whether the collider at each interval index is in `oversized`.
+    is_oversized: Vec<bool>,
+    /// Whether a warning has already been logged for an invalid cell size.
+    warned_invalid_cell_size: bool,
+    /// The pairs of interval indices found during the current frame.
+    pairs: Vec<(usize, usize)>,
+}
+
+impl SpatialHashGrid {
+    /// Returns `true` if the given cell size can be used for the grid, which requires it to be positive and finite.
+    ///
+    /// Logs a warning the first time an invalid cell size is encountered.
+    pub(super) fn validate_cell_size(&mut self, cell_size: Scalar) -> bool {
+        let is_valid = cell_size.is_finite() && cell_size > 0.0;
+        if !is_valid && !self.warned_invalid_cell_size {
+            warn!(
+                "invalid cell size {cell_size} for `BroadPhaseBackend::UniformGrid`, falling back to sweep and prune"
+            );
+        }
+        self.warned_invalid_cell_size = !is_valid;
+        is_valid
+    }
+
+    /// Rebuilds the grid and collects the pairs of colliders with intersecting AABBs that are allowed to collide.
+    pub(super) fn collect_pairs(
+        &mut self,
+        intervals: &[AabbInterval],
+        cell_size: Scalar,
+        broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+    ) {
+        // Remove the cells that were empty during the previous frame and reuse the rest
+        self.cells.retain(|_, indices| !indices.is_empty());
+        self.cells.values_mut().for_each(Vec::clear);
+        self.oversized.clear();
+        self.is_oversized.clear();
+        self.is_oversized.resize(intervals.len(), false);
+        self.pairs.clear();
+
+        // Add colliders to the cells that their AABBs overlap
+        for (index, (_, _, aabb, ..)) in intervals.iter().enumerate() {
+            let (min, max) = cell_range(aabb, cell_size);
+            if cell_count(min, max) > MAX_CELLS_PER_COLLIDER {
+                self.oversized.push(index);
+                self.is_oversized[index] = true;
+                continue;
+            }
+            for cell in cells_in_range(min, max) {
+                self.cells.entry(cell).or_default().push(index);
+            }
+        }
+
+        // Check colliders that share a cell
+        for (cell, indices) in self.cells.iter() {
+            for (i, &index1) in indices.iter().enumerate() {
+                for &index2 in indices.iter().skip(i + 1) {
+                    let (interval1, interval2) = (&intervals[index1], &intervals[index2]);
+                    if !interval1.2.intersects(&interval2.2) {
+                        continue;
+                    }
+
+                    // Colliders can share several cells, so only report the pair in the cell
+                    // containing the minimum corner of the intersection of their AABBs
+                    let intersection_min = interval1.2.mins.coords.sup(&interval2.2.mins.coords);
+                    if point_to_cell(intersection_min.into(), cell_size) != *cell {
+                        continue;
+                    }
+
+                    if can_collide(interval1, interval2) {
+                        self.pairs.push((index1.min(index2), index1.max(index2)));
+                    }
+                }
+            }
+        }
+
+        // Check oversized colliders against all other colliders
+        for &index1 in self.oversized.iter() {
+            let interval1 = &intervals[index1];
+            for (index2, interval2) in intervals.iter().enumerate() {
+                // Only report pairs of oversized colliders once
+                if index2 == index1 || (index2 < index1 && self.is_oversized[index2]) {
+                    continue;
+                }
+
+                if interval1.2.intersects(&interval2.2) && can_collide(interval1, interval2) {
+                    self.pairs.push((index1.min(index2), index1.max(index2)));
+                }
+            }
+        }
+
+        // Sort the pairs so that the order doesn't depend on the iteration order of the hash map
+        self.pairs.sort_unstable();
+        broad_collision_pairs.extend(
+            self.pairs
+                .iter()
+                .map(|(index1, index2)| (intervals[*index1].0, intervals[*index2].0)),
+        );
+    }
+}
+
+/// Returns the cell containing the given point.
+#[cfg(feature = "2d")]
+fn point_to_cell(point: Vector, cell_size: Scalar) -> Cell {
+    (point / cell_size).floor().as_ivec2().to_array()
+}
+
+/// Returns the cell containing the given point.
+#[cfg(feature = "3d")]
+fn point_to_cell(point: Vector, cell_size: Scalar) -> Cell {
+    (point / cell_size).floor().as_ivec3().to_array()
+}
+
+/// Returns the minimum and maximum cells overlapped by the given AABB.
+fn cell_range(aabb: &Aabb, cell_size: Scalar) -> (Cell, Cell) {
+    (
+        point_to_cell(aabb.mins.into(), cell_size),
+        point_to_cell(aabb.maxs.into(), cell_size),
+    )
+}
+
+/// Returns the number of cells between the given minimum and maximum cells.
+fn cell_count(min: Cell, max: Cell) -> usize {
+    min.iter()
+        .zip(max.iter())
+        .map(|(min, max)| (*max as i64 - *min as i64 + 1) as usize)
+        .fold(1, usize::saturating_mul)
+}
+
+/// Iterates over the cells between the given minimum and maximum cells.
+#[cfg(feature = "2d")]
+fn cells_in_range(min: Cell, max: Cell) -> impl Iterator<Item = Cell> {
+    (min[0]..=max[0]).flat_map(move |x| (min[1]..=max[1]).map(move |y| [x, y]))
+}
+
+/// Iterates over the cells between the given minimum and maximum cells.
+#[cfg(feature = "3d")]
+fn cells_in_range(min: Cell, max: Cell) -> impl Iterator<Item = Cell> {
+    (min[0]..=max[0]).flat_map(move |x| {
+        (min[1]..=max[1]).flat_map(move |y| (min[2]..=max[2]).map(move |z| [x, y, z]))
+    })
+}
@@ -0,0 +1,671 @@
+//! Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+//! [AABB](ColliderAabb) intersection checks.
+//!
+//! See [`BroadPhasePlugin`].
+
+mod aabb_tree;
+mod grid;
+mod query_pipeline;
+
+use std::marker::PhantomData;
+
+use crate::prelude::*;
+use bevy::{
+    ecs::{
+        query::ROQueryItem,
+        system::{ReadOnlySystemParam, StaticSystemParam, SystemParamItem},
+    },
+    prelude::*,
+    utils::HashMap,
+};
+use indexmap::IndexMap;
+
+/// Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
+/// [AABB](ColliderAabb) intersection checks. This speeds up narrow phase collision detection,
+/// as the number of precise collision checks required is greatly reduced.
+///
+/// The algorithm used for finding the pairs can be selected with the [`BroadPhaseBackend`] in the [`BroadPhaseConfig`] resource.
+/// By default, the [sweep and prune](https://en.wikipedia.org/wiki/Sweep_and_prune) algorithm is used.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, PhysicsPlugins::default()))
+///         .insert_resource(BroadPhaseConfig {
+///             backend: BroadPhaseBackend::DynamicAabbTree,
+///         })
+///         .run();
+/// }
+/// ```
+///
+/// Collision pairs can be filtered using custom logic by adding a [`CollisionPairFilterPlugin`].
+///
+/// Pairs are kept in [`BroadCollisionPairs`] in a stable order for as long as their AABBs intersect,
+/// even though the backends collect all pairs again every frame. A [`BroadPhasePairStarted`] event is sent
+/// when a new pair is found, and a [`BroadPhasePairEnded`] event is sent when the AABBs of a pair
+/// stop intersecting or the pair is filtered out.
+/// This can be used for building expensive per-pair caches only once.
+///
+/// The broad phase systems run in [`PhysicsStepSet::BroadPhase`].
+pub struct BroadPhasePlugin;
+
+impl Plugin for BroadPhasePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BroadPhasePairStarted>()
+            .add_event::<BroadPhasePairEnded>()
+            .init_resource::<AabbIntervals>()
+            .init_resource::<PersistentCollisionPairs>()
+            .init_resource::<BroadPhaseConfig>()
+            .init_resource::<aabb_tree::DynamicAabbTree>()
+            .init_resource::<grid::SpatialHashGrid>()
+            .register_type::<BroadPhaseConfig>();
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            (
+                update_aabb,
+                (update_aabb_intervals, add_new_aabb_intervals)
+                    .chain()
+                    .run_if(uses_aabb_intervals),
+                collect_collision_pairs,
+            )
+                .chain()
+                .in_set(PhysicsStepSet::BroadPhase),
+        );
+
+        physics_schedule.add_systems(
+            update_persistent_pairs
+                .after(collect_collision_pairs)
+                .after(CollisionPairFilterSet)
+                .in_set(PhysicsStepSet::BroadPhase),
+        );
+    }
+}
+
+/// An event that is sent when the broad phase finds a new pair of colliders with intersecting [AABBs](ColliderAabb).
+///
+/// The pair stays in [`BroadCollisionPairs`] until a [`BroadPhasePairEnded`] event is sent for it.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BroadPhasePairStarted(pub Entity, pub Entity);
+
+/// An event that is sent when a pair of colliders is removed from [`BroadCollisionPairs`], because their
+/// [AABBs](ColliderAabb) no longer intersect, one of the colliders was removed, or the pair was filtered out.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BroadPhasePairEnded(pub Entity, pub Entity);
+
+/// A resource for configuring the [broad phase](BroadPhasePlugin).
+#[derive(Resource, Reflect, Clone, Debug, Default, PartialEq)]
+#[reflect(Resource)]
+pub struct BroadPhaseConfig {
+    /// The algorithm used for finding pairs of colliders with intersecting [AABBs](ColliderAabb).
+    pub backend: BroadPhaseBackend,
+}
+
+/// The algorithm used by the [broad phase](BroadPhasePlugin) for finding pairs of colliders
+/// with intersecting [AABBs](ColliderAabb). All backends produce the same [`BroadCollisionPairs`].
+#[derive(Reflect, Clone, Copy, Debug, Default, PartialEq)]
+pub enum BroadPhaseBackend {
+    /// Sorts the AABBs along the x axis and only checks overlapping intervals.
+    ///
+    /// This is fast for scenes where colliders are spread out along the x axis, but it degenerates to
+    /// checking every pair when many colliders share an x range, for example in tall stacks.
+    #[default]
+    SweepAndPrune,
+    /// An incremental bounding volume hierarchy with slightly enlarged leaf AABBs,
+    /// so that leaves only need to be reinserted when their colliders move significantly.
+    ///
+    /// This is a good general-purpose choice that handles stacks and colliders of very different sizes well.
+    DynamicAabbTree,
+    /// A uniform grid stored in a spatial hash map. Each collider is added to the cells that its AABB overlaps,
+    /// and only colliders sharing a cell are checked against each other.
+    ///
+    /// This works best when most colliders are roughly the size of a cell. Colliders that overlap
+    /// a very large number of cells, like big static floors, are checked against every other collider.
+    UniformGrid {
+        /// The side length of each grid cell.
+        ///
+        /// Must be positive and finite. Otherwise, a warning is logged and
+        /// [`BroadPhaseBackend::SweepAndPrune`] is used instead.
+        cell_size: Scalar,
+    },
+    /// Shares one acceleration structure with [spatial queries](spatial_query). The broad phase incrementally
+    /// updates the `Qbvh` of the [`SpatialQueryPipeline`] using the [`ColliderAabb`]s of changed colliders,
+    /// and queries it for pairs. The [`SpatialQueryPlugin`] then only needs to update the poses of moved colliders
+    /// instead of refitting the tree again. The colliders are read directly from their components,
+    /// so the broad phase doesn't maintain its own list of AABBs either.
+    ///
+    /// This avoids maintaining two trees in games that perform a lot of spatial queries.
+    /// Requires the [`SpatialQueryPlugin`], and falls back to [`BroadPhaseBackend::DynamicAabbTree`] without it.
+    SpatialQueryPipeline,
+}
+
+/// Filters [`BroadCollisionPairs`] using a [`CollisionPairFilter`].
+///
+/// The filter is run for every pair collected by the broad phase. Pairs that are ignored by the filter
+/// are removed from [`BroadCollisionPairs`], so no contacts are computed for them in the narrow phase.
+/// Pairs flagged as sensor-only are added to [`SensorCollisionPairs`].
+///
+/// Multiple filter plugins can be added, and a pair is kept only if it is accepted by all of them.
+///
+/// The filter systems run in [`PhysicsStepSet::BroadPhase`] after the collision pairs have been collected.
+///
+/// ## Example
+///
+/// ```no_run
+/// use bevy::{ecs::system::SystemParamItem, prelude::*};
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Projectile {
+///     shooter: Entity,
+/// }
+///
+/// // Projectiles shouldn't hit the entity that shot them
+/// struct IgnoreShooter;
+///
+/// impl CollisionPairFilter for IgnoreShooter {
+///     type Param = Query<'static, 'static, &'static Projectile>;
+///
+///     fn filter(
+///         projectiles: &SystemParamItem<Self::Param>,
+///         entity1: Entity,
+///         entity2: Entity,
+///     ) -> PairFilterResult {
+///         let shot_by = |projectile: Entity, shooter: Entity| {
+///             projectiles
+///                 .get(projectile)
+///                 .map_or(false, |projectile| projectile.shooter == shooter)
+///         };
+///
+///         if shot_by(entity1, entity2) || shot_by(entity2, entity1) {
+///             PairFilterResult::Ignore
+///         } else {
+///             PairFilterResult::Collide
+///         }
+///     }
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             PhysicsPlugins::default(),
+///             CollisionPairFilterPlugin::<IgnoreShooter>::default(),
+///         ))
+///         .run();
+/// }
+/// ```
+pub struct CollisionPairFilterPlugin<F: CollisionPairFilter>(PhantomData<F>);
+
+impl<F: CollisionPairFilter> Default for CollisionPairFilterPlugin<F> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<F: CollisionPairFilter> Plugin for CollisionPairFilterPlugin<F> {
+    fn build(&self, app: &mut App) {
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        physics_schedule.add_systems(
+            filter_collision_pairs::<F>
+                .after(collect_collision_pairs)
+                .in_set(PhysicsStepSet::BroadPhase)
+                .in_set(CollisionPairFilterSet)
+                .ambiguous_with(CollisionPairFilterSet),
+        );
+    }
+}
+
+/// The system set for the systems added by [`CollisionPairFilterPlugin`]s.
+///
+/// Filters don't depend on each other, so they can run in any order.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct CollisionPairFilterSet;
+
+/// A custom filter for the collision pairs collected by the broad phase.
+///
+/// The filter can access any data through its [`Param`](CollisionPairFilter::Param),
+/// so it can be used to filter collisions with arbitrary game logic like team membership
+/// or parent-child relationships.
+///
+/// Filters are added using the [`CollisionPairFilterPlugin`].
+pub trait CollisionPairFilter: Send + Sync + 'static {
+    /// The read-only system parameter used by the filter, for example a [`Query`] or a [`Res`].
+    ///
+    /// Use `'static` lifetimes for the parameter, like `Query<'static, 'static, &'static Team>`.
+    type Param: ReadOnlySystemParam + 'static;
+
+    /// Determines if the given pair of entities should collide, collide as sensors or be ignored.
+    fn filter(
+        param: &SystemParamItem<Self::Param>,
+        entity1: Entity,
+        entity2: Entity,
+    ) -> PairFilterResult;
+}
+
+/// The result of a [`CollisionPairFilter`] for a pair of entities.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PairFilterResult {
+    /// The entities can collide normally.
+    #[default]
+    Collide,
+    /// Collisions between the entities are detected and reported, but not resolved,
+    /// like for [sensors](Sensor).
+    Sensor,
+    /// The entities can't collide.
+    Ignore,
+}
+
+/// Removes [`BroadCollisionPairs`] rejected by the given [`CollisionPairFilter`]
+/// and collects pairs flagged as sensors into [`SensorCollisionPairs`].
+fn filter_collision_pairs<F: CollisionPairFilter>(
+    param: StaticSystemParam<F::Param>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut sensor_collision_pairs: ResMut<SensorCollisionPairs>,
+) {
+    broad_collision_pairs.0.retain(|(entity1, entity2)| {
+        match F::filter(&param, *entity1, *entity2) {
+            PairFilterResult::Collide => true,
+            PairFilterResult::Sensor => {
+                sensor_collision_pairs.0.insert((*entity1, *entity2));
+                true
+            }
+            PairFilterResult::Ignore => false,
+        }
+    });
+}
+
+type AABBChanged = Or<(
+    Changed<Collider>,
+    Changed<Position>,
+    Changed<Rotation>,
+    Changed<LinearVelocity>,
+    Changed<AngularVelocity>,
+)>;
+
+/// Updates the Axis-Aligned Bounding Boxes of all colliders. A safety margin will be added to account for sudden accelerations.
+#[allow(clippy::type_complexity)]
+fn update_aabb(
+    mut bodies: Query<
+        (
+            &Collider,
+            &mut ColliderAabb,
+            &Position,
+            &Rotation,
+            Option<&ColliderParent>,
+        ),
+        AABBChanged,
+    >,
+    velocities: Query<(&LinearVelocity, &AngularVelocity)>,
+    dt: Res<DeltaTime>,
+) {
+    // Safety margin multiplier bigger than DELTA_TIME to account for sudden accelerations
+    let safety_margin_factor = 2.0 * dt.0;
+
+    for (collider, mut aabb, pos, rot, collider_parent) in &mut bodies {
+        // Colliders move with the rigid bodies they are attached to
+        let (lin_vel, ang_vel) = collider_parent
+            .and_then(|parent| velocities.get(parent.get()).ok())
+            .unzip();
+        let lin_vel = lin_vel.map_or(Vector::ZERO, |v| v.0);
+
+        #[cfg(feature = "2d")]
+        let ang_vel_magnitude = ang_vel.map_or(0.0, |v| v.0.abs());
+        #[cfg(feature = "3d")]
+        let ang_vel_magnitude = ang_vel.map_or(0.0, |v| v.0.length());
+
+        // Compute AABB half extents and center
+        let computed_aabb = collider
+            .get_shape()
+            .compute_aabb(&utils::make_isometry(pos.0, rot));
+        let half_extents = Vector::from(computed_aabb.half_extents());
+        let center = Vector::from(computed_aabb.center());
+
+        // Todo: Somehow consider the shape of the object for the safety margin
+        // caused by angular velocity. For example, balls shouldn't get any safety margin.
+        let ang_vel_safety_margin = safety_margin_factor * ang_vel_magnitude;
+
+        // Compute AABB mins and maxs, extending them by a safety margin that depends on the velocity
+        // of the body. Linear velocity only extends the AABB in the movement direction.
+        let mut mins = center - half_extents - ang_vel_safety_margin;
+        mins += safety_margin_factor * lin_vel.min(Vector::ZERO);
+        let mut maxs = center + half_extents + ang_vel_safety_margin;
+        maxs += safety_margin_factor * lin_vel.max(Vector::ZERO);
+
+        aabb.mins.coords = mins.into();
+        aabb.maxs.coords = maxs.into();
+    }
+}
+
+/// A collider with an [AABB](ColliderAabb), along with the data used for filtering collision pairs.
+///
+/// The [`RigidBody`] is the type of the body that the collider is attached to.
+type AabbInterval = (
+    Entity,
+    Option<ColliderParent>,
+    ColliderAabb,
+    RigidBody,
+    CollisionLayers,
+);
+
+/// Entities with [`ColliderAabb`]s. With [`BroadPhaseBackend::SweepAndPrune`],
+/// they are sorted along an axis by their extents.
+#[derive(Resource, Default)]
+struct AabbIntervals(Vec<AabbInterval>);
+
+/// Updates [`AabbIntervals`] to keep them in sync with the [`ColliderAabb`]s.
+fn update_aabb_intervals(
+    aabbs: Query<(&ColliderAabb, Option<&ColliderParent>)>,
+    bodies: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+) {
+    intervals
+        .0
+        .retain_mut(|(entity, collider_parent, aabb, rb, _)| {
+            if let Ok((new_aabb, new_collider_parent)) = aabbs.get(*entity) {
+                *aabb = *new_aabb;
+                *collider_parent = new_collider_parent.copied();
+                if let Some(new_rb) =
+                    new_collider_parent.and_then(|parent| bodies.get(parent.get()).ok())
+                {
+                    *rb = *new_rb;
+                }
+                true
+            } else {
+                false
+            }
+        });
+}
+
+type AabbIntervalComponents = (
+    Entity,
+    Option<&'static ColliderParent>,
+    &'static ColliderAabb,
+    Option<&'static CollisionLayers>,
+);
+
+/// Adds new [`ColliderAabb`]s to [`AabbIntervals`].
+fn add_new_aabb_intervals(
+    aabbs: Query<AabbIntervalComponents, Added<ColliderAabb>>,
+    bodies: Query<&RigidBody>,
+    mut intervals: ResMut<AabbIntervals>,
+) {
+    let aabbs = aabbs.iter().map(|item| aabb_interval(item, &bodies));
+    intervals.0.extend(aabbs);
+}
+
+/// Creates an [`AabbInterval`] from the components of a collider.
+fn aabb_interval(
+    (entity, collider_parent, aabb, layers): ROQueryItem<AabbIntervalComponents>,
+    bodies: &Query<&RigidBody>,
+) -> AabbInterval {
+    (
+        entity,
+        collider_parent.copied(),
+        *aabb,
+        // Default to treating collider as immovable/static for filtering unnecessary collision checks
+        collider_parent
+            .and_then(|parent| bodies.get(parent.get()).ok())
+            .map_or(RigidBody::Static, |rb| *rb),
+        layers.map_or(CollisionLayers::default(), |layers| *layers),
+    )
+}
+
+/// Returns `true` if the broad phase collects pairs from [`AabbIntervals`].
+///
+/// With [`BroadPhaseBackend::SpatialQueryPipeline`], the pairs are collected from the components
+/// of the colliders instead, so the intervals don't need to be updated. They are brought up to date
+/// again if another backend is selected later.
+fn uses_aabb_intervals(
+    config: Res<BroadPhaseConfig>,
+    query_pipeline: Option<Res<SpatialQueryPipeline>>,
+    removed_colliders: Option<Res<spatial_query::RemovedColliders>>,
+) -> bool {
+    config.backend != BroadPhaseBackend::SpatialQueryPipeline
+        || query_pipeline.is_none()
+        || removed_colliders.is_none()
+}
+
+type PipelineColliderChanged = (
+    Or<(
+        Changed<ColliderAabb>,
+        Changed<Collider>,
+        Changed<CollisionLayers>,
+    )>,
+    With<ColliderAabb>,
+);
+
+/// Collects bodies that are potentially colliding using the [`BroadPhaseBackend`] in [`BroadPhaseConfig`].
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn collect_collision_pairs(
+    mut intervals: ResMut<AabbIntervals>,
+    mut aabb_tree: ResMut<aabb_tree::DynamicAabbTree>,
+    mut grid: ResMut<grid::SpatialHashGrid>,
+    query_pipeline: Option<ResMut<SpatialQueryPipeline>>,
+    removed_colliders: Option<ResMut<spatial_query::RemovedColliders>>,
+    changed_colliders: Query<
+        (
+            Entity,
+            &Position,
+            &Rotation,
+            &Collider,
+            Option<&CollisionLayers>,
+        ),
+        PipelineColliderChanged,
+    >,
+    colliders: Query<AabbIntervalComponents>,
+    bodies: Query<&RigidBody>,
+    config: Res<BroadPhaseConfig>,
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut sensor_collision_pairs: ResMut<SensorCollisionPairs>,
+) {
+    sensor_collision_pairs.0.clear();
+
+    // Clear broad phase collisions from previous iteration.
+    broad_collision_pairs.0.clear();
+
+    // The spatial query pipeline can only be shared if the spatial query plugin is enabled
+    let shared_pipeline = match (config.backend, query_pipeline, removed_colliders) {
+        (
+            BroadPhaseBackend::SpatialQueryPipeline,
+            Some(query_pipeline),
+            Some(removed_colliders),
+        ) => Some((query_pipeline, removed_colliders)),
+        _ => None,
+    };
+
+    // Free the memory used by backends that are no longer in use
+    let uses_aabb_tree = config.backend == BroadPhaseBackend::DynamicAabbTree
+        || (config.backend == BroadPhaseBackend::SpatialQueryPipeline && shared_pipeline.is_none());
+    if !uses_aabb_tree {
+        aabb_tree.clear();
+    }
+
+    match (config.backend, shared_pipeline) {
+        (BroadPhaseBackend::UniformGrid { cell_size }, _) if grid.validate_cell_size(cell_size) => {
+            grid.collect_pairs(&intervals.0, cell_size, &mut broad_collision_pairs.0)
+        }
+        (BroadPhaseBackend::SweepAndPrune | BroadPhaseBackend::UniformGrid { .. }, _) => {
+            sweep_and_prune(&mut intervals.0, &mut broad_collision_pairs.0)
+        }
+        (
+            BroadPhaseBackend::SpatialQueryPipeline,
+            Some((mut query_pipeline, mut removed_colliders)),
+        ) => {
+            query_pipeline::collect_pairs(
+                &mut query_pipeline,
+                &colliders,
+                &bodies,
+                changed_colliders.iter(),
+                removed_colliders.drain(),
+                &mut broad_collision_pairs.0,
+            );
+        }
+        (BroadPhaseBackend::DynamicAabbTree | BroadPhaseBackend::SpatialQueryPipeline, _) => {
+            aabb_tree.update(&intervals.0);
+            aabb_tree.collect_pairs(&intervals.0, &mut broad_collision_pairs.0);
+        }
+    }
+}
+
+/// The [`BroadCollisionPairs`] of the previous frame in a stable order.
+#[derive(Resource, Default)]
+struct PersistentCollisionPairs {
+    /// The pairs of the previous frame, keyed by the entities of each pair in ascending order.
+    pairs: IndexMap<(Entity, Entity), (Entity, Entity), fxhash::FxBuildHasher>,
+    /// The pairs collected during the current frame, keyed like `pairs`.
+    /// Stored here so that its allocation is reused across frames.
+    current_pairs: HashMap<(Entity, Entity), (Entity, Entity)>,
+}
+
+/// Replaces the pairs collected during the current frame with the persistent pairs of the previous frame
+/// that still exist, followed by the new pairs, and sends [`BroadPhasePairStarted`] and [`BroadPhasePairEnded`] events.
+///
+/// The backends still collect every pair from scratch each frame. Only the order of the pairs
+/// and the events are persistent, which are found by comparing the collected pairs to the previous ones.
+///
+/// Persistent pairs keep the order of their entities, so [`SensorCollisionPairs`] are updated to match it.
+fn update_persistent_pairs(
+    mut broad_collision_pairs: ResMut<BroadCollisionPairs>,
+    mut sensor_collision_pairs: ResMut<SensorCollisionPairs>,
+    mut persistent_pairs: ResMut<PersistentCollisionPairs>,
+    mut pair_started_ev_writer: EventWriter<BroadPhasePairStarted>,
+    mut pair_ended_ev_writer: EventWriter<BroadPhasePairEnded>,
+) {
+    let PersistentCollisionPairs {
+        pairs: persistent_pairs,
+        current_pairs,
+    } = &mut *persistent_pairs;
+
+    current_pairs.clear();
+    current_pairs.extend(
+        broad_collision_pairs
+            .0
+            .iter()
+            .map(|pair| (sorted_pair(*pair), *pair)),
+    );
+
+    // Remove pairs that ended, keeping the order of the remaining pairs
+    persistent_pairs.retain(|key, pair| {
+        let Some(current_pair) = current_pairs.get(key) else {
+            pair_ended_ev_writer.send(BroadPhasePairEnded(pair.0, pair.1));
+            return false;
+        };
+
+        // Keep the entity order of the persistent pair
+        if current_pair != pair && sensor_collision_pairs.0.remove(current_pair) {
+            sensor_collision_pairs.0.insert(*pair);
+        }
+
+        true
+    });
+
+    // Add new pairs to the end
+    for pair in broad_collision_pairs.0.iter() {
+        let key = sorted_pair(*pair);
+        if !persistent_pairs.contains_key(&key) {
+            persistent_pairs.insert(key, *pair);
+            pair_started_ev_writer.send(BroadPhasePairStarted(pair.0, pair.1));
+        }
+    }
+
+    broad_collision_pairs.0.clear();
+    broad_collision_pairs
+        .0
+        .extend(persistent_pairs.values().copied());
+}
+
+/// Returns the given pair with its entities in ascending order.
+fn sorted_pair((entity1, entity2): (Entity, Entity)) -> (Entity, Entity) {
+    if entity1 <= entity2 {
+        (entity1, entity2)
+    } else {
+        (entity2, entity1)
+    }
+}
+
+/// Returns `true` if the colliders of the given intervals are allowed to collide.
+///
+/// Static-static pairs, pairs with incompatible [`CollisionLayers`] and colliders attached to the same
+/// [rigid body](RigidBody) are skipped.
+fn can_collide(interval1: &AabbInterval, interval2: &AabbInterval) -> bool {
+    let (_, parent1, _, rb1, layers1) = interval1;
+    let (_, parent2, _, rb2, layers2) = interval2;
+
+    // No static-static collisions or collisions with incompatible layers
+    if (rb1.is_static() && rb2.is_static()) || !layers1.interacts_with(*layers2) {
+        return false;
+    }
+
+    // No collisions between colliders attached to the same rigid body
+    parent1.is_none() || parent1 != parent2
+}
+
+/// Sorts the entities by their minimum extents along an axis and collects the entity pairs that have intersecting AABBs.
+///
+/// Sweep and prune exploits temporal coherence, as bodies are unlikely to move significantly between two simulation steps. Insertion sort is used, as it is good at sorting nearly sorted lists efficiently.
+fn sweep_and_prune(
+    intervals: &mut Vec<AabbInterval>,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+) {
+    // Sort bodies along the x-axis using insertion sort, a sorting algorithm great for sorting nearly sorted lists.
+    insertion_sort(intervals, |a, b| a.2.mins.x > b.2.mins.x);
+
+    // Find potential collisions by checking for AABB intersections along all axes.
+    for (i, interval1) in intervals.iter().enumerate() {
+        let aabb1 = &interval1.2;
+        for interval2 in intervals.iter().skip(i + 1) {
+            let aabb2 = &interval2.2;
+
+            if !can_collide(interval1, interval2) {
+                continue;
+            }
+
+            // x doesn't intersect
+            if aabb2.mins.x > aabb1.maxs.x {
+                break;
+            }
+
+            // y doesn't intersect
+            if aabb1.mins.y > aabb2.maxs.y || aabb1.maxs.y < aabb2.mins.y {
+                continue;
+            }
+
+            #[cfg(feature = "3d")]
+            // z doesn't intersect
+            if aabb1.mins.z > aabb2.maxs.z || aabb1.maxs.z < aabb2.mins.z {
+                continue;
+            }
+
+            broad_collision_pairs.push((interval1.0, interval2.0));
+        }
+    }
+}
+
+/// Sorts a list iteratively using comparisons. In an ascending sort order, when a smaller value is encountered, it is moved lower in the list until it is larger than the item before it.
+///
+/// This is relatively slow for large lists, but very efficient in cases where the list is already mostly sorted.
+fn insertion_sort<T>(items: &mut Vec<T>, comparison: fn(&T, &T) -> bool) {
+    for i in 1..items.len() {
+        let mut j = i;
+        while j > 0 && comparison(&items[j - 1], &items[j]) {
+            items.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
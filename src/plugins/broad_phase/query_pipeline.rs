@@ -0,0 +1,65 @@
+//! Collision pair collection using the acceleration structure of the [`SpatialQueryPipeline`],
+//! used by [`BroadPhaseBackend::SpatialQueryPipeline`].
+
+use super::{aabb_interval, can_collide, AabbIntervalComponents};
+use crate::prelude::*;
+use bevy::prelude::*;
+use parry::bounding_volume::BoundingVolume;
+
+/// Updates the [`SpatialQueryPipeline`] with the changed and removed colliders and collects
+/// the pairs of colliders with intersecting AABBs that are allowed to collide.
+///
+/// The colliders are read directly from their components, so [`AabbIntervals`](super::AabbIntervals)
+/// don't need to be kept up to date with this backend.
+pub(super) fn collect_pairs<'a>(
+    pipeline: &mut SpatialQueryPipeline,
+    colliders: &Query<AabbIntervalComponents>,
+    bodies: &Query<&RigidBody>,
+    changed_colliders: impl Iterator<
+        Item = (
+            Entity,
+            &'a Position,
+            &'a Rotation,
+            &'a Collider,
+            Option<&'a CollisionLayers>,
+        ),
+    >,
+    removed_colliders: impl Iterator<Item = Entity>,
+    broad_collision_pairs: &mut Vec<(Entity, Entity)>,
+) {
+    // The leaves of the tree use the AABBs of the broad phase, which are extended by a safety margin
+    pipeline.update_from_broad_phase(changed_colliders, removed_colliders, |entity| {
+        colliders.get(entity).ok().map(|(_, _, aabb, _)| **aabb)
+    });
+
+    let mut hits = vec![];
+
+    for interval1 in colliders.iter().map(|item| aabb_interval(item, bodies)) {
+        // Static colliders can't collide with each other, so they are only found by the queries of other colliders
+        if interval1.3.is_static() {
+            continue;
+        }
+
+        hits.clear();
+        pipeline.qbvh.intersect_aabb(&interval1.2, &mut hits);
+
+        for entity_index in hits.iter() {
+            let entity2 = pipeline.entity_from_index(*entity_index);
+            let Ok(interval2) = colliders
+                .get(entity2)
+                .map(|item| aabb_interval(item, bodies))
+            else {
+                continue;
+            };
+
+            // Pairs of non-static colliders are found by both queries, so only report them once
+            if entity2 == interval1.0 || (!interval2.3.is_static() && entity2 < interval1.0) {
+                continue;
+            }
+
+            if interval1.2.intersects(&interval2.2) && can_collide(&interval1, &interval2) {
+                broad_collision_pairs.push((interval1.0, interval2.0));
+            }
+        }
+    }
+}
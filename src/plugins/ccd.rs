@@ -0,0 +1,284 @@
+//! Performs swept **Continuous Collision Detection** (CCD) for bodies with the [`SweptCcd`] component
+//! to prevent fast bodies from tunnelling through other colliders.
+//!
+//! See [`CcdPlugin`].
+
+use crate::prelude::*;
+use bevy::{ecs::query::ROQueryItem, prelude::*, utils::HashMap};
+use parry::query::{NonlinearRigidMotion, TOIStatus};
+
+/// Performs swept **Continuous Collision Detection** (CCD) for bodies with the [`SweptCcd`] component
+/// to prevent fast bodies from tunnelling through other colliders.
+///
+/// After the positions have been integrated, the [colliders](Collider) of each body with [`SweptCcd`],
+/// including colliders on descendant entities, are swept from the body's [`PreviousPosition`] and [`PreviousRotation`]
+/// to its new position and rotation against the other colliders in [`BroadCollisionPairs`],
+/// taking both translation and rotation into account.
+///
+/// If the sweep hits something, the motion of the body along the contact normal is clamped at the first time of impact,
+/// leaving a small penetration that is then resolved by the [solver] like any other contact.
+/// Motion along the contact surface is kept, so bodies can still slide along the surfaces they hit.
+///
+/// Bodies that move less than a quarter of their smallest extent during a substep can't tunnel, so they are skipped.
+///
+/// The CCD system runs in the [`SubstepSchedule`] after [`SubstepSet::Integrate`] and before [`SubstepSet::NarrowPhase`].
+pub struct CcdPlugin;
+
+impl Plugin for CcdPlugin {
+    fn build(&self, app: &mut App) {
+        let substep_schedule = app
+            .get_schedule_mut(SubstepSchedule)
+            .expect("add SubstepSchedule first");
+
+        substep_schedule.add_systems(
+            solve_swept_ccd
+                .after(SubstepSet::Integrate)
+                .before(SubstepSet::NarrowPhase),
+        );
+    }
+}
+
+type CcdColliderComponents = (
+    Option<&'static ColliderParent>,
+    Option<&'static ColliderTransform>,
+    &'static Position,
+    &'static Rotation,
+    &'static Collider,
+    Option<&'static CollisionLayers>,
+    Option<&'static Sensor>,
+);
+
+type CcdBodyComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static mut AccumulatedTranslation,
+    &'static Rotation,
+    Option<&'static PreviousPosition>,
+    Option<&'static PreviousRotation>,
+    Option<&'static SweptCcd>,
+    Option<&'static Sleeping>,
+);
+
+/// The earliest impact found for a body using swept CCD during the current substep.
+struct SweptImpact {
+    /// The time of impact as a fraction of the body's motion during the substep, from 0 to 1.
+    toi: Scalar,
+    /// The world-space contact normal pointing away from the body using CCD.
+    normal: Vector,
+    /// How much the body is allowed to penetrate the hit collider. The [solver] resolves the rest.
+    allowed_penetration: Scalar,
+}
+
+/// Sweeps the colliders of bodies with [`SweptCcd`] from their previous poses to their current poses
+/// and clamps the motion of the bodies at the first time of impact.
+///
+/// Colliders are moved with the [rigid bodies](RigidBody) they are attached to, so child colliders
+/// are swept using the motion of their body and their [`ColliderTransform`].
+fn solve_swept_ccd(
+    colliders: Query<CcdColliderComponents>,
+    mut bodies: Query<CcdBodyComponents>,
+    broad_collision_pairs: Res<BroadCollisionPairs>,
+    sensor_collision_pairs: Res<SensorCollisionPairs>,
+) {
+    let mut impacts: HashMap<Entity, SweptImpact> = HashMap::default();
+
+    for (entity1, entity2) in broad_collision_pairs.0.iter() {
+        let Ok([collider1, collider2]) = colliders.get_many([*entity1, *entity2]) else {
+            continue;
+        };
+
+        // Colliders attached to the same rigid body can't collide with each other
+        if collider1.0.is_some() && collider1.0 == collider2.0 {
+            continue;
+        }
+
+        // Sensors never stop bodies
+        if collider1.6.is_some()
+            || collider2.6.is_some()
+            || sensor_collision_pairs.0.contains(&(*entity1, *entity2))
+        {
+            continue;
+        }
+
+        let layers1 = collider1.5.map_or(CollisionLayers::default(), |l| *l);
+        let layers2 = collider2.5.map_or(CollisionLayers::default(), |l| *l);
+        if !layers1.interacts_with(layers2) {
+            continue;
+        }
+
+        let body1 = collider1.0.and_then(|parent| bodies.get(parent.get()).ok());
+        let body2 = collider2.0.and_then(|parent| bodies.get(parent.get()).ok());
+
+        let motion1 = compute_swept_motion(&collider1, body1.as_ref());
+        let motion2 = compute_swept_motion(&collider2, body2.as_ref());
+
+        // Only dynamic bodies with CCD that move far enough to tunnel need to be swept
+        let ccd1 = motion1.delta.filter(|_| {
+            body1
+                .as_ref()
+                .map_or(false, |body| body.6.is_some() && body.0.is_dynamic())
+        });
+        let ccd2 = motion2.delta.filter(|_| {
+            body2
+                .as_ref()
+                .map_or(false, |body| body.6.is_some() && body.0.is_dynamic())
+        });
+        if ccd1.is_none() && ccd2.is_none() {
+            continue;
+        }
+
+        let Ok(Some(toi)) = parry::query::nonlinear_time_of_impact(
+            &motion1.motion,
+            collider1.4.get_shape().0.as_ref(),
+            &motion2.motion,
+            collider2.4.get_shape().0.as_ref(),
+            0.0,
+            1.0,
+            true,
+        ) else {
+            continue;
+        };
+
+        // Bodies that are already penetrating are handled by the normal contact constraints
+        if matches!(toi.status, TOIStatus::Penetrating | TOIStatus::Failed) {
+            continue;
+        }
+
+        let pos1 = motion1.motion.position_at_time(toi.toi);
+        let pos2 = motion2.motion.position_at_time(toi.toi);
+
+        for (parent, delta, allowed_penetration, normal) in [
+            (
+                collider1.0,
+                ccd1,
+                motion1.allowed_penetration,
+                pos1.rotation * toi.normal1.into_inner(),
+            ),
+            (
+                collider2.0,
+                ccd2,
+                motion2.allowed_penetration,
+                pos2.rotation * toi.normal2.into_inner(),
+            ),
+        ] {
+            let (Some(parent), Some(_)) = (parent, delta) else {
+                continue;
+            };
+            let is_earliest = impacts
+                .get(&parent.get())
+                .map_or(true, |impact| toi.toi < impact.toi);
+            if is_earliest {
+                impacts.insert(
+                    parent.get(),
+                    SweptImpact {
+                        toi: toi.toi,
+                        normal: normal.into(),
+                        allowed_penetration,
+                    },
+                );
+            }
+        }
+    }
+
+    for (entity, impact) in impacts {
+        let Ok((_, pos, mut translation, _, Some(prev_pos), ..)) = bodies.get_mut(entity) else {
+            continue;
+        };
+
+        let delta = pos.0 + translation.0 - prev_pos.0;
+
+        // How far the body moves towards the hit collider during the substep
+        let approach = delta.dot(impact.normal);
+        if approach <= 0.0 {
+            continue;
+        }
+
+        // Remove the part of the motion that would go past the time of impact,
+        // leaving the allowed penetration for the contact constraints to resolve.
+        let excess = approach * (1.0 - impact.toi) - impact.allowed_penetration;
+        if excess > 0.0 {
+            translation.0 -= impact.normal * excess;
+        }
+    }
+}
+
+/// The motion of a collider during the current substep.
+struct SweptMotion {
+    motion: NonlinearRigidMotion,
+    /// The translation of the collider's body during the substep, or `None` if the body can't tunnel.
+    delta: Option<Vector>,
+    /// How much the collider is allowed to penetrate other colliders before CCD is needed.
+    allowed_penetration: Scalar,
+}
+
+/// Computes the motion of a collider during the current substep from the motion of the
+/// [rigid body](RigidBody) it is attached to. Colliders without a body don't move.
+fn compute_swept_motion(
+    (_, collider_transform, position, rotation, collider, ..): &ROQueryItem<CcdColliderComponents>,
+    body: Option<&ROQueryItem<CcdBodyComponents>>,
+) -> SweptMotion {
+    // A quarter of the smallest extent of the collider
+    let allowed_penetration = 0.5
+        * collider
+            .get_shape()
+            .compute_local_aabb()
+            .half_extents()
+            .min();
+
+    let Some((rb, body_pos, translation, body_rot, prev_pos, prev_rot, _, sleeping)) = body else {
+        return SweptMotion {
+            motion: NonlinearRigidMotion::constant_position(utils::make_isometry(
+                position.0, rotation,
+            )),
+            delta: None,
+            allowed_penetration,
+        };
+    };
+
+    let collider_transform = collider_transform.copied().unwrap_or_default();
+    let current_pos = body_pos.0 + translation.0;
+
+    let is_moving = !rb.is_static() && sleeping.is_none();
+    let (Some(prev_pos), Some(prev_rot), true) = (prev_pos, prev_rot, is_moving) else {
+        let (position, rotation) = collider_transform.global_pose(current_pos, body_rot);
+        return SweptMotion {
+            motion: NonlinearRigidMotion::constant_position(utils::make_isometry(
+                position, &rotation,
+            )),
+            delta: None,
+            allowed_penetration,
+        };
+    };
+
+    let delta = current_pos - prev_pos.0;
+
+    #[cfg(feature = "2d")]
+    let ang_vel = body_rot.mul(prev_rot.0.inverse()).as_radians();
+    #[cfg(feature = "3d")]
+    let ang_vel = {
+        let mut delta_rot = body_rot.0 * prev_rot.0.inverse().0;
+        // Take the shortest path
+        if delta_rot.w < 0.0 {
+            delta_rot = -delta_rot;
+        }
+        delta_rot.to_scaled_axis().into()
+    };
+
+    // The collider rotates around the origin of its body, which is at this point in the local space of the collider
+    let local_center = collider_transform
+        .rotation
+        .inverse()
+        .rotate(-collider_transform.translation);
+    let (start_pos, start_rot) = collider_transform.global_pose(prev_pos.0, &prev_rot.0);
+
+    SweptMotion {
+        motion: NonlinearRigidMotion::new(
+            utils::make_isometry(start_pos, &start_rot),
+            local_center.into(),
+            delta.into(),
+            ang_vel,
+        ),
+        delta: (delta.length() > allowed_penetration).then_some(delta),
+        allowed_penetration,
+    }
+}
@@ -71,6 +71,7 @@ impl Plugin for PhysicsDebugPlugin {
                     debug_render_joints::<DistanceJoint>,
                     debug_render_joints::<RevoluteJoint>,
                     debug_render_joints::<SphericalJoint>,
+                    debug_render_joints::<D6Joint>,
                     change_mesh_visibility,
                 )
                     .after(PhysicsSet::StepSimulation)
@@ -80,11 +81,25 @@ impl Plugin for PhysicsDebugPlugin {
 }
 
 fn debug_render_axes(
-    bodies: Query<(&Position, &Rotation, &CenterOfMass, Option<&DebugRender>)>,
+    bodies: Query<(
+        Entity,
+        &Position,
+        &Rotation,
+        &CenterOfMass,
+        Option<&DebugRender>,
+    )>,
+    world_body: Option<Res<WorldBody>>,
     mut debug_renderer: PhysicsDebugRenderer,
     config: Res<PhysicsDebugConfig>,
 ) {
-    for (pos, rot, local_com, render_config) in &bodies {
+    for (entity, pos, rot, local_com, render_config) in &bodies {
+        // The world body only represents the world frame, so it has no axes worth drawing
+        if world_body
+            .as_ref()
+            .is_some_and(|world| world.entity() == entity)
+        {
+            continue;
+        }
         if let Some(lengths) = render_config.map_or(config.axis_lengths, |c| c.axis_lengths) {
             let global_com = pos.0 + rot.rotate(local_com.0);
             let x = rot.rotate(Vector::X * lengths.x);
@@ -199,22 +214,40 @@ fn debug_render_colliders(
 fn debug_render_joints<T: Joint>(
     bodies: Query<(&Position, &Rotation)>,
     joints: Query<&T>,
+    world_body: Option<Res<WorldBody>>,
     mut debug_renderer: PhysicsDebugRenderer,
     config: Res<PhysicsDebugConfig>,
 ) {
     for joint in &joints {
         if let Ok([(pos1, rot1), (pos2, rot2)]) = bodies.get_many(joint.entities()) {
             if let Some(anchor_color) = config.joint_anchor_color {
-                debug_renderer.draw_line(
-                    pos1.0,
-                    pos1.0 + rot1.rotate(joint.local_anchor_1()),
-                    anchor_color,
-                );
-                debug_renderer.draw_line(
-                    pos2.0,
-                    pos2.0 + rot2.rotate(joint.local_anchor_2()),
-                    anchor_color,
-                );
+                for (entity, pos, rot, local_anchor) in [
+                    (joint.entities()[0], pos1, rot1, joint.local_anchor_1()),
+                    (joint.entities()[1], pos2, rot2, joint.local_anchor_2()),
+                ] {
+                    let anchor = pos.0 + rot.rotate(local_anchor);
+
+                    // Mark world anchors with a cross instead of drawing a line from the world origin
+                    if world_body
+                        .as_ref()
+                        .is_some_and(|world| world.entity() == entity)
+                    {
+                        #[cfg(feature = "2d")]
+                        let axes = [Vector::X, Vector::Y];
+                        #[cfg(feature = "3d")]
+                        let axes = [Vector::X, Vector::Y, Vector::Z];
+
+                        for axis in axes {
+                            debug_renderer.draw_line(
+                                anchor - axis * 0.1,
+                                anchor + axis * 0.1,
+                                anchor_color,
+                            );
+                        }
+                    } else {
+                        debug_renderer.draw_line(pos.0, anchor, anchor_color);
+                    }
+                }
             }
             if let Some(separation_color) = config.joint_separation_color {
                 debug_renderer.draw_line(
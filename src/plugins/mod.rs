@@ -21,6 +21,7 @@
 //! - [`SubstepSchedule`] and [`SubstepSet`]
 
 pub mod broad_phase;
+pub mod ccd;
 #[cfg(feature = "debug-plugin")]
 pub mod debug;
 pub mod integrator;
@@ -32,7 +33,11 @@ pub mod solver;
 pub mod spatial_query;
 pub mod sync;
 
-pub use broad_phase::BroadPhasePlugin;
+pub use broad_phase::{
+    BroadPhaseBackend, BroadPhaseConfig, BroadPhasePairEnded, BroadPhasePairStarted,
+    BroadPhasePlugin, CollisionPairFilter, CollisionPairFilterPlugin, PairFilterResult,
+};
+pub use ccd::CcdPlugin;
 #[cfg(feature = "debug-plugin")]
 pub use debug::*;
 pub use integrator::IntegratorPlugin;
@@ -40,7 +45,7 @@ pub use narrow_phase::*;
 pub use prepare::PreparePlugin;
 pub use setup::*;
 pub use sleeping::SleepingPlugin;
-pub use solver::{solve_constraint, SolverPlugin};
+pub use solver::{break_joints, solve_constraint, SolverPlugin};
 pub use spatial_query::*;
 pub use sync::SyncPlugin;
 
@@ -58,6 +63,7 @@ use bevy::prelude::*;
 /// - [`BroadPhasePlugin`]: Collects pairs of potentially colliding entities into [`BroadCollisionPairs`] using
 /// [AABB](ColliderAabb) intersection checks.
 /// - [`IntegratorPlugin`]: Integrates Newton's 2nd law of motion, applying forces and moving entities according to their velocities.
+/// - [`CcdPlugin`]: Performs swept continuous collision detection for bodies with [`SweptCcd`] to prevent tunnelling.
 /// - [`NarrowPhasePlugin`]: Computes contacts between entities and sends collision events.
 /// - [`SolverPlugin`]: Solves positional and angular [constraints], updates velocities and solves velocity constraints
 /// (dynamic [friction](Friction) and [restitution](Restitution)).
@@ -196,6 +202,7 @@ impl PluginGroup for PhysicsPlugins {
             .add(PreparePlugin::new(self.schedule.dyn_clone()))
             .add(BroadPhasePlugin)
             .add(IntegratorPlugin)
+            .add(CcdPlugin)
             .add(NarrowPhasePlugin)
             .add(SolverPlugin)
             .add(SleepingPlugin)
@@ -14,6 +14,9 @@ use parry::query::{PersistentQueryDispatcher, QueryDispatcher};
 /// Collisions are only checked between entities contained in [`BroadCollisionPairs`],
 /// which is handled by the [`BroadPhasePlugin`].
 ///
+/// The computed contacts are stored in [`Collisions`]. They can be modified before they are
+/// handled by the [solver] using systems in [`SubstepSet::PostProcessCollisions`].
+///
 /// The following collision events are sent each frame:
 ///
 /// - [`Collision`]
@@ -142,6 +145,17 @@ impl Collisions {
             })
     }
 
+    /// Retains only the collisions for which the given predicate returns `true`.
+    ///
+    /// This can be used in [`SubstepSet::PostProcessCollisions`] to ignore collisions
+    /// before they are handled by the [solver].
+    pub fn retain<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&mut Contacts) -> bool,
+    {
+        self.0.retain(|_, contacts| keep(contacts));
+    }
+
     /// Removes collisions against the given entity from the `HashMap`.
     fn remove_collisions_with_entity(&mut self, entity: Entity) {
         self.0
@@ -166,18 +180,30 @@ pub struct CollisionStarted(pub Entity, pub Entity);
 #[derive(Event, Clone, Debug, PartialEq)]
 pub struct CollisionEnded(pub Entity, pub Entity);
 
-#[allow(clippy::too_many_arguments)]
-#[allow(clippy::type_complexity)]
+type ColliderComponents = (
+    Option<&'static ColliderParent>,
+    Option<&'static ColliderTransform>,
+    &'static Position,
+    &'static Rotation,
+    &'static Collider,
+    Option<&'static CollisionLayers>,
+);
+
+type ColliderBodyComponents = (
+    &'static RigidBody,
+    &'static Position,
+    &'static AccumulatedTranslation,
+    &'static Rotation,
+    Option<&'static Sleeping>,
+);
+
+/// Computes contacts between the colliders in [`BroadCollisionPairs`] and stores them in [`Collisions`].
+///
+/// Colliders are positioned using the current positions and rotations of the [rigid bodies](RigidBody)
+/// they are attached to, so child colliders move with their bodies during substeps.
 fn collect_collisions(
-    bodies: Query<(
-        Option<&RigidBody>,
-        &Position,
-        Option<&AccumulatedTranslation>,
-        &Rotation,
-        &Collider,
-        Option<&CollisionLayers>,
-        Option<&Sleeping>,
-    )>,
+    colliders: Query<ColliderComponents>,
+    bodies: Query<ColliderBodyComponents>,
     broad_collision_pairs: Res<BroadCollisionPairs>,
     mut collisions: ResMut<Collisions>,
     narrow_phase_config: Res<NarrowPhaseConfig>,
@@ -191,47 +217,14 @@ fn collect_collisions(
             .par_splat_map(pool, None, |chunks| {
                 let mut collisions: Vec<((Entity, Entity), Contacts)> = vec![];
                 for (entity1, entity2) in chunks {
-                    if let Ok([bundle1, bundle2]) = bodies.get_many([*entity1, *entity2]) {
-                        let (
-                            rb1,
-                            position1,
-                            accumulated_translation1,
-                            rotation1,
-                            collider1,
-                            layers1,
-                            sleeping1,
-                        ) = bundle1;
-                        let (
-                            rb2,
-                            position2,
-                            accumulated_translation2,
-                            rotation2,
-                            collider2,
-                            layers2,
-                            sleeping2,
-                        ) = bundle2;
-
-                        if check_collision_validity(
-                            rb1, rb2, layers1, layers2, sleeping1, sleeping2,
-                        ) {
-                            let contacts = compute_contacts(
-                                *entity1,
-                                *entity2,
-                                position1.0
-                                    + accumulated_translation1.map_or(Vector::default(), |t| t.0),
-                                position2.0
-                                    + accumulated_translation2.map_or(Vector::default(), |t| t.0),
-                                rotation1,
-                                rotation2,
-                                collider1,
-                                collider2,
-                                narrow_phase_config.prediction_distance,
-                            );
-
-                            if !contacts.manifolds.is_empty() {
-                                collisions.push(((*entity1, *entity2), contacts));
-                            }
-                        }
+                    if let Some(contacts) = collide_pair(
+                        *entity1,
+                        *entity2,
+                        &colliders,
+                        &bodies,
+                        narrow_phase_config.prediction_distance,
+                    ) {
+                        collisions.push(((*entity1, *entity2), contacts));
                     }
                 }
                 collisions
@@ -243,48 +236,91 @@ fn collect_collisions(
     #[cfg(not(feature = "parallel"))]
     {
         for (entity1, entity2) in broad_collision_pairs.0.iter() {
-            if let Ok([bundle1, bundle2]) = bodies.get_many([*entity1, *entity2]) {
-                let (
-                    rb1,
-                    position1,
-                    accumulated_translation1,
-                    rotation1,
-                    collider1,
-                    layers1,
-                    sleeping1,
-                ) = bundle1;
-                let (
-                    rb2,
-                    position2,
-                    accumulated_translation2,
-                    rotation2,
-                    collider2,
-                    layers2,
-                    sleeping2,
-                ) = bundle2;
-
-                if check_collision_validity(rb1, rb2, layers1, layers2, sleeping1, sleeping2) {
-                    let contacts = compute_contacts(
-                        *entity1,
-                        *entity2,
-                        position1.0 + accumulated_translation1.map_or(Vector::default(), |t| t.0),
-                        position2.0 + accumulated_translation2.map_or(Vector::default(), |t| t.0),
-                        rotation1,
-                        rotation2,
-                        collider1,
-                        collider2,
-                        narrow_phase_config.prediction_distance,
-                    );
-
-                    if !contacts.manifolds.is_empty() {
-                        collisions.0.insert((*entity1, *entity2), contacts);
-                    }
-                }
+            if let Some(contacts) = collide_pair(
+                *entity1,
+                *entity2,
+                &colliders,
+                &bodies,
+                narrow_phase_config.prediction_distance,
+            ) {
+                collisions.0.insert((*entity1, *entity2), contacts);
             }
         }
     }
 }
 
+/// Computes the contacts between two colliders if they can collide and are in contact.
+fn collide_pair(
+    entity1: Entity,
+    entity2: Entity,
+    colliders: &Query<ColliderComponents>,
+    bodies: &Query<ColliderBodyComponents>,
+    prediction_distance: Scalar,
+) -> Option<Contacts> {
+    let [(parent1, collider_transform1, position1, rotation1, collider1, layers1), (parent2, collider_transform2, position2, rotation2, collider2, layers2)] =
+        colliders.get_many([entity1, entity2]).ok()?;
+
+    // Colliders attached to the same rigid body can't collide with each other
+    if parent1.is_some() && parent1 == parent2 {
+        return None;
+    }
+
+    let body1 = parent1.and_then(|parent| bodies.get(parent.get()).ok());
+    let body2 = parent2.and_then(|parent| bodies.get(parent.get()).ok());
+
+    if !check_collision_validity(
+        body1.map(|(rb, ..)| rb),
+        body2.map(|(rb, ..)| rb),
+        layers1,
+        layers2,
+        body1.and_then(|(.., sleeping)| sleeping),
+        body2.and_then(|(.., sleeping)| sleeping),
+    ) {
+        return None;
+    }
+
+    let (position1, rotation1) = collider_pose(position1, rotation1, collider_transform1, body1);
+    let (position2, rotation2) = collider_pose(position2, rotation2, collider_transform2, body2);
+
+    let contacts = compute_contacts(
+        entity1,
+        entity2,
+        position1,
+        position2,
+        &rotation1,
+        &rotation2,
+        collider1,
+        collider2,
+        prediction_distance,
+    );
+
+    (!contacts.manifolds.is_empty()).then_some(contacts)
+}
+
+/// Computes the current global position and rotation of a collider
+/// based on the rigid body it is attached to, if any.
+fn collider_pose(
+    position: &Position,
+    rotation: &Rotation,
+    collider_transform: Option<&ColliderTransform>,
+    body: Option<(
+        &RigidBody,
+        &Position,
+        &AccumulatedTranslation,
+        &Rotation,
+        Option<&Sleeping>,
+    )>,
+) -> (Vector, Rotation) {
+    match (collider_transform, body) {
+        (
+            Some(collider_transform),
+            Some((_, body_position, accumulated_translation, body_rotation, _)),
+        ) => collider_transform
+            .global_pose(body_position.0 + accumulated_translation.0, body_rotation),
+        _ => (position.0, *rotation),
+    }
+}
+
 fn check_collision_validity(
     rb1: Option<&RigidBody>,
     rb2: Option<&RigidBody>,
@@ -458,12 +494,37 @@ pub struct ContactData {
     pub normal: Vector,
     /// Penetration depth.
     pub penetration: Scalar,
+    /// Overrides the combined [friction](Friction) of the colliding bodies for this contact.
+    ///
+    /// This is `None` by default, and it can be set in [`SubstepSet::PostProcessCollisions`].
+    pub friction: Option<Friction>,
+    /// Overrides the combined [restitution](Restitution) of the colliding bodies for this contact.
+    ///
+    /// This is `None` by default, and it can be set in [`SubstepSet::PostProcessCollisions`].
+    pub restitution: Option<Restitution>,
     /// True if both colliders are convex. Currently, contacts between
     /// convex and non-convex colliders have to be handled differently.
     pub(crate) convex: bool,
 }
 
 impl ContactData {
+    /// Creates a new [`ContactData`] with the given local contact points, local contact normal
+    /// and penetration depth, without [friction](Friction) or [restitution](Restitution) overrides.
+    ///
+    /// The contact is treated as a contact between convex colliders, so its penetration depth
+    /// is recomputed from the contact points while it is being solved.
+    pub fn new(point1: Vector, point2: Vector, normal: Vector, penetration: Scalar) -> Self {
+        Self {
+            point1,
+            point2,
+            normal,
+            penetration,
+            friction: None,
+            restitution: None,
+            convex: true,
+        }
+    }
+
     /// Returns the global contact point on the first entity,
     /// transforming the local point by the given entity position and rotation.
     pub fn global_point1(&self, position: &Position, rotation: &Rotation) -> Vector {
@@ -528,6 +589,8 @@ pub(crate) fn compute_contacts(
                             point2: contact.local_p2.into(),
                             normal: manifold.local_n1.into(),
                             penetration: -contact.dist,
+                            friction: None,
+                            restitution: None,
                             convex,
                         })
                         .collect(),
@@ -553,6 +616,8 @@ pub(crate) fn compute_contacts(
                 point2: contact.point2.into(),
                 normal: contact.normal1.into(),
                 penetration: -contact.dist,
+                friction: None,
+                restitution: None,
                 convex,
             });
         Contacts {
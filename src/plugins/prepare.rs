@@ -4,16 +4,22 @@
 //! See [`PreparePlugin`].
 
 use crate::prelude::*;
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 
 /// Runs systems at the start of each physics frame; initializes [rigid bodies](RigidBody)
 /// and [colliders](Collider) and updates components.
 ///
 /// - Adds missing rigid body components for entities with a [`RigidBody`] component
 /// - Adds missing collider components for entities with a [`Collider`] component
+/// - Attaches colliders to the [rigid bodies](RigidBody) on the same entity or on ancestor entities
+/// using [`ColliderParent`] and [`ColliderTransform`]
+/// - Scales colliders based on the scale of their `GlobalTransform`
 /// - Updates mass properties and adds [`ColliderMassProperties`] on top of the existing mass properties
+/// - Removes the mass properties of removed and despawned colliders from their bodies
+/// - Updates the [`Position`] and [`Rotation`] of colliders attached to ancestor rigid bodies
 ///
-/// The systems run in [`PhysicsSet::Prepare`].
+/// The systems run in [`PhysicsSet::Prepare`], except for the positions of child colliders,
+/// which are updated after [`PhysicsStepSet::Substeps`].
 pub struct PreparePlugin {
     schedule: Box<dyn ScheduleLabel>,
 }
@@ -37,22 +43,44 @@ impl Default for PreparePlugin {
 
 impl Plugin for PreparePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            self.schedule.dyn_clone(),
-            (
+        app.init_resource::<ColliderMassContributions>()
+            .add_systems(
+                self.schedule.dyn_clone(),
                 (
-                    bevy::transform::systems::sync_simple_transforms,
-                    bevy::transform::systems::propagate_transforms,
-                    init_rigid_bodies,
+                    (
+                        bevy::transform::systems::sync_simple_transforms,
+                        bevy::transform::systems::propagate_transforms,
+                        init_rigid_bodies,
+                    )
+                        .chain()
+                        .run_if(any_new_rigid_bodies),
+                    init_mass_properties,
+                    init_colliders,
+                    remove_collider_mass_properties,
+                    // Bodies and colliders need their components before they can be attached
+                    apply_deferred,
+                    update_collider_parents,
+                    apply_deferred,
+                    update_collider_transforms,
+                    update_collider_scale,
+                    update_collider_mass_properties,
+                    track_collider_mass_contributions,
+                    update_mass_properties,
                 )
                     .chain()
-                    .run_if(any_new_rigid_bodies),
-                init_mass_properties,
-                init_colliders,
-                update_mass_properties,
-            )
-                .chain()
-                .in_set(PhysicsSet::Prepare),
+                    .in_set(PhysicsSet::Prepare),
+            );
+
+        let physics_schedule = app
+            .get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first");
+
+        // Move child colliders with their bodies for the spatial queries and the next frame's broad phase.
+        // Newly attached colliders already have the pose of their `GlobalTransform` from `init_colliders`.
+        physics_schedule.add_systems(
+            update_child_collider_positions
+                .after(PhysicsStepSet::Substeps)
+                .before(PhysicsStepSet::Sleeping),
         );
     }
 }
@@ -318,60 +346,336 @@ fn init_colliders(
     }
 }
 
-type MassPropertiesComponents = (
+type ColliderParentComponents = (
     Entity,
-    Option<&'static RigidBody>,
-    MassPropertiesQuery,
-    Option<&'static Collider>,
-    Option<&'static mut ColliderMassProperties>,
+    Option<&'static ColliderParent>,
     Option<&'static mut PreviousColliderMassProperties>,
 );
 
-type MassPropertiesChanged = Or<(
-    Changed<Mass>,
-    Changed<InverseMass>,
-    Changed<Inertia>,
-    Changed<InverseInertia>,
-    Changed<Collider>,
-    Changed<ColliderMassProperties>,
-)>;
+type HierarchyChanged = Or<(Added<Collider>, Added<RigidBody>, Changed<Parent>)>;
 
-/// Updates each body's mass properties whenever their dependant mass properties or the body's [`Collider`] change.
+/// Attaches new and reparented colliders to the closest [rigid body](RigidBody) in their ancestors,
+/// including the collider entity itself, by inserting a [`ColliderParent`].
 ///
-/// Also updates the collider's mass properties if the body has a collider.
-fn update_mass_properties(mut bodies: Query<MassPropertiesComponents, MassPropertiesChanged>) {
+/// Colliders are also attached again when a rigid body is added to or removed from one of their ancestors
+/// or when one of their ancestors is reparented.
+///
+/// If a collider is moved to another body or removed from its parent entity,
+/// its mass properties are removed from the previous body.
+#[allow(clippy::too_many_arguments)]
+fn update_collider_parents(
+    mut commands: Commands,
+    mut colliders: Query<ColliderParentComponents, With<Collider>>,
+    changed_hierarchies: Query<Entity, HierarchyChanged>,
+    mut removed_parents: RemovedComponents<Parent>,
+    mut removed_bodies: RemovedComponents<RigidBody>,
+    mut bodies: Query<MassPropertiesQuery, With<RigidBody>>,
+    parents: Query<&Parent>,
+    children: Query<&Children>,
+) {
+    // The colliders below a changed entity may be attached to a different body now
+    let mut changed: Vec<Entity> = changed_hierarchies
+        .iter()
+        .chain(removed_parents.iter())
+        .chain(removed_bodies.iter())
+        .flat_map(|entity| std::iter::once(entity).chain(children.iter_descendants(entity)))
+        .collect();
+    changed.sort_unstable();
+    changed.dedup();
+
+    for entity in changed {
+        let Ok((entity, collider_parent, previous_mass_properties)) = colliders.get_mut(entity)
+        else {
+            continue;
+        };
+
+        // Find the closest rigid body in the ancestors of the collider
+        let mut body_entity = Some(entity);
+        while let Some(current) = body_entity {
+            if bodies.contains(current) {
+                break;
+            }
+            body_entity = parents.get(current).ok().map(|parent| parent.get());
+        }
+
+        if collider_parent.map(|parent| parent.get()) == body_entity {
+            continue;
+        }
+
+        // Remove the collider's mass properties from the previous body
+        if let (Some(collider_parent), Some(mut previous_mass_properties)) =
+            (collider_parent, previous_mass_properties)
+        {
+            if let Ok(mut mass_properties) = bodies.get_mut(collider_parent.get()) {
+                mass_properties -= previous_mass_properties.0;
+            }
+            previous_mass_properties.0 = ColliderMassProperties::ZERO;
+        }
+
+        let mut entity_commands = commands.entity(entity);
+
+        if let Some(body_entity) = body_entity {
+            entity_commands.insert((ColliderParent(body_entity), ColliderTransform::default()));
+        } else {
+            entity_commands.remove::<(ColliderParent, ColliderTransform)>();
+        }
+    }
+}
+
+/// The [rigid bodies](RigidBody) that colliders are attached to and the mass properties that the colliders
+/// have added to them, so that the mass properties can be removed when a collider is removed or despawned.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ColliderMassContributions(HashMap<Entity, (Entity, ColliderMassProperties)>);
+
+/// Removes the mass properties of removed and despawned colliders from the bodies they were attached to.
+fn remove_collider_mass_properties(
+    mut commands: Commands,
+    mut removed_colliders: RemovedComponents<Collider>,
+    mut contributions: ResMut<ColliderMassContributions>,
+    mut bodies: Query<MassPropertiesQuery, With<RigidBody>>,
+    mut previous_mass_properties: Query<&mut PreviousColliderMassProperties, Without<Collider>>,
+    colliders: Query<(), With<Collider>>,
+) {
+    for entity in removed_colliders.iter() {
+        // The collider may have been inserted again, in which case its mass properties are simply updated
+        if colliders.contains(entity) {
+            continue;
+        }
+
+        let Some((body, mass_properties)) = contributions.remove(&entity) else {
+            continue;
+        };
+
+        if let Ok(mut body_mass_properties) = bodies.get_mut(body) {
+            body_mass_properties -= mass_properties;
+        }
+
+        // Detach the collider if only the `Collider` component was removed
+        if let Ok(mut previous_mass_properties) = previous_mass_properties.get_mut(entity) {
+            previous_mass_properties.0 = ColliderMassProperties::ZERO;
+            commands
+                .entity(entity)
+                .remove::<(ColliderParent, ColliderTransform)>();
+        }
+    }
+}
+
+/// Stores the mass properties that colliders have added to their bodies in [`ColliderMassContributions`].
+fn track_collider_mass_contributions(
+    colliders: Query<
+        (
+            Entity,
+            Option<&ColliderParent>,
+            &PreviousColliderMassProperties,
+        ),
+        Changed<PreviousColliderMassProperties>,
+    >,
+    mut contributions: ResMut<ColliderMassContributions>,
+) {
+    for (entity, collider_parent, previous_mass_properties) in &colliders {
+        if let Some(collider_parent) = collider_parent {
+            contributions.insert(entity, (collider_parent.get(), previous_mass_properties.0));
+        } else {
+            contributions.remove(&entity);
+        }
+    }
+}
+
+type ColliderTransformComponents = (
+    Entity,
+    Ref<'static, ColliderParent>,
+    &'static mut ColliderTransform,
+    &'static mut Position,
+    &'static mut Rotation,
+);
+
+/// Updates the [`ColliderTransform`] of each collider based on the `Transform` hierarchy
+/// between the collider and the [rigid body](RigidBody) it is attached to.
+///
+/// Newly attached colliders and colliders whose [`ColliderTransform`] changed are also moved to their
+/// place on the body, so that they are up to date on their first physics frame.
+fn update_collider_transforms(
+    mut colliders: Query<ColliderTransformComponents, Without<RigidBody>>,
+    bodies: Query<(&Position, &Rotation, Option<&GlobalTransform>), With<RigidBody>>,
+    transforms: Query<(&Transform, Option<&Parent>)>,
+) {
     for (
         entity,
-        rb,
-        mut mass_properties,
-        collider,
-        collider_mass_properties,
-        previous_collider_mass_properties,
-    ) in &mut bodies
+        collider_parent,
+        mut collider_transform,
+        mut collider_position,
+        mut collider_rotation,
+    ) in &mut colliders
     {
-        if mass_properties.mass.is_changed() && mass_properties.mass.0 >= Scalar::EPSILON {
-            mass_properties.inverse_mass.0 = 1.0 / mass_properties.mass.0;
+        if entity == collider_parent.get() {
+            continue;
         }
 
-        if let Some(collider) = collider {
-            let Some(mut collider_mass_properties) = collider_mass_properties else {
-                continue;
+        let Ok((body_position, body_rotation, body_global_transform)) =
+            bodies.get(collider_parent.get())
+        else {
+            continue;
+        };
+
+        // Combine the local transforms of the collider and its ancestors up to the body
+        let mut transform = Transform::IDENTITY;
+        let mut current = entity;
+        while current != collider_parent.get() {
+            let Ok((local_transform, parent)) = transforms.get(current) else {
+                break;
+            };
+            transform = local_transform.mul_transform(transform);
+            let Some(parent) = parent else {
+                break;
             };
-            let Some(mut previous_collider_mass_properties) = previous_collider_mass_properties
-            else {
-                continue;
+            current = parent.get();
+        }
+
+        // The offset is scaled by the global scale of the body, like the shape of the collider
+        let body_scale = body_global_transform.map_or(Vec3::ONE, |global_transform| {
+            global_transform.compute_transform().scale
+        });
+        #[cfg(feature = "2d")]
+        let translation = (body_scale * transform.translation)
+            .truncate()
+            .adjust_precision();
+        #[cfg(feature = "3d")]
+        let translation = (body_scale * transform.translation).adjust_precision();
+        let rotation = Rotation::from(transform.rotation.adjust_precision());
+
+        // Only trigger change detection if the transform actually changed, ignoring floating point error
+        // from decomposing the `GlobalTransform` of the body
+        if !collider_transform.translation.abs_diff_eq(
+            translation,
+            SCALE_EPSILON * translation.abs().max_element().max(1.0),
+        ) || Quaternion::from(collider_transform.rotation) != Quaternion::from(rotation)
+        {
+            *collider_transform = ColliderTransform {
+                translation,
+                rotation,
             };
+        }
+
+        if collider_parent.is_changed() || collider_transform.is_changed() {
+            let (new_position, new_rotation) =
+                collider_transform.global_pose(body_position.0, body_rotation);
+            collider_position.set_if_neq(Position(new_position));
+            collider_rotation.set_if_neq(new_rotation);
+        }
+    }
+}
+
+/// The relative change in scale below which colliders aren't rescaled.
+const SCALE_EPSILON: Scalar = 1.0e-5;
+
+/// Updates the scale of colliders based on the scale of their `GlobalTransform`.
+///
+/// The shapes are only rescaled when the scale or the unscaled shape actually changes,
+/// which also updates the mass properties.
+#[allow(clippy::type_complexity)]
+fn update_collider_scale(
+    mut colliders: Query<
+        (&mut Collider, &GlobalTransform),
+        Or<(Changed<GlobalTransform>, Changed<Collider>)>,
+    >,
+) {
+    for (mut collider, global_transform) in &mut colliders {
+        // Shapes modified through the deprecated `get_shape_mut` need to be rescaled
+        if collider.is_shape_modified() {
+            let shape = collider.get_unscaled_shape().clone();
+            collider.set_shape(shape);
+        }
+
+        #[cfg(feature = "2d")]
+        let scale = global_transform
+            .compute_transform()
+            .scale
+            .truncate()
+            .adjust_precision();
+        #[cfg(feature = "3d")]
+        let scale = global_transform
+            .compute_transform()
+            .scale
+            .adjust_precision();
+
+        // Only trigger change detection if the scale actually changed. Decomposing a rotated
+        // `GlobalTransform` introduces floating point error, which would otherwise make uniformly
+        // scaled shapes like balls non-uniform and approximate them with convex hulls.
+        let previous_scale = collider.scale();
+        if (scale - previous_scale).abs().max_element()
+            > SCALE_EPSILON * previous_scale.abs().max_element()
+        {
+            collider.set_scale(scale, 10);
+        }
+    }
+}
+
+type ColliderMassPropertiesComponents = (
+    &'static ColliderParent,
+    &'static Collider,
+    &'static ColliderTransform,
+    &'static mut ColliderMassProperties,
+    &'static mut PreviousColliderMassProperties,
+);
+
+type ColliderMassPropertiesChanged = Or<(
+    Changed<Collider>,
+    Changed<ColliderParent>,
+    Changed<ColliderTransform>,
+    Changed<ColliderMassProperties>,
+)>;
 
-            // Subtract previous collider mass props from the body's mass props
-            mass_properties -= previous_collider_mass_properties.0;
+/// Updates the mass properties of colliders and adds them to the mass properties of the
+/// [rigid bodies](RigidBody) they are attached to, whether the colliders are on the body entity
+/// itself or on its descendants.
+fn update_collider_mass_properties(
+    mut bodies: Query<MassPropertiesQuery, With<RigidBody>>,
+    mut colliders: Query<ColliderMassPropertiesComponents, ColliderMassPropertiesChanged>,
+) {
+    for (
+        collider_parent,
+        collider,
+        collider_transform,
+        mut collider_mass_properties,
+        mut previous_collider_mass_properties,
+    ) in &mut colliders
+    {
+        let Ok(mut mass_properties) = bodies.get_mut(collider_parent.get()) else {
+            continue;
+        };
+
+        // Subtract previous collider mass props from the body's mass props
+        mass_properties -= previous_collider_mass_properties.0;
 
-            // Update previous and current collider mass props
-            previous_collider_mass_properties.0 = *collider_mass_properties;
-            *collider_mass_properties =
-                ColliderMassProperties::new_computed(collider, collider_mass_properties.density);
+        // Update current collider mass props without triggering change detection unnecessarily
+        let new_collider_mass_properties =
+            ColliderMassProperties::new_computed(collider, collider_mass_properties.density);
+        collider_mass_properties.set_if_neq(new_collider_mass_properties);
 
-            // Add new collider mass props to the body's mass props
-            mass_properties += *collider_mass_properties;
+        // Add new collider mass props to the body's mass props in the body's local space
+        let body_space_mass_properties =
+            new_collider_mass_properties.transformed_by(collider_transform);
+        mass_properties += body_space_mass_properties;
+        previous_collider_mass_properties.0 = body_space_mass_properties;
+    }
+}
+
+type MassPropertiesComponents = (Entity, Option<&'static RigidBody>, MassPropertiesQuery);
+
+type MassPropertiesChanged = Or<(
+    Changed<Mass>,
+    Changed<InverseMass>,
+    Changed<Inertia>,
+    Changed<InverseInertia>,
+)>;
+
+/// Updates each body's mass properties whenever their dependant mass properties change.
+///
+/// The mass properties of colliders are added to the bodies in `update_collider_mass_properties`.
+fn update_mass_properties(mut bodies: Query<MassPropertiesComponents, MassPropertiesChanged>) {
+    for (entity, rb, mut mass_properties) in &mut bodies {
+        if mass_properties.mass.is_changed() && mass_properties.mass.0 >= Scalar::EPSILON {
+            mass_properties.inverse_mass.0 = 1.0 / mass_properties.mass.0;
         }
 
         // Warn about dynamic bodies with no mass or inertia
@@ -393,3 +697,40 @@ fn update_mass_properties(mut bodies: Query<MassPropertiesComponents, MassProper
         }
     }
 }
+
+/// Updates the [`Position`] and [`Rotation`] of colliders attached to ancestor [rigid bodies](RigidBody)
+/// based on the positions and rotations of the bodies and the [`ColliderTransform`]s of the colliders.
+///
+/// Colliders are only updated when the pose of their body or their [`ColliderTransform`] has changed.
+#[allow(clippy::type_complexity)]
+fn update_child_collider_positions(
+    mut colliders: Query<
+        (
+            Ref<ColliderParent>,
+            Ref<ColliderTransform>,
+            &mut Position,
+            &mut Rotation,
+        ),
+        Without<RigidBody>,
+    >,
+    bodies: Query<(Ref<Position>, Ref<Rotation>), With<RigidBody>>,
+) {
+    for (collider_parent, collider_transform, mut position, mut rotation) in &mut colliders {
+        let Ok((body_position, body_rotation)) = bodies.get(collider_parent.get()) else {
+            continue;
+        };
+
+        if !(collider_parent.is_changed()
+            || collider_transform.is_changed()
+            || body_position.is_changed()
+            || body_rotation.is_changed())
+        {
+            continue;
+        }
+
+        let (new_position, new_rotation) =
+            collider_transform.global_pose(body_position.0, &body_rotation);
+        position.set_if_neq(Position(new_position));
+        rotation.set_if_neq(new_rotation);
+    }
+}
@@ -59,6 +59,7 @@ impl Plugin for PhysicsSetupPlugin {
             .init_resource::<SubstepCount>()
             .init_resource::<IterationCount>()
             .init_resource::<BroadCollisionPairs>()
+            .init_resource::<SensorCollisionPairs>()
             .init_resource::<SleepingThreshold>()
             .init_resource::<DeactivationTime>()
             .init_resource::<PhysicsLoop>()
@@ -69,6 +70,7 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<SubstepCount>()
             .register_type::<IterationCount>()
             .register_type::<BroadCollisionPairs>()
+            .register_type::<SensorCollisionPairs>()
             .register_type::<SleepingThreshold>()
             .register_type::<DeactivationTime>()
             .register_type::<PhysicsLoop>()
@@ -76,6 +78,7 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<RigidBody>()
             .register_type::<Sleeping>()
             .register_type::<SleepingDisabled>()
+            .register_type::<SweptCcd>()
             .register_type::<TimeSleeping>()
             .register_type::<Position>()
             .register_type::<Rotation>()
@@ -102,6 +105,8 @@ impl Plugin for PhysicsSetupPlugin {
             .register_type::<CenterOfMass>()
             .register_type::<LockedAxes>()
             .register_type::<CollisionLayers>()
+            .register_type::<ColliderParent>()
+            .register_type::<ColliderTransform>()
             .register_type::<CollidingEntities>();
 
         // Configure higher level system sets for the given schedule
@@ -158,6 +163,7 @@ impl Plugin for PhysicsSetupPlugin {
             (
                 SubstepSet::Integrate,
                 SubstepSet::NarrowPhase,
+                SubstepSet::PostProcessCollisions,
                 SubstepSet::SolveConstraints,
                 SubstepSet::SolveUserConstraints,
                 SubstepSet::UpdateVelocities,
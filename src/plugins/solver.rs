@@ -26,6 +26,13 @@ use constraints::penetration::PenetrationConstraint;
 /// 3. **Velocity solve**: Velocity corrections caused by dynamic friction, restitution and joint damping are applied.
 /// Runs in [`SubstepSet::SolveVelocities`].
 ///
+/// After the constraints have been solved, [breakable joints](BreakableJoint) whose force or torque exceeded
+/// their break thresholds during the substep are removed, and [`JointBroken`] events are sent for them.
+/// This runs every substep, so joints break as soon as any substep of a step exceeds a threshold.
+///
+/// The plugin also spawns the static [`WorldBody`] that joints can be attached to in order to pin bodies to world points,
+/// and spawns it again if it is despawned.
+///
 /// In the case of collisions, [`PenetrationConstraint`]s are created for each contact pair.
 /// The constraints are resolved by moving the bodies so that they no longer penetrate.
 /// Then, the velocities are updated, and velocity corrections caused by dynamic friction and restitution are applied.
@@ -33,7 +40,13 @@ pub struct SolverPlugin;
 
 impl Plugin for SolverPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PenetrationConstraints>();
+        app.init_resource::<PenetrationConstraints>()
+            .init_resource::<WorldBody>()
+            .add_event::<JointBroken>();
+
+        app.get_schedule_mut(PhysicsSchedule)
+            .expect("add PhysicsSchedule first")
+            .add_systems(recreate_world_body.before(PhysicsStepSet::BroadPhase));
 
         let substeps = app
             .get_schedule_mut(SubstepSchedule)
@@ -47,11 +60,30 @@ impl Plugin for SolverPlugin {
                 solve_constraint::<SphericalJoint, 2>,
                 solve_constraint::<PrismaticJoint, 2>,
                 solve_constraint::<DistanceJoint, 2>,
+                solve_constraint::<D6Joint, 2>,
+                solve_constraint::<GearJoint, 2>,
+                solve_constraint::<PulleyJoint, 2>,
             )
                 .chain()
                 .in_set(SubstepSet::SolveConstraints),
         );
 
+        substeps.add_systems(
+            (
+                break_joints::<FixedJoint>,
+                break_joints::<RevoluteJoint>,
+                break_joints::<SphericalJoint>,
+                break_joints::<PrismaticJoint>,
+                break_joints::<DistanceJoint>,
+                break_joints::<D6Joint>,
+                break_joints::<GearJoint>,
+                break_joints::<PulleyJoint>,
+            )
+                .chain()
+                .after(SubstepSet::SolveUserConstraints)
+                .before(SubstepSet::UpdateVelocities),
+        );
+
         substeps.add_systems((update_lin_vel, update_ang_vel).in_set(SubstepSet::UpdateVelocities));
 
         substeps.add_systems(
@@ -62,6 +94,9 @@ impl Plugin for SolverPlugin {
                 joint_damping::<SphericalJoint>,
                 joint_damping::<PrismaticJoint>,
                 joint_damping::<DistanceJoint>,
+                joint_damping::<D6Joint>,
+                gear_damping,
+                pulley_damping,
             )
                 .chain()
                 .in_set(SubstepSet::SolveVelocities),
@@ -76,13 +111,17 @@ impl Plugin for SolverPlugin {
 pub struct PenetrationConstraints(pub Vec<PenetrationConstraint>);
 
 /// Iterates through broad phase collision pairs, checks which ones are actually colliding, and uses [`PenetrationConstraint`]s to resolve the collisions.
+///
+/// The contacts between colliders are resolved against the [rigid bodies](RigidBody) that the colliders are attached to.
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::type_complexity)]
 fn penetration_constraints(
     mut commands: Commands,
-    mut bodies: Query<(RigidBodyQuery, Option<&Sensor>, Option<&Sleeping>)>,
+    mut bodies: Query<(RigidBodyQuery, Option<&Sleeping>)>,
+    colliders: Query<(&ColliderParent, &ColliderTransform, Option<&Sensor>)>,
     mut penetration_constraints: ResMut<PenetrationConstraints>,
     collisions: Res<Collisions>,
+    sensor_collision_pairs: Res<SensorCollisionPairs>,
     sub_dt: Res<SubDeltaTime>,
 ) {
     penetration_constraints.0.clear();
@@ -92,9 +131,18 @@ fn penetration_constraints(
         .iter()
         .filter(|(_, contacts)| contacts.during_current_substep)
     {
-        if let Ok([bundle1, bundle2]) = bodies.get_many_mut([*entity1, *entity2]) {
-            let (mut body1, sensor1, sleeping1) = bundle1;
-            let (mut body2, sensor2, sleeping2) = bundle2;
+        let Ok(
+            [(collider_parent1, collider_transform1, sensor1), (collider_parent2, collider_transform2, sensor2)],
+        ) = colliders.get_many([*entity1, *entity2])
+        else {
+            continue;
+        };
+
+        if let Ok([bundle1, bundle2]) =
+            bodies.get_many_mut([collider_parent1.get(), collider_parent2.get()])
+        {
+            let (mut body1, sleeping1) = bundle1;
+            let (mut body2, sleeping2) = bundle2;
 
             let inactive1 = body1.rb.is_static() || sleeping1.is_some();
             let inactive2 = body2.rb.is_static() || sleeping2.is_some();
@@ -104,18 +152,28 @@ fn penetration_constraints(
                 continue;
             }
 
+            let is_sensor_pair = sensor_collision_pairs.0.contains(&(*entity1, *entity2));
+
             // Create and solve constraint if both colliders are solid
-            if sensor1.is_none() && sensor2.is_none() {
+            if sensor1.is_none() && sensor2.is_none() && !is_sensor_pair {
                 // When an active body collides with a sleeping body, wake up the sleeping body
                 if sleeping1.is_some() {
-                    commands.entity(*entity1).remove::<Sleeping>();
+                    commands.entity(body1.entity).remove::<Sleeping>();
                 } else if sleeping2.is_some() {
-                    commands.entity(*entity2).remove::<Sleeping>();
+                    commands.entity(body2.entity).remove::<Sleeping>();
                 }
 
                 for contact_manifold in contacts.manifolds.iter() {
                     for contact in contact_manifold.contacts.iter() {
-                        let mut constraint = PenetrationConstraint::new(&body1, &body2, *contact);
+                        // Transform the contact from the local space of the colliders
+                        // to the local space of the bodies
+                        let contact = ContactData {
+                            point1: collider_transform1.transform_point(contact.point1),
+                            point2: collider_transform2.transform_point(contact.point2),
+                            normal: collider_transform1.transform_vector(contact.normal),
+                            ..*contact
+                        };
+                        let mut constraint = PenetrationConstraint::new(&body1, &body2, contact);
                         constraint.solve([&mut body1, &mut body2], sub_dt.0);
                         penetration_constraints.0.push(constraint);
                     }
@@ -345,7 +403,11 @@ fn solve_vel(
             // Compute dynamic friction
             let friction_impulse = get_dynamic_friction(
                 tangent_vel,
-                body1.friction.combine(*body2.friction).dynamic_coefficient,
+                constraint
+                    .contact
+                    .friction
+                    .unwrap_or(body1.friction.combine(*body2.friction))
+                    .dynamic_coefficient,
                 constraint.normal_lagrange,
                 sub_dt.0,
             );
@@ -355,7 +417,11 @@ fn solve_vel(
                 normal,
                 normal_vel,
                 pre_solve_normal_vel,
-                body1.restitution.combine(*body2.restitution).coefficient,
+                constraint
+                    .contact
+                    .restitution
+                    .unwrap_or(body1.restitution.combine(*body2.restitution))
+                    .coefficient,
                 gravity.0,
                 sub_dt.0,
             );
@@ -472,3 +538,154 @@ fn compute_delta_ang_vel(inverse_inertia: Scalar, r: Vector, p: Vector) -> Scala
 fn compute_delta_ang_vel(inverse_inertia: Matrix3, r: Vector, p: Vector) -> Vector {
     inverse_inertia * r.cross(p)
 }
+
+/// Applies velocity corrections caused by the damping of [gear joints](GearJoint).
+fn gear_damping(
+    mut bodies: Query<
+        (&RigidBody, &Rotation, &mut AngularVelocity, &InverseInertia),
+        Without<Sleeping>,
+    >,
+    joints: Query<&GearJoint, Without<RigidBody>>,
+    sub_dt: Res<SubDeltaTime>,
+) {
+    for joint in &joints {
+        if let Ok(
+            [(rb1, rot1, mut ang_vel1, inv_inertia1), (rb2, rot2, mut ang_vel2, inv_inertia2)],
+        ) = bodies.get_many_mut(joint.entities())
+        {
+            let [n1, n2] = joint.world_axes(rot1, rot2);
+            let inv_inertia1 = inv_inertia1.rotated(rot1).0;
+            let inv_inertia2 = inv_inertia2.rotated(rot2).0;
+
+            // The velocity of the coupled rotation, which is zero when the bodies rotate at the gear ratio
+            #[cfg(feature = "2d")]
+            let (velocity, w1, w2) = (
+                ang_vel1.0 * n1.z + joint.ratio * ang_vel2.0 * n2.z,
+                inv_inertia1,
+                inv_inertia2,
+            );
+            #[cfg(feature = "3d")]
+            let (velocity, w1, w2) = (
+                ang_vel1.dot(n1) + joint.ratio * ang_vel2.dot(n2),
+                n1.dot(inv_inertia1 * n1),
+                n2.dot(inv_inertia2 * n2),
+            );
+
+            let w1 = if rb1.is_dynamic() { w1 } else { 0.0 };
+            let w2 = if rb2.is_dynamic() { w2 } else { 0.0 };
+            let w_sum = w1 + joint.ratio.powi(2) * w2;
+
+            if w_sum <= Scalar::EPSILON {
+                continue;
+            }
+
+            let p = -velocity * (joint.damping_angular * sub_dt.0).min(1.0) / w_sum;
+
+            if rb1.is_dynamic() {
+                #[cfg(feature = "2d")]
+                {
+                    ang_vel1.0 += inv_inertia1 * p * n1.z;
+                }
+                #[cfg(feature = "3d")]
+                {
+                    ang_vel1.0 += inv_inertia1 * (p * n1);
+                }
+            }
+            if rb2.is_dynamic() {
+                #[cfg(feature = "2d")]
+                {
+                    ang_vel2.0 += inv_inertia2 * p * joint.ratio * n2.z;
+                }
+                #[cfg(feature = "3d")]
+                {
+                    ang_vel2.0 += inv_inertia2 * (p * joint.ratio * n2);
+                }
+            }
+        }
+    }
+}
+
+/// Applies velocity corrections caused by the damping of [pulley joints](PulleyJoint).
+#[allow(clippy::type_complexity)]
+fn pulley_damping(
+    mut bodies: Query<
+        (
+            &RigidBody,
+            &Position,
+            &AccumulatedTranslation,
+            &Rotation,
+            &mut LinearVelocity,
+            &InverseMass,
+        ),
+        Without<Sleeping>,
+    >,
+    joints: Query<&PulleyJoint, Without<RigidBody>>,
+    sub_dt: Res<SubDeltaTime>,
+) {
+    for joint in &joints {
+        if let Ok(
+            [(rb1, pos1, translation1, rot1, mut lin_vel1, inv_mass1), (rb2, pos2, translation2, rot2, mut lin_vel2, inv_mass2)],
+        ) = bodies.get_many_mut(joint.entities())
+        {
+            let Some([(n1, _), (n2, _)]) = joint.compute_rope_segments(
+                pos1.0 + translation1.0 + rot1.rotate(joint.local_anchor1),
+                pos2.0 + translation2.0 + rot2.rotate(joint.local_anchor2),
+            ) else {
+                continue;
+            };
+
+            // The velocity along the rope, which is zero when the total length of the rope doesn't change
+            let velocity = lin_vel1.dot(n1) + joint.ratio * lin_vel2.dot(n2);
+
+            let w1 = if rb1.is_dynamic() { inv_mass1.0 } else { 0.0 };
+            let w2 = if rb2.is_dynamic() { inv_mass2.0 } else { 0.0 };
+            let w_sum = w1 + joint.ratio.powi(2) * w2;
+
+            if w_sum <= Scalar::EPSILON {
+                continue;
+            }
+
+            let p = -velocity * (joint.damping_linear * sub_dt.0).min(1.0) / w_sum;
+
+            lin_vel1.0 += p * n1 * w1;
+            lin_vel2.0 += p * joint.ratio * n2 * w2;
+        }
+    }
+}
+
+/// Removes joints whose force or torque exceeded their `break_force` or `break_torque`
+/// during the current substep and sends [`JointBroken`] events for them.
+///
+/// The built-in joints are registered by the [`SolverPlugin`]. Custom [breakable joints](BreakableJoint)
+/// can be registered in the [`SubstepSchedule`] after the constraints have been solved:
+///
+/// ```ignore
+/// let substeps = app
+///     .get_schedule_mut(SubstepSchedule)
+///     .expect("add SubstepSchedule first");
+///
+/// substeps.add_systems(
+///     break_joints::<YourJoint>
+///         .after(SubstepSet::SolveUserConstraints)
+///         .before(SubstepSet::UpdateVelocities),
+/// );
+/// ```
+pub fn break_joints<T: BreakableJoint>(
+    mut commands: Commands,
+    joints: Query<(Entity, &T)>,
+    mut joint_broken_ev_writer: EventWriter<JointBroken>,
+) {
+    for (entity, joint) in &joints {
+        if joint.is_broken() {
+            let [entity1, entity2] = joint.entities();
+            commands.entity(entity).remove::<T>();
+            joint_broken_ev_writer.send(JointBroken {
+                joint: entity,
+                entity1,
+                entity2,
+                force: joint.force(),
+                torque: joint.torque(),
+            });
+        }
+    }
+}
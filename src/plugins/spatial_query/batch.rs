@@ -0,0 +1,225 @@
+//! Batched [spatial queries](spatial_query) that perform many queries of the same type at once.
+//!
+//! When the `parallel` feature is enabled, the queries are distributed over the [`ComputeTaskPool`].
+//! The results are always returned in the same order as the requests.
+
+use crate::prelude::*;
+#[cfg(feature = "parallel")]
+use bevy::tasks::{ComputeTaskPool, ParallelSlice};
+
+/// A [ray cast](spatial_query#ray-casting) request used for batched ray casts with
+/// [`SpatialQuery::cast_rays`] and [`SpatialQueryPipeline::cast_rays`].
+#[derive(Clone)]
+pub struct RayRequest {
+    /// Where the ray is cast from.
+    pub origin: Vector,
+    /// What direction the ray is cast in.
+    pub direction: Vector,
+    /// The maximum distance that the ray can travel.
+    pub max_time_of_impact: Scalar,
+    /// If true and the ray origin is inside of a collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    pub solid: bool,
+    /// Determines which colliders are taken into account in the query.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl RayRequest {
+    /// Creates a new [`RayRequest`] with a given origin and direction.
+    /// The ray is solid and has no maximum time of impact by default.
+    pub fn new(origin: Vector, direction: Vector) -> Self {
+        Self {
+            origin,
+            direction,
+            max_time_of_impact: Scalar::MAX,
+            solid: true,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+
+    /// Sets the maximum time of impact, i.e. the maximum distance that the ray is allowed to travel.
+    pub fn with_max_time_of_impact(mut self, max_time_of_impact: Scalar) -> Self {
+        self.max_time_of_impact = max_time_of_impact;
+        self
+    }
+
+    /// Sets if the ray treats [colliders](Collider) as solid.
+    pub fn with_solidness(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Sets the [query filter](SpatialQueryFilter) that controls which colliders are included in the ray cast.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+}
+
+/// A [shape cast](spatial_query#shape-casting) request used for batched shape casts with
+/// [`SpatialQuery::cast_shapes`] and [`SpatialQueryPipeline::cast_shapes`].
+#[derive(Clone)]
+pub struct ShapeCastRequest {
+    /// The shape being cast represented as a [`Collider`].
+    pub shape: Collider,
+    /// Where the shape is cast from.
+    pub origin: Vector,
+    /// The rotation of the shape being cast.
+    pub shape_rotation: RotationValue,
+    /// What direction the shape is cast in.
+    pub direction: Vector,
+    /// The maximum distance that the shape can travel.
+    pub max_time_of_impact: Scalar,
+    /// If true and the shape is already penetrating a collider at the shape origin,
+    /// the hit will be ignored and only the next hit will be computed.
+    pub ignore_origin_penetration: bool,
+    /// Determines which colliders are taken into account in the query.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl ShapeCastRequest {
+    /// Creates a new [`ShapeCastRequest`] with a given shape, origin, shape rotation and direction.
+    /// The cast has no maximum time of impact by default.
+    pub fn new(
+        shape: Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Vector,
+    ) -> Self {
+        Self {
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact: Scalar::MAX,
+            ignore_origin_penetration: false,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+
+    /// Sets the maximum time of impact, i.e. the maximum distance that the shape is allowed to travel.
+    pub fn with_max_time_of_impact(mut self, max_time_of_impact: Scalar) -> Self {
+        self.max_time_of_impact = max_time_of_impact;
+        self
+    }
+
+    /// Sets if initial penetration at the shape origin should be ignored.
+    pub fn with_ignore_origin_penetration(mut self, ignore: bool) -> Self {
+        self.ignore_origin_penetration = ignore;
+        self
+    }
+
+    /// Sets the [query filter](SpatialQueryFilter) that controls which colliders are included in the shape cast.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+}
+
+/// A [point projection](spatial_query#point-projection) request used for batched point projections with
+/// [`SpatialQuery::project_points`] and [`SpatialQueryPipeline::project_points`].
+#[derive(Clone)]
+pub struct PointProjectionRequest {
+    /// The point to project.
+    pub point: Vector,
+    /// If true and the point is inside of a collider, the projection will be at the point itself.
+    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
+    pub solid: bool,
+    /// Determines which colliders are taken into account in the query.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl PointProjectionRequest {
+    /// Creates a new solid [`PointProjectionRequest`] for the given point.
+    pub fn new(point: Vector) -> Self {
+        Self {
+            point,
+            solid: true,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+
+    /// Sets if colliders are treated as solid.
+    pub fn with_solidness(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Sets the [query filter](SpatialQueryFilter) that controls which colliders are included in the projection.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+}
+
+impl SpatialQueryPipeline {
+    /// Casts a batch of [rays](spatial_query#ray-casting) and computes the closest [hit](RayHitData)
+    /// for each of them. The results are in the same order as the requests.
+    ///
+    /// See also: [`SpatialQuery::cast_rays`]
+    pub fn cast_rays(&self, requests: &[RayRequest]) -> Vec<Option<RayHitData>> {
+        map_batch(requests, |request| {
+            self.cast_ray(
+                request.origin,
+                request.direction,
+                request.max_time_of_impact,
+                request.solid,
+                request.query_filter.clone(),
+            )
+        })
+    }
+
+    /// Casts a batch of [shapes](spatial_query#shape-casting) and computes the closest [hit](ShapeHitData)
+    /// for each of them. The results are in the same order as the requests.
+    ///
+    /// See also: [`SpatialQuery::cast_shapes`]
+    pub fn cast_shapes(&self, requests: &[ShapeCastRequest]) -> Vec<Option<ShapeHitData>> {
+        map_batch(requests, |request| {
+            self.cast_shape(
+                &request.shape,
+                request.origin,
+                request.shape_rotation,
+                request.direction,
+                request.max_time_of_impact,
+                request.ignore_origin_penetration,
+                request.query_filter.clone(),
+            )
+        })
+    }
+
+    /// Finds the [projection](spatial_query#point-projection) of each point in a batch on the closest collider.
+    /// The results are in the same order as the requests.
+    ///
+    /// See also: [`SpatialQuery::project_points`]
+    pub fn project_points(
+        &self,
+        requests: &[PointProjectionRequest],
+    ) -> Vec<Option<PointProjection>> {
+        map_batch(requests, |request| {
+            self.project_point(request.point, request.solid, request.query_filter.clone())
+        })
+    }
+}
+
+/// Performs the given query for each request, distributing the requests over the [`ComputeTaskPool`]
+/// if the `parallel` feature is enabled. The results are in the same order as the requests.
+fn map_batch<T: Sync, R: Send + 'static>(
+    requests: &[T],
+    query: impl Fn(&T) -> R + Send + Sync,
+) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        // The chunks are returned in order, so the results stay deterministic
+        requests
+            .par_splat_map(ComputeTaskPool::get(), None, |chunk| {
+                chunk.iter().map(&query).collect::<Vec<R>>()
+            })
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        requests.iter().map(query).collect()
+    }
+}
@@ -15,9 +15,11 @@
 //! a variety of things like getting information about the environment for character controllers and AI,
 //! and even rendering using ray tracing.
 //!
-//! For each hit during ray casting, the hit entity, a *time of impact* and a normal will be stored in [`RayHitData`].
-//! The time of impact refers to how long the ray travelled, which is essentially the distance from the ray origin to
-//! the point of intersection.
+//! For each hit during ray casting, the hit entity, a *time of impact*, the point of intersection, a normal
+//! and the hit [feature](FeatureId) will be stored in [`RayHitData`]. The time of impact refers to how long the ray
+//! travelled, which is essentially the distance from the ray origin to the point of intersection.
+//! The feature can be used to find the triangle of a triangle mesh that was hit, for example to look up
+//! per-triangle surface materials.
 //!
 //! There are two ways to perform ray casts.
 //!
@@ -28,6 +30,12 @@
 //! [`SpatialQuery`], like [`cast_ray`](SpatialQuery#method.cast_ray), [`ray_hits`](SpatialQuery#method.ray_hits) or
 //! [`ray_hits_callback`](SpatialQuery#method.ray_hits_callback).
 //!
+//! Many rays in a fan, cone, grid or custom pattern can be cast from a single entity with the [`MultiRayCaster`]
+//! component, which stores the hits of each ray in the [`MultiRayHits`] component.
+//!
+//! To react when a [`RayCaster`] starts or stops hitting an entity, enable its `hit_events` and read the
+//! [`RayHitStarted`] and [`RayHitEnded`] events.
+//!
 //! See the documentation of the components and methods for more information.
 //!
 //! A simple example using the component-based method looks like this:
@@ -46,14 +54,14 @@
 //!     // ...spawn colliders and other things
 //! }
 //!
-//! fn print_hits(query: Query<(&RayCaster, &RayHits)>) {
-//!     for (ray, hits) in &query {
+//! fn print_hits(query: Query<&RayHits, With<RayCaster>>) {
+//!     for hits in &query {
 //!         // For the faster iterator that isn't sorted, use `.iter()`
 //!         for hit in hits.iter_sorted() {
 //!             println!(
 //!                 "Hit entity {:?} at {} with normal {}",
 //!                 hit.entity,
-//!                 ray.origin + ray.direction * hit.time_of_impact,
+//!                 hit.point,
 //!                 hit.normal,
 //!             );
 //!         }
@@ -83,6 +91,12 @@
 //! [`SpatialQuery`], like [`cast_shape`](SpatialQuery#method.cast_shape), [`shape_hits`](SpatialQuery#method.shape_hits) or
 //! [`shape_hits_callback`](SpatialQuery#method.shape_hits_callback).
 //!
+//! Shapes can also be swept with a linear and angular velocity over a time interval using
+//! [`cast_shape_nonlinear`](SpatialQuery#method.cast_shape_nonlinear) or [`ShapeCastMode::Nonlinear`].
+//! In this case, the time of impact is the time at which the shape first hits a collider.
+//!
+//! Like ray casters, shape casters with `hit_events` enabled send [`ShapeHitStarted`] and [`ShapeHitEnded`] events.
+//!
 //! See the documentation of the components and methods for more information.
 //!
 //! A simple example using the component-based method looks like this:
@@ -125,6 +139,10 @@
 //! Point projection can be done with the [`project_point`](SpatialQuery#method.project_point) method of the [`SpatialQuery`]
 //! system parameter. See its documentation for more information.
 //!
+//! To find several colliders sorted by their distance to a point, use [`nearest_colliders`](SpatialQuery#method.nearest_colliders)
+//! for the `k` closest colliders, or [`colliders_within_radius`](SpatialQuery#method.colliders_within_radius)
+//! for all colliders within a given distance.
+//!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
 //!
 //! ## Intersection tests
@@ -145,21 +163,58 @@
 //! See the documentation of the components and methods for more information.
 //!
 //! To specify which colliders should be considered in the query, use a [spatial query filter](`SpatialQueryFilter`).
+//!
+//! ## Entity-targeted queries
+//!
+//! Ray casts, shape casts, point projections and intersection tests can also be performed against the collider
+//! of a single known entity using [`cast_ray_on`](SpatialQuery#method.cast_ray_on),
+//! [`cast_shape_on`](SpatialQuery#method.cast_shape_on), [`project_point_on`](SpatialQuery#method.project_point_on)
+//! and [`intersects`](SpatialQuery#method.intersects). They don't traverse the acceleration structure,
+//! and they don't need a query filter.
+//!
+//! ## Distance queries
+//!
+//! [`closest_points`](SpatialQuery#method.closest_points) computes the closest points, the normal and the distance
+//! between the colliders of two entities that aren't necessarily touching, and [`distance`](SpatialQuery#method.distance)
+//! finds the collider closest to a given shape. The results are described by [`ClosestPointsData`].
+//!
+//! [`compute_penetration`](SpatialQuery#method.compute_penetration) computes the [penetration](PenetrationData)
+//! of a shape into each collider that it overlaps, and [`compute_depenetration`](SpatialQuery#method.compute_depenetration)
+//! computes a single translation that moves the shape out of all of them. This can be used for resolving
+//! overlaps without running a physics step, for example after teleporting a character.
+//!
+//! ## Batched queries
+//!
+//! When many queries of the same type are needed at once, for example for AI perception, they can be performed
+//! in a batch with [`cast_rays`](SpatialQuery#method.cast_rays), [`cast_shapes`](SpatialQuery#method.cast_shapes)
+//! and [`project_points`](SpatialQuery#method.project_points). Each request is described by a [`RayRequest`],
+//! [`ShapeCastRequest`] or [`PointProjectionRequest`], and the results are returned in the same order as the requests.
+//!
+//! With the `parallel` feature, the queries of a batch are performed in parallel.
 
+mod batch;
+mod multi_ray_caster;
 mod pipeline;
 mod query_filter;
 mod ray_caster;
 mod shape_caster;
 mod system_param;
 
+pub use batch::*;
+pub use multi_ray_caster::*;
 pub use pipeline::*;
 pub use query_filter::*;
 pub use ray_caster::*;
 pub use shape_caster::*;
 pub use system_param::*;
 
+use std::any::TypeId;
+
 use crate::prelude::*;
-use bevy::{prelude::*, utils::HashSet};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 /// Initializes the [`SpatialQueryPipeline`] resource and handles component-based [spatial queries](spatial_query)
 /// like [ray casting](spatial_query#ray-casting) and [shape casting](spatial_query#shape-casting) with
@@ -187,12 +242,32 @@ impl Default for SpatialQueryPlugin {
 
 impl Plugin for SpatialQueryPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<SpatialQueryPipeline>()
+        app.add_event::<RayHitStarted>()
+            .add_event::<RayHitEnded>()
+            .add_event::<ShapeHitStarted>()
+            .add_event::<ShapeHitEnded>()
+            .init_resource::<SpatialQueryPipeline>()
+            .init_resource::<PreviousRayHits>()
+            .init_resource::<PreviousShapeHits>()
             .init_resource::<RemovedColliders>()
-            .add_systems(Last, update_removed_colliders)
+            .init_resource::<RemovedQueryFilterComponents>()
+            .init_resource::<JointConnections>()
+            .add_systems(
+                Last,
+                (
+                    update_removed_colliders,
+                    update_removed_query_filter_components,
+                ),
+            )
             .add_systems(
                 self.schedule.dyn_clone(),
-                (init_ray_hits, init_shape_hit, update_removed_colliders)
+                (
+                    init_ray_hits,
+                    init_multi_ray_hits,
+                    init_shape_hit,
+                    update_removed_colliders,
+                    update_removed_query_filter_components,
+                )
                     .in_set(PhysicsSet::Prepare),
             );
 
@@ -203,11 +278,27 @@ impl Plugin for SpatialQueryPlugin {
         physics_schedule.add_systems(
             (
                 update_ray_caster_positions,
+                update_multi_ray_caster_positions,
                 update_shape_caster_positions,
-                |mut spatial_query: SpatialQuery| spatial_query.update_pipeline(),
+                update_spatial_query_pipeline,
+                update_query_filter_data,
                 |mut removed: ResMut<RemovedColliders>| removed.clear(),
+                (
+                    update_joint_connections::<FixedJoint>,
+                    update_joint_connections::<RevoluteJoint>,
+                    update_joint_connections::<SphericalJoint>,
+                    update_joint_connections::<PrismaticJoint>,
+                    update_joint_connections::<DistanceJoint>,
+                    update_joint_connections::<D6Joint>,
+                    update_joint_connections::<GearJoint>,
+                    update_joint_connections::<PulleyJoint>,
+                )
+                    .chain(),
                 raycast,
+                multi_raycast,
                 shapecast,
+                send_ray_hit_events,
+                send_shape_hit_events,
             )
                 .chain()
                 .in_set(PhysicsStepSet::SpatialQuery),
@@ -215,9 +306,232 @@ impl Plugin for SpatialQueryPlugin {
     }
 }
 
+/// Updates the [`SpatialQueryPipeline`].
+///
+/// With [`BroadPhaseBackend::SpatialQueryPipeline`], the broad phase already keeps the acceleration
+/// structure up to date, so only the poses of the colliders are updated.
+fn update_spatial_query_pipeline(
+    mut spatial_query: SpatialQuery,
+    broad_phase_config: Option<Res<BroadPhaseConfig>>,
+) {
+    let shared_with_broad_phase = broad_phase_config.map_or(false, |config| {
+        config.backend == BroadPhaseBackend::SpatialQueryPipeline
+    });
+
+    if shared_with_broad_phase {
+        spatial_query.update_pipeline_poses();
+    } else {
+        spatial_query.update_pipeline();
+    }
+}
+
+type QueryFilterColliderComponents = (
+    Entity,
+    Option<&'static ColliderParent>,
+    Option<&'static Sensor>,
+);
+
+type QueryFilterColliderChanged = (
+    With<Collider>,
+    Or<(Added<Collider>, Changed<ColliderParent>, Changed<Sensor>)>,
+);
+
+type QueryFilterBodyComponents = (Entity, &'static RigidBody, Option<&'static Sleeping>);
+
+type QueryFilterBodyChanged = Or<(Changed<RigidBody>, Changed<Sleeping>)>;
+
+type QueryFilterCurrentComponents = (
+    Option<&'static Collider>,
+    Option<&'static ColliderParent>,
+    Option<&'static Sensor>,
+    Option<&'static RigidBody>,
+    Option<&'static Sleeping>,
+);
+
+/// Updates the rigid body and sensor information that the [`SpatialQueryPipeline`] uses
+/// for testing [`SpatialQueryFilter`]s.
+///
+/// Only colliders and rigid bodies whose information has changed are updated.
+fn update_query_filter_data(
+    mut query_pipeline: ResMut<SpatialQueryPipeline>,
+    colliders: Query<QueryFilterColliderComponents, QueryFilterColliderChanged>,
+    bodies: Query<QueryFilterBodyComponents, QueryFilterBodyChanged>,
+    mut removed: ResMut<RemovedQueryFilterComponents>,
+    current_components: Query<QueryFilterCurrentComponents>,
+) {
+    let filter_data = &mut query_pipeline.filter_data;
+
+    // Removals are handled first, using the components that the entities have now,
+    // so that components that were removed and inserted again are kept
+    for entity in removed.drain() {
+        let (collider, collider_parent, sensor, rb, sleeping) =
+            current_components.get(entity).unwrap_or_default();
+
+        match collider_parent.filter(|_| collider.is_some()) {
+            Some(collider_parent) => {
+                filter_data
+                    .collider_bodies
+                    .insert(entity, collider_parent.get());
+            }
+            None => {
+                filter_data.collider_bodies.remove(&entity);
+            }
+        }
+        if collider.is_some() && sensor.is_some() {
+            filter_data.sensors.insert(entity);
+        } else {
+            filter_data.sensors.remove(&entity);
+        }
+        match rb {
+            Some(rb) => {
+                filter_data.body_types.insert(entity, *rb);
+            }
+            None => {
+                filter_data.body_types.remove(&entity);
+            }
+        }
+        if rb.is_some() && sleeping.is_some() {
+            filter_data.sleeping.insert(entity);
+        } else {
+            filter_data.sleeping.remove(&entity);
+        }
+    }
+
+    for (entity, collider_parent, sensor) in &colliders {
+        match collider_parent {
+            Some(collider_parent) => {
+                filter_data
+                    .collider_bodies
+                    .insert(entity, collider_parent.get());
+            }
+            None => {
+                filter_data.collider_bodies.remove(&entity);
+            }
+        }
+        if sensor.is_some() {
+            filter_data.sensors.insert(entity);
+        } else {
+            filter_data.sensors.remove(&entity);
+        }
+    }
+
+    for (entity, rb, sleeping) in &bodies {
+        filter_data.body_types.insert(entity, *rb);
+        if sleeping.is_some() {
+            filter_data.sleeping.insert(entity);
+        } else {
+            filter_data.sleeping.remove(&entity);
+        }
+    }
+}
+
 #[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
 pub(crate) struct RemovedColliders(HashSet<Entity>);
 
+/// Entities that had components used for testing [`SpatialQueryFilter`]s removed since the last physics step.
+///
+/// Removal events are only kept for two frames and the [`PhysicsSchedule`] doesn't run every frame,
+/// so the removals are collected every frame and handled by [`update_query_filter_data`].
+#[derive(Resource, Debug, Default, Clone, Deref, DerefMut)]
+pub(crate) struct RemovedQueryFilterComponents(HashSet<Entity>);
+
+/// The entities that each entity is connected to with [joints](joints).
+/// Used for excluding jointed bodies in [`SpatialQuery::cast_body`].
+///
+/// The connections are updated by [`update_joint_connections`].
+#[derive(Resource, Debug, Default, Clone)]
+pub struct JointConnections {
+    /// The entities constrained by each joint, keyed by the joint entity and the type of the joint.
+    joints: HashMap<(Entity, TypeId), [Entity; 2]>,
+    /// The entities that each entity is connected to. An entity is listed once for each joint between them.
+    connections: HashMap<Entity, Vec<Entity>>,
+}
+
+impl JointConnections {
+    /// Returns the entities that the given entity is connected to with joints.
+    pub fn get(&self, entity: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.connections.get(&entity).into_iter().flatten().copied()
+    }
+
+    /// Sets the entities constrained by a joint, replacing the connections of its previous entities.
+    fn insert(&mut self, joint: (Entity, TypeId), entities: [Entity; 2]) {
+        if self.joints.get(&joint) == Some(&entities) {
+            return;
+        }
+        self.remove(joint);
+        self.joints.insert(joint, entities);
+
+        let [entity1, entity2] = entities;
+        self.connections.entry(entity1).or_default().push(entity2);
+        self.connections.entry(entity2).or_default().push(entity1);
+    }
+
+    /// Removes the connections of the joints for which `f` returns `false`.
+    fn retain(&mut self, mut f: impl FnMut(&(Entity, TypeId)) -> bool) {
+        let removed: Vec<_> = self
+            .joints
+            .keys()
+            .filter(|joint| !f(joint))
+            .copied()
+            .collect();
+        for joint in removed {
+            self.remove(joint);
+        }
+    }
+
+    /// Removes the connections of a joint.
+    fn remove(&mut self, joint: (Entity, TypeId)) {
+        let Some([entity1, entity2]) = self.joints.remove(&joint) else {
+            return;
+        };
+
+        for (entity, other) in [(entity1, entity2), (entity2, entity1)] {
+            if let Some(connected) = self.connections.get_mut(&entity) {
+                if let Some(index) = connected.iter().position(|e| *e == other) {
+                    connected.swap_remove(index);
+                }
+                if connected.is_empty() {
+                    self.connections.remove(&entity);
+                }
+            }
+        }
+    }
+}
+
+/// Updates the [`JointConnections`] for joints of type `T`.
+///
+/// The solver mutates every joint each substep, so change detection can't be used for finding changed joints.
+/// Instead, every joint of type `T` is visited each physics step, but the connections are only modified
+/// for joints that were added, removed or attached to different entities.
+///
+/// Removed joints are found by checking which joint entities no longer have `T`, because removal events
+/// are missed on frames where the [`PhysicsSchedule`] doesn't run.
+///
+/// The built-in joints are registered by the [`SpatialQueryPlugin`]. Custom joints can be registered
+/// in [`PhysicsStepSet::SpatialQuery`] so that [`SpatialQuery::cast_body`] excludes the bodies connected by them:
+///
+/// ```ignore
+/// let physics_schedule = app
+///     .get_schedule_mut(PhysicsSchedule)
+///     .expect("add PhysicsSchedule first");
+///
+/// physics_schedule.add_systems(
+///     update_joint_connections::<YourJoint>.in_set(PhysicsStepSet::SpatialQuery),
+/// );
+/// ```
+pub fn update_joint_connections<T: XpbdConstraint<2> + Component>(
+    joints: Query<(Entity, &T)>,
+    mut connections: ResMut<JointConnections>,
+) {
+    let joint_type = TypeId::of::<T>();
+
+    connections.retain(|(entity, ty)| *ty != joint_type || joints.contains(*entity));
+
+    for (entity, joint) in &joints {
+        connections.insert((entity, joint_type), joint.entities());
+    }
+}
+
 fn init_ray_hits(mut commands: Commands, rays: Query<(Entity, &RayCaster), Added<RayCaster>>) {
     for (entity, ray) in &rays {
         let max_hits = if ray.max_hits == u32::MAX {
@@ -232,6 +546,12 @@ fn init_ray_hits(mut commands: Commands, rays: Query<(Entity, &RayCaster), Added
     }
 }
 
+fn init_multi_ray_hits(mut commands: Commands, multi_rays: Query<Entity, Added<MultiRayCaster>>) {
+    for entity in &multi_rays {
+        commands.entity(entity).insert(MultiRayHits::default());
+    }
+}
+
 fn init_shape_hit(
     mut commands: Commands,
     shape_casters: Query<(Entity, &ShapeCaster), Added<ShapeCaster>>,
@@ -256,42 +576,91 @@ fn update_ray_caster_positions(
     parents: Query<(Option<&Position>, Option<&Rotation>), With<Children>>,
 ) {
     for (mut ray, position, rotation, parent) in &mut rays {
-        let origin = ray.origin;
-        let direction = ray.direction;
+        let (global_origin, global_rotation) =
+            caster_global_transform(ray.origin, position, rotation, parent, &parents);
 
-        if let Some(position) = position {
-            ray.set_global_origin(position.0 + rotation.map_or(origin, |rot| rot.rotate(origin)));
-        } else if parent.is_none() {
-            ray.set_global_origin(origin);
+        if let Some(global_origin) = global_origin {
+            ray.set_global_origin(global_origin);
         }
-
-        if let Some(rotation) = rotation {
-            let global_direction = rotation.rotate(ray.direction);
+        if let Some(global_rotation) = global_rotation {
+            let global_direction = global_rotation.rotate(ray.direction);
             ray.set_global_direction(global_direction);
-        } else if parent.is_none() {
-            ray.set_global_direction(direction);
         }
+    }
+}
 
-        if let Some(parent) = parent {
-            if let Ok((parent_position, parent_rotation)) = parents.get(parent.get()) {
-                if position.is_none() {
-                    if let Some(position) = parent_position {
-                        let rotation = rotation.map_or(
-                            parent_rotation.map_or(Rotation::default(), |rot| *rot),
-                            |rot| *rot,
-                        );
-                        ray.set_global_origin(position.0 + rotation.rotate(origin));
-                    }
+type MultiRayCasterPositionQueryComponents = (
+    &'static mut MultiRayCaster,
+    Option<&'static Position>,
+    Option<&'static Rotation>,
+    Option<&'static Parent>,
+);
+
+fn update_multi_ray_caster_positions(
+    mut multi_rays: Query<MultiRayCasterPositionQueryComponents>,
+    parents: Query<(Option<&Position>, Option<&Rotation>), With<Children>>,
+) {
+    for (mut multi_ray, position, rotation, parent) in &mut multi_rays {
+        let (global_origin, global_rotation) =
+            caster_global_transform(multi_ray.origin, position, rotation, parent, &parents);
+
+        if let Some(global_origin) = global_origin {
+            multi_ray.set_global_origin(global_origin);
+        }
+        if let Some(global_rotation) = global_rotation {
+            multi_ray.set_global_rotation(global_rotation);
+        }
+        multi_ray.update_global_rays();
+    }
+}
+
+/// Computes the global origin of a caster with the given local `origin` and the rotation that
+/// its local directions should be rotated by, based on the [`Position`] and [`Rotation`]
+/// of the caster entity or its parent.
+///
+/// Values that can't be determined are `None`, and the previous global values should be kept.
+fn caster_global_transform(
+    origin: Vector,
+    position: Option<&Position>,
+    rotation: Option<&Rotation>,
+    parent: Option<&Parent>,
+    parents: &Query<(Option<&Position>, Option<&Rotation>), With<Children>>,
+) -> (Option<Vector>, Option<Rotation>) {
+    let mut global_origin = None;
+    let mut global_rotation = None;
+
+    if let Some(position) = position {
+        global_origin = Some(position.0 + rotation.map_or(origin, |rot| rot.rotate(origin)));
+    } else if parent.is_none() {
+        global_origin = Some(origin);
+    }
+
+    if let Some(rotation) = rotation {
+        global_rotation = Some(*rotation);
+    } else if parent.is_none() {
+        global_rotation = Some(Rotation::default());
+    }
+
+    if let Some(parent) = parent {
+        if let Ok((parent_position, parent_rotation)) = parents.get(parent.get()) {
+            if position.is_none() {
+                if let Some(position) = parent_position {
+                    let rotation = rotation.map_or(
+                        parent_rotation.map_or(Rotation::default(), |rot| *rot),
+                        |rot| *rot,
+                    );
+                    global_origin = Some(position.0 + rotation.rotate(origin));
                 }
-                if rotation.is_none() {
-                    if let Some(rotation) = parent_rotation {
-                        let global_direction = rotation.rotate(ray.direction);
-                        ray.set_global_direction(global_direction);
-                    }
+            }
+            if rotation.is_none() {
+                if let Some(rotation) = parent_rotation {
+                    global_rotation = Some(*rotation);
                 }
             }
         }
     }
+
+    (global_origin, global_rotation)
 }
 
 type ShapeCasterPositionQueryComponents = (
@@ -309,6 +678,7 @@ fn update_shape_caster_positions(
         let origin = shape_caster.origin;
         let shape_rotation = shape_caster.shape_rotation;
         let direction = shape_caster.direction;
+        let mode = shape_caster.mode;
 
         if let Some(position) = position {
             shape_caster
@@ -320,16 +690,19 @@ fn update_shape_caster_positions(
         if let Some(rotation) = rotation {
             let global_direction = rotation.rotate(shape_caster.direction);
             shape_caster.set_global_direction(global_direction);
+            let global_mode = mode.rotated(rotation);
+            shape_caster.set_global_mode(global_mode);
             #[cfg(feature = "2d")]
             {
                 shape_caster.set_global_shape_rotation(shape_rotation + rotation.as_radians());
             }
             #[cfg(feature = "3d")]
             {
-                shape_caster.set_global_shape_rotation(shape_rotation + rotation.0);
+                shape_caster.set_global_shape_rotation(rotation.0 * shape_rotation);
             }
         } else if parent.is_none() {
             shape_caster.set_global_direction(direction);
+            shape_caster.set_global_mode(mode);
             #[cfg(feature = "2d")]
             {
                 shape_caster.set_global_shape_rotation(shape_rotation);
@@ -355,6 +728,8 @@ fn update_shape_caster_positions(
                     if let Some(rotation) = parent_rotation {
                         let global_direction = rotation.rotate(shape_caster.direction);
                         shape_caster.set_global_direction(global_direction);
+                        let global_mode = mode.rotated(rotation);
+                        shape_caster.set_global_mode(global_mode);
                         #[cfg(feature = "2d")]
                         {
                             shape_caster
@@ -362,7 +737,7 @@ fn update_shape_caster_positions(
                         }
                         #[cfg(feature = "3d")]
                         {
-                            shape_caster.set_global_shape_rotation(shape_rotation + rotation.0);
+                            shape_caster.set_global_shape_rotation(rotation.0 * shape_rotation);
                         }
                     }
                 }
@@ -381,6 +756,19 @@ fn raycast(mut rays: Query<(&RayCaster, &mut RayHits)>, spatial_query: SpatialQu
     }
 }
 
+fn multi_raycast(
+    mut multi_rays: Query<(&MultiRayCaster, &mut MultiRayHits)>,
+    spatial_query: SpatialQuery,
+) {
+    for (multi_ray, mut hits) in &mut multi_rays {
+        if multi_ray.enabled {
+            multi_ray.cast(&mut hits, &spatial_query.query_pipeline);
+        } else if hits.iter().any(|hits| !hits.is_empty()) {
+            hits.clear();
+        }
+    }
+}
+
 fn shapecast(
     mut shape_casters: Query<(&ShapeCaster, &mut ShapeHits)>,
     spatial_query: SpatialQuery,
@@ -394,9 +782,117 @@ fn shapecast(
     }
 }
 
+/// The entities hit by each [`RayCaster`] with `hit_events` enabled during the previous physics frame.
+#[derive(Resource, Default)]
+struct PreviousRayHits(HashMap<Entity, Vec<Entity>>);
+
+/// The entities hit by each [`ShapeCaster`] with `hit_events` enabled during the previous physics frame.
+#[derive(Resource, Default)]
+struct PreviousShapeHits(HashMap<Entity, Vec<Entity>>);
+
+/// Sends [`RayHitStarted`] and [`RayHitEnded`] events for ray casters with `hit_events` enabled.
+fn send_ray_hit_events(
+    rays: Query<(Entity, &RayCaster, &RayHits)>,
+    mut previous_hits: ResMut<PreviousRayHits>,
+    mut hit_started_ev_writer: EventWriter<RayHitStarted>,
+    mut hit_ended_ev_writer: EventWriter<RayHitEnded>,
+) {
+    let current_hits = rays
+        .iter()
+        .filter(|(_, ray, _)| ray.hit_events)
+        .map(|(entity, _, hits)| (entity, hits.iter().map(|hit| hit.entity).collect()));
+
+    update_previous_hits(
+        &mut previous_hits.0,
+        current_hits,
+        |caster, entity| hit_started_ev_writer.send(RayHitStarted(caster, entity)),
+        |caster, entity| hit_ended_ev_writer.send(RayHitEnded(caster, entity)),
+    );
+}
+
+/// Sends [`ShapeHitStarted`] and [`ShapeHitEnded`] events for shape casters with `hit_events` enabled.
+fn send_shape_hit_events(
+    shape_casters: Query<(Entity, &ShapeCaster, &ShapeHits)>,
+    mut previous_hits: ResMut<PreviousShapeHits>,
+    mut hit_started_ev_writer: EventWriter<ShapeHitStarted>,
+    mut hit_ended_ev_writer: EventWriter<ShapeHitEnded>,
+) {
+    let current_hits = shape_casters
+        .iter()
+        .filter(|(_, shape_caster, _)| shape_caster.hit_events)
+        .map(|(entity, _, hits)| (entity, hits.iter().map(|hit| hit.entity).collect()));
+
+    update_previous_hits(
+        &mut previous_hits.0,
+        current_hits,
+        |caster, entity| hit_started_ev_writer.send(ShapeHitStarted(caster, entity)),
+        |caster, entity| hit_ended_ev_writer.send(ShapeHitEnded(caster, entity)),
+    );
+}
+
+/// Compares the entities hit by each caster to the hits of the previous frame, calls `on_started` and `on_ended`
+/// for the hits that started or ended, and replaces the previous hits with the current ones.
+///
+/// Casters that are no longer included in `current_hits` end all of their previous hits.
+fn update_previous_hits(
+    previous_hits: &mut HashMap<Entity, Vec<Entity>>,
+    current_hits: impl Iterator<Item = (Entity, Vec<Entity>)>,
+    mut on_started: impl FnMut(Entity, Entity),
+    mut on_ended: impl FnMut(Entity, Entity),
+) {
+    let mut new_hits = HashMap::with_capacity(previous_hits.len());
+
+    for (caster, hits) in current_hits {
+        let old_hits = previous_hits.remove(&caster).unwrap_or_default();
+
+        for entity in hits.iter() {
+            if !old_hits.contains(entity) {
+                on_started(caster, *entity);
+            }
+        }
+        for entity in old_hits {
+            if !hits.contains(&entity) {
+                on_ended(caster, entity);
+            }
+        }
+
+        new_hits.insert(caster, hits);
+    }
+
+    // Casters that were removed or had their hit events disabled.
+    // They are sorted so that the order of the events doesn't depend on the iteration order of the hash map.
+    let mut removed_casters = previous_hits.drain().collect::<Vec<_>>();
+    removed_casters.sort_unstable_by_key(|(caster, _)| *caster);
+    for (caster, hits) in removed_casters {
+        for entity in hits {
+            on_ended(caster, entity);
+        }
+    }
+
+    *previous_hits = new_hits;
+}
+
 fn update_removed_colliders(
     mut removals: RemovedComponents<Collider>,
     mut removed_colliders: ResMut<RemovedColliders>,
 ) {
     removed_colliders.extend(removals.iter());
 }
+
+fn update_removed_query_filter_components(
+    mut removed_colliders: RemovedComponents<Collider>,
+    mut removed_collider_parents: RemovedComponents<ColliderParent>,
+    mut removed_sensors: RemovedComponents<Sensor>,
+    mut removed_bodies: RemovedComponents<RigidBody>,
+    mut removed_sleeping: RemovedComponents<Sleeping>,
+    mut removed: ResMut<RemovedQueryFilterComponents>,
+) {
+    removed.extend(
+        removed_colliders
+            .iter()
+            .chain(removed_collider_parents.iter())
+            .chain(removed_sensors.iter())
+            .chain(removed_bodies.iter())
+            .chain(removed_sleeping.iter()),
+    );
+}
@@ -0,0 +1,473 @@
+use crate::prelude::*;
+use bevy::prelude::*;
+
+/// A component used for casting many [rays](spatial_query#ray-casting) from a single entity.
+///
+/// **Ray casting** is a type of [spatial query](spatial_query) that finds one or more hits
+/// between a ray and a set of colliders.
+///
+/// The rays of a [`MultiRayCaster`] are described by a [`RayPattern`] in the local space of the entity,
+/// for example a fan or a grid of rays. They share a local `origin` and follow the [`Position`] and [`Rotation`]
+/// of the entity or its parent like a [`RayCaster`]. All rays are cast in the same system, and the hits
+/// of each ray are stored in the [`MultiRayHits`] component in the order of the rays in the pattern.
+///
+/// This is useful for things like vehicle sensors, whiskers and vision cones that would otherwise need
+/// many [`RayCaster`] entities.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// # #[cfg(all(feature = "3d", feature = "f32"))]
+/// fn setup(mut commands: Commands) {
+///     // Spawn five rays in a 90 degree fan around the X axis, rotated around the Y axis
+///     commands.spawn(
+///         MultiRayCaster::new(
+///             Vec3::ZERO,
+///             RayPattern::Fan {
+///                 direction: Vec3::X,
+///                 axis: Vec3::Y,
+///                 angle: std::f32::consts::FRAC_PI_2,
+///                 count: 5,
+///             },
+///         )
+///         .with_max_hits(1),
+///     );
+/// }
+///
+/// fn print_hits(query: Query<&MultiRayHits, With<MultiRayCaster>>) {
+///     for multi_hits in &query {
+///         for (i, hits) in multi_hits.iter().enumerate() {
+///             for hit in hits.iter() {
+///                 println!("Ray {} hit entity {:?}", i, hit.entity);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component)]
+pub struct MultiRayCaster {
+    /// Controls if the multi-ray caster is enabled.
+    pub enabled: bool,
+    /// The local origin of the rays relative to the [`Position`] and [`Rotation`] of the entity or its parent.
+    ///
+    /// To get the global origin, use the `global_origin` method.
+    pub origin: Vector,
+    /// The global origin of the rays.
+    global_origin: Vector,
+    /// The global rotation that the local rays are rotated by.
+    global_rotation: Rotation,
+    /// The local rays relative to the `origin` and the [`Rotation`] of the entity or its parent.
+    pub pattern: RayPattern,
+    /// The global origins and directions of the rays.
+    global_rays: Vec<(Vector, Vector)>,
+    /// The maximum distance each ray can travel. By default this is infinite, so the rays will travel
+    /// until all hits up to `max_hits` have been checked.
+    pub max_time_of_impact: Scalar,
+    /// The maximum number of hits allowed for each ray.
+    ///
+    /// When there are more hits than `max_hits`, **some hits will be missed**.
+    /// To guarantee that the closest hit is included, you should set `max_hits` to one or a value that
+    /// is enough to contain all hits.
+    pub max_hits: u32,
+    /// Controls how the rays behave when a ray origin is inside of a [collider](Collider).
+    ///
+    /// If `solid` is true, the point of intersection will be the ray origin itself.\
+    /// If `solid` is false, the collider will be considered to have no interior, and the point of intersection
+    /// will be at the collider shape's boundary.
+    pub solid: bool,
+    /// Rules that determine which colliders are taken into account in the query.
+    pub query_filter: SpatialQueryFilter,
+}
+
+impl Default for MultiRayCaster {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            origin: Vector::ZERO,
+            global_origin: Vector::ZERO,
+            global_rotation: Rotation::default(),
+            pattern: RayPattern::Custom(vec![]),
+            global_rays: vec![],
+            max_time_of_impact: Scalar::MAX,
+            max_hits: u32::MAX,
+            solid: true,
+            query_filter: SpatialQueryFilter::default(),
+        }
+    }
+}
+
+impl MultiRayCaster {
+    /// Creates a new [`MultiRayCaster`] with a given origin and ray pattern.
+    pub fn new(origin: Vector, pattern: RayPattern) -> Self {
+        Self {
+            origin,
+            pattern,
+            ..default()
+        }
+    }
+
+    /// Sets the origin of the rays.
+    pub fn with_origin(mut self, origin: Vector) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Sets the [pattern](RayPattern) that describes the rays.
+    pub fn with_pattern(mut self, pattern: RayPattern) -> Self {
+        self.pattern = pattern;
+        self
+    }
+
+    /// Sets if the rays treat [colliders](Collider) as solid.
+    ///
+    /// If `solid` is true, the point of intersection will be the ray origin itself.\
+    /// If `solid` is false, the collider will be considered to have no interior, and the point of intersection
+    /// will be at the collider shape's boundary.
+    pub fn with_solidness(mut self, solid: bool) -> Self {
+        self.solid = solid;
+        self
+    }
+
+    /// Sets the maximum time of impact, i.e. the maximum distance that each ray is allowed to travel.
+    pub fn with_max_time_of_impact(mut self, max_time_of_impact: Scalar) -> Self {
+        self.max_time_of_impact = max_time_of_impact;
+        self
+    }
+
+    /// Sets the maximum number of allowed hits for each ray.
+    pub fn with_max_hits(mut self, max_hits: u32) -> Self {
+        self.max_hits = max_hits;
+        self
+    }
+
+    /// Sets the multi-ray caster's [query filter](SpatialQueryFilter) that controls which colliders
+    /// should be included or excluded by the ray casts.
+    pub fn with_query_filter(mut self, query_filter: SpatialQueryFilter) -> Self {
+        self.query_filter = query_filter;
+        self
+    }
+
+    /// Enables the [`MultiRayCaster`].
+    pub fn enable(&mut self) {
+        self.enabled = true;
+    }
+
+    /// Disables the [`MultiRayCaster`].
+    pub fn disable(&mut self) {
+        self.enabled = false;
+    }
+
+    /// Returns the global origin of the rays.
+    pub fn global_origin(&self) -> Vector {
+        self.global_origin
+    }
+
+    /// Returns the global origins and directions of the rays in the order of the pattern.
+    pub fn global_rays(&self) -> &[(Vector, Vector)] {
+        &self.global_rays
+    }
+
+    /// Sets the global origin of the rays.
+    pub(crate) fn set_global_origin(&mut self, global_origin: Vector) {
+        self.global_origin = global_origin;
+    }
+
+    /// Sets the global rotation that the local rays are rotated by.
+    pub(crate) fn set_global_rotation(&mut self, global_rotation: Rotation) {
+        self.global_rotation = global_rotation;
+    }
+
+    /// Computes the global rays from the pattern and the global origin and rotation.
+    pub(crate) fn update_global_rays(&mut self) {
+        let origin = self.global_origin;
+        let rotation = self.global_rotation;
+        self.global_rays = self
+            .pattern
+            .local_rays()
+            .into_iter()
+            .map(|(offset, direction)| {
+                (origin + rotation.rotate(offset), rotation.rotate(direction))
+            })
+            .collect();
+    }
+
+    pub(crate) fn cast(&self, hits: &mut MultiRayHits, query_pipeline: &SpatialQueryPipeline) {
+        hits.rays.resize_with(self.global_rays.len(), default);
+
+        for ((origin, direction), ray_hits) in self.global_rays.iter().zip(hits.rays.iter_mut()) {
+            let ray = parry::query::Ray::new((*origin).into(), (*direction).into());
+            cast_ray_into_hits(
+                &ray,
+                self.max_time_of_impact,
+                self.max_hits,
+                self.solid,
+                &self.query_filter,
+                ray_hits,
+                query_pipeline,
+            );
+        }
+    }
+}
+
+/// Describes the rays of a [`MultiRayCaster`] in its local space.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RayPattern {
+    /// `count` rays that start at the origin and are spread evenly across an `angle` in radians,
+    /// centered on the `direction`. A single ray points in the `direction`.
+    #[cfg(feature = "2d")]
+    Fan {
+        /// The direction of the center of the fan.
+        direction: Vector,
+        /// The angle between the first and the last ray in radians.
+        angle: Scalar,
+        /// The number of rays.
+        count: u32,
+    },
+    /// `count` rays that start at the origin and are spread evenly across an `angle` in radians
+    /// around the `axis`, centered on the `direction`. A single ray points in the `direction`.
+    #[cfg(feature = "3d")]
+    Fan {
+        /// The direction of the center of the fan.
+        direction: Vector,
+        /// The axis that the rays are rotated around. It should be perpendicular to the `direction`.
+        axis: Vector,
+        /// The angle between the first and the last ray in radians.
+        angle: Scalar,
+        /// The number of rays.
+        count: u32,
+    },
+    /// A ray in the `direction` surrounded by `rings` of rays that start at the origin.
+    /// The angles of the rings from the `direction` are spread evenly up to `half_angle` in radians,
+    /// and each ring has `rays_per_ring` rays spread evenly around the `direction`.
+    #[cfg(feature = "3d")]
+    Cone {
+        /// The direction of the center of the cone.
+        direction: Vector,
+        /// The angle between the `direction` and the outermost ring in radians.
+        half_angle: Scalar,
+        /// The number of rings around the center ray.
+        rings: u32,
+        /// The number of rays in each ring.
+        rays_per_ring: u32,
+    },
+    /// `count` parallel rays in the `direction` with origins on a line perpendicular to the `direction`,
+    /// centered on the origin and `spacing` apart.
+    #[cfg(feature = "2d")]
+    Grid {
+        /// The direction of the rays.
+        direction: Vector,
+        /// The number of rays.
+        count: u32,
+        /// The distance between neighbouring rays.
+        spacing: Scalar,
+    },
+    /// `rows` times `columns` parallel rays in the `direction` with origins on a grid perpendicular
+    /// to the `direction`, centered on the origin and `spacing` apart. The rays are ordered row by row.
+    #[cfg(feature = "3d")]
+    Grid {
+        /// The direction of the rays.
+        direction: Vector,
+        /// The number of rows.
+        rows: u32,
+        /// The number of rays in each row.
+        columns: u32,
+        /// The distance between neighbouring rays.
+        spacing: Scalar,
+    },
+    /// An explicit list of rays, each defined by its origin relative to the caster's `origin` and its direction.
+    Custom(Vec<(Vector, Vector)>),
+}
+
+impl RayPattern {
+    /// Returns the number of rays in the pattern.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Fan { count, .. } => *count as usize,
+            #[cfg(feature = "2d")]
+            Self::Grid { count, .. } => *count as usize,
+            #[cfg(feature = "3d")]
+            Self::Cone {
+                rings,
+                rays_per_ring,
+                ..
+            } => 1 + (*rings * *rays_per_ring) as usize,
+            #[cfg(feature = "3d")]
+            Self::Grid { rows, columns, .. } => (*rows * *columns) as usize,
+            Self::Custom(rays) => rays.len(),
+        }
+    }
+
+    /// Returns true if the pattern has no rays.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the origins and directions of the rays relative to the caster's `origin` and rotation.
+    pub fn local_rays(&self) -> Vec<(Vector, Vector)> {
+        match self {
+            #[cfg(feature = "2d")]
+            Self::Fan {
+                direction,
+                angle,
+                count,
+            } => evenly_spaced(*count, *angle)
+                .map(|offset| {
+                    (
+                        Vector::ZERO,
+                        Rotation::from_radians(offset).rotate(*direction),
+                    )
+                })
+                .collect(),
+            #[cfg(feature = "3d")]
+            Self::Fan {
+                direction,
+                axis,
+                angle,
+                count,
+            } => {
+                let axis = axis.normalize_or_zero();
+                evenly_spaced(*count, *angle)
+                    .map(|offset| {
+                        (
+                            Vector::ZERO,
+                            Quaternion::from_axis_angle(axis, offset) * *direction,
+                        )
+                    })
+                    .collect()
+            }
+            #[cfg(feature = "3d")]
+            Self::Cone {
+                direction,
+                half_angle,
+                rings,
+                rays_per_ring,
+            } => {
+                let (tilt_axis, _) = direction.normalize_or_zero().any_orthonormal_pair();
+                let mut rays = vec![(Vector::ZERO, *direction)];
+                for ring in 1..=*rings {
+                    let tilt = Quaternion::from_axis_angle(
+                        tilt_axis,
+                        *half_angle * ring as Scalar / *rings as Scalar,
+                    );
+                    let tilted = tilt * *direction;
+                    for i in 0..*rays_per_ring {
+                        let spin = Quaternion::from_axis_angle(
+                            direction.normalize_or_zero(),
+                            2.0 * PI * i as Scalar / *rays_per_ring as Scalar,
+                        );
+                        rays.push((Vector::ZERO, spin * tilted));
+                    }
+                }
+                rays
+            }
+            #[cfg(feature = "2d")]
+            Self::Grid {
+                direction,
+                count,
+                spacing,
+            } => {
+                let side = direction.normalize_or_zero().perp();
+                evenly_spaced(*count, (*count as Scalar - 1.0).max(0.0) * *spacing)
+                    .map(|offset| (side * offset, *direction))
+                    .collect()
+            }
+            #[cfg(feature = "3d")]
+            Self::Grid {
+                direction,
+                rows,
+                columns,
+                spacing,
+            } => {
+                let (right, up) = direction.normalize_or_zero().any_orthonormal_pair();
+                let height = (*rows as Scalar - 1.0).max(0.0) * *spacing;
+                let width = (*columns as Scalar - 1.0).max(0.0) * *spacing;
+                evenly_spaced(*rows, height)
+                    .rev()
+                    .flat_map(|y| {
+                        evenly_spaced(*columns, width)
+                            .map(move |x| (right * x + up * y, *direction))
+                    })
+                    .collect()
+            }
+            Self::Custom(rays) => rays.clone(),
+        }
+    }
+}
+
+/// Returns `count` values spread evenly across `extent`, centered on zero.
+fn evenly_spaced(count: u32, extent: Scalar) -> impl DoubleEndedIterator<Item = Scalar> {
+    (0..count).map(move |i| {
+        if count > 1 {
+            -extent / 2.0 + extent * i as Scalar / (count - 1) as Scalar
+        } else {
+            0.0
+        }
+    })
+}
+
+/// Contains the hits of each ray cast by a [`MultiRayCaster`] in the order of the rays in its [`RayPattern`].
+///
+/// The maximum number of hits for each ray depends on the value of `max_hits` in [`MultiRayCaster`].
+/// Like with [`RayHits`], the order of the hits of a ray is not guaranteed.
+///
+/// ## Example
+///
+/// ```
+/// use bevy::prelude::*;
+/// # #[cfg(feature = "2d")]
+/// # use bevy_xpbd_2d::prelude::*;
+/// # #[cfg(feature = "3d")]
+/// use bevy_xpbd_3d::prelude::*;
+///
+/// fn print_closest_hits(query: Query<&MultiRayHits, With<MultiRayCaster>>) {
+///     for multi_hits in &query {
+///         for (i, hits) in multi_hits.iter().enumerate() {
+///             if let Some(hit) = hits.iter_sorted().next() {
+///                 println!("Ray {} hit entity {:?} at {}", i, hit.entity, hit.point);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Default)]
+pub struct MultiRayHits {
+    pub(crate) rays: Vec<RayHits>,
+}
+
+impl MultiRayHits {
+    /// Returns a slice over the hits of each ray.
+    pub fn as_slice(&self) -> &[RayHits] {
+        &self.rays
+    }
+
+    /// Returns the hits of the ray at the given index in the [`RayPattern`].
+    pub fn get(&self, index: usize) -> Option<&RayHits> {
+        self.rays.get(index)
+    }
+
+    /// Returns the number of rays.
+    pub fn len(&self) -> usize {
+        self.rays.len()
+    }
+
+    /// Returns true if there are no rays.
+    pub fn is_empty(&self) -> bool {
+        self.rays.is_empty()
+    }
+
+    /// Clears the hits of each ray.
+    pub fn clear(&mut self) {
+        self.rays.iter_mut().for_each(|hits| hits.clear());
+    }
+
+    /// Returns an iterator over the hits of each ray in the order of the rays in the [`RayPattern`].
+    pub fn iter(&self) -> std::slice::Iter<'_, RayHits> {
+        self.rays.iter()
+    }
+}
@@ -1,18 +1,20 @@
-use std::sync::Arc;
+use std::{cmp::Ordering, collections::BinaryHeap, sync::Arc};
 
 use crate::prelude::*;
 use bevy::{prelude::*, utils::HashMap};
 use parry::{
+    bounding_volume::Aabb,
     partitioning::{Qbvh, QbvhUpdateWorkspace},
     query::{
         details::{
+            NonlinearTOICompositeShapeShapeBestFirstVisitor,
             RayCompositeShapeToiAndNormalBestFirstVisitor, TOICompositeShapeShapeBestFirstVisitor,
         },
         point::PointCompositeShapeProjBestFirstVisitor,
         visitors::{
             BoundingVolumeIntersectionsVisitor, PointIntersectionsVisitor, RayIntersectionsVisitor,
         },
-        DefaultQueryDispatcher, QueryDispatcher,
+        DefaultQueryDispatcher, NonlinearRigidMotion, PointQuery, QueryDispatcher, TOI,
     },
     shape::{Shape, TypedSimdCompositeShape},
     utils::DefaultStorage,
@@ -22,6 +24,9 @@ use parry::{
 ///
 /// The pipeline maintains a quaternary bounding volume hierarchy `Qbvh` of the world's colliders
 /// as an acceleration structure for spatial queries.
+///
+/// With [`BroadPhaseBackend::SpatialQueryPipeline`], the broad phase maintains the `Qbvh`
+/// and uses it for finding collision pairs, so the tree isn't refitted twice every frame.
 #[derive(Resource, Clone)]
 pub struct SpatialQueryPipeline {
     pub(crate) qbvh: Qbvh<u32>,
@@ -29,6 +34,7 @@ pub struct SpatialQueryPipeline {
     pub(crate) workspace: QbvhUpdateWorkspace,
     pub(crate) colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
     pub(crate) entity_generations: HashMap<u32, u32>,
+    pub(crate) filter_data: QueryFilterData,
 }
 
 impl Default for SpatialQueryPipeline {
@@ -39,6 +45,7 @@ impl Default for SpatialQueryPipeline {
             workspace: QbvhUpdateWorkspace::default(),
             colliders: HashMap::default(),
             entity_generations: HashMap::default(),
+            filter_data: QueryFilterData::default(),
         }
     }
 }
@@ -60,10 +67,14 @@ impl SpatialQueryPipeline {
         }
     }
 
-    /// Updates the associated acceleration structures with a new set of entities.
+    /// Updates the associated acceleration structures with the given added or changed colliders,
+    /// and removes the given removed colliders.
+    ///
+    /// Colliders that are not given are kept as they are, so only colliders that have been added or have had
+    /// their [`Position`], [`Rotation`], [`Collider`] or [`CollisionLayers`] changed need to be passed here.
     pub fn update_incremental<'a>(
         &mut self,
-        colliders: impl Iterator<
+        changed_colliders: impl Iterator<
             Item = (
                 Entity,
                 &'a Position,
@@ -72,71 +83,105 @@ impl SpatialQueryPipeline {
                 Option<&'a CollisionLayers>,
             ),
         >,
-        added_colliders: impl Iterator<Item = Entity>,
-        changed_colliders: impl Iterator<Item = Entity>,
         removed_colliders: impl Iterator<Item = Entity>,
         refit_and_balance: bool,
     ) {
-        let colliders = colliders
-            .map(|(entity, position, rotation, collider, layers)| {
-                (
-                    entity,
-                    (
-                        utils::make_isometry(position.0, rotation),
-                        collider.clone(),
-                        layers.map_or(CollisionLayers::default(), |layers| *layers),
-                    ),
-                )
-            })
-            .collect();
-        let added = added_colliders.collect::<Vec<_>>();
-        let modified = changed_colliders.collect::<Vec<_>>();
-        let removed = removed_colliders.collect::<Vec<_>>();
+        self.update_colliders(changed_colliders, removed_colliders);
 
-        self.update_incremental_internal(colliders, &added, &modified, &removed, refit_and_balance)
+        if refit_and_balance {
+            self.refit_and_rebalance(|_| None);
+        }
     }
 
-    fn update_incremental_internal(
+    /// Updates the pipeline using the [`ColliderAabb`]s of the broad phase when it is using
+    /// [`BroadPhaseBackend::SpatialQueryPipeline`].
+    ///
+    /// Only the given changed and removed colliders are updated. The AABBs of the leaves are
+    /// returned by `aabb`, or computed from the collider shapes if it returns `None`.
+    pub(crate) fn update_from_broad_phase<'a>(
         &mut self,
-        colliders: HashMap<Entity, (Isometry<Scalar>, Collider, CollisionLayers)>,
-        added: &[Entity],
-        modified: &[Entity],
-        removed: &[Entity],
-        refit_and_balance: bool,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+        aabb: impl Fn(Entity) -> Option<Aabb>,
     ) {
-        self.colliders = colliders;
+        self.update_colliders(changed_colliders, removed_colliders);
+        self.refit_and_rebalance(aabb);
+    }
 
-        // Insert or update generations of added entities
-        for added in added {
-            let index = added.index();
-            if let Some(generation) = self.entity_generations.get_mut(&index) {
-                *generation = added.generation();
-            } else {
-                self.entity_generations.insert(index, added.generation());
+    /// Removes the removed colliders, inserts or replaces the changed colliders,
+    /// and marks their leaves in the `Qbvh` as needing to be refitted.
+    fn update_colliders<'a>(
+        &mut self,
+        changed_colliders: impl Iterator<
+            Item = (
+                Entity,
+                &'a Position,
+                &'a Rotation,
+                &'a Collider,
+                Option<&'a CollisionLayers>,
+            ),
+        >,
+        removed_colliders: impl Iterator<Item = Entity>,
+    ) {
+        for removed in removed_colliders {
+            if self.colliders.remove(&removed).is_some() {
+                self.qbvh.remove(removed.index());
+                self.entity_generations.remove(&removed.index());
             }
         }
 
-        for removed in removed {
-            self.qbvh.remove(removed.index());
-        }
-
-        for modified in modified {
-            if self.colliders.contains_key(modified) {
-                self.qbvh.pre_update_or_insert(modified.index());
-            }
+        for (entity, position, rotation, collider, layers) in changed_colliders {
+            self.entity_generations
+                .insert(entity.index(), entity.generation());
+            self.colliders.insert(
+                entity,
+                (
+                    utils::make_isometry(position.0, rotation),
+                    collider.clone(),
+                    layers.map_or(CollisionLayers::default(), |layers| *layers),
+                ),
+            );
+            self.qbvh.pre_update_or_insert(entity.index());
         }
+    }
 
-        if refit_and_balance {
-            let _ = self.qbvh.refit(0.0, &mut self.workspace, |entity_index| {
-                // Construct entity ID
-                let generation = self.entity_generations.get(entity_index).map_or(0, |i| *i);
-                let entity = utils::entity_from_index_and_gen(*entity_index, generation);
-                // Compute and return AABB
+    /// Refits the leaves that have been marked as changed and rebalances the `Qbvh`.
+    ///
+    /// The AABBs of the leaves are returned by `aabb`, or computed from the collider shapes if it returns `None`.
+    fn refit_and_rebalance(&mut self, aabb: impl Fn(Entity) -> Option<Aabb>) {
+        let _ = self.qbvh.refit(0.0, &mut self.workspace, |entity_index| {
+            // Construct entity ID
+            let generation = self.entity_generations.get(entity_index).map_or(0, |i| *i);
+            let entity = utils::entity_from_index_and_gen(*entity_index, generation);
+            // Compute and return AABB
+            aabb(entity).unwrap_or_else(|| {
                 let (iso, shape, _) = self.colliders.get(&entity).unwrap();
-                let aabb = shape.get_shape().compute_aabb(iso);
-                aabb
-            });
-            self.qbvh.rebalance(0.0, &mut self.workspace);
+                shape.get_shape().compute_aabb(iso)
+            })
+        });
+        self.qbvh.rebalance(0.0, &mut self.workspace);
+    }
+
+    /// Updates the poses of the given colliders without updating the acceleration structure.
+    ///
+    /// This is used when the broad phase maintains the acceleration structure,
+    /// see [`BroadPhaseBackend::SpatialQueryPipeline`].
+    pub(crate) fn update_poses<'a>(
+        &mut self,
+        colliders: impl Iterator<Item = (Entity, &'a Position, &'a Rotation)>,
+    ) {
+        for (entity, position, rotation) in colliders {
+            if let Some((iso, ..)) = self.colliders.get_mut(&entity) {
+                *iso = utils::make_isometry(position.0, rotation);
+            }
         }
     }
 
@@ -144,6 +189,21 @@ impl SpatialQueryPipeline {
         utils::entity_from_index_and_gen(index, *self.entity_generations.get(&index).unwrap())
     }
 
+    /// Creates the [`ShapeHitData`] for a shape cast hit against the pipeline's composite shape,
+    /// where the first witness point and normal are on the hit collider in world space.
+    fn composite_shape_hit(&self, entity_index: u32, hit: &TOI) -> Option<ShapeHitData> {
+        let entity = self.entity_from_index(entity_index);
+        let (iso, collider, _) = self.colliders.get(&entity)?;
+        Some(ShapeHitData::new(
+            entity,
+            hit,
+            hit.witness1.into(),
+            hit.normal1.into(),
+            collider,
+            iso,
+        ))
+    }
+
     /// Casts a [ray](spatial_query#ray-casting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -176,10 +236,10 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| RayHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.toi,
-                normal: hit.normal.into(),
+            .and_then(|(_, (entity_index, hit))| {
+                let entity = self.entity_from_index(entity_index);
+                let (iso, collider, _) = self.colliders.get(&entity)?;
+                Some(RayHitData::new(entity, &ray, hit, collider, iso))
             })
     }
 
@@ -255,15 +315,11 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
             if let Some((iso, shape, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
+                if query_filter.test_collider(entity, *layers, &self.filter_data) {
                     if let Some(hit) =
                         shape.cast_ray_and_get_normal(iso, &ray, max_time_of_impact, solid)
                     {
-                        let hit = RayHitData {
-                            entity,
-                            time_of_impact: hit.toi,
-                            normal: hit.normal.into(),
-                        };
+                        let hit = RayHitData::new(entity, &ray, hit, shape, iso);
                         hits.push(hit);
 
                         return callback(hit);
@@ -319,12 +375,32 @@ impl SpatialQueryPipeline {
             rotation = Rotation::from(shape_rotation);
         }
 
-        let shape_isometry = utils::make_isometry(origin, &rotation);
+        self.cast_shape_with_isometry(
+            shape,
+            &utils::make_isometry(origin, &rotation),
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
+    /// Casts a [shape](spatial_query#shape-casting) from the given isometry and computes the closest
+    /// [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    pub(crate) fn cast_shape_with_isometry(
+        &self,
+        shape: &Collider,
+        shape_isometry: &Isometry<Scalar>,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
         let shape_direction = direction.into();
         let pipeline_shape = self.as_composite_shape(query_filter);
         let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
             &*self.dispatcher,
-            &shape_isometry,
+            shape_isometry,
             &shape_direction,
             &pipeline_shape,
             &**shape.get_shape(),
@@ -334,14 +410,104 @@ impl SpatialQueryPipeline {
 
         self.qbvh
             .traverse_best_first(&mut visitor)
-            .map(|(_, (entity_index, hit))| ShapeHitData {
-                entity: self.entity_from_index(entity_index),
-                time_of_impact: hit.toi,
-                point1: hit.witness1.into(),
-                point2: hit.witness2.into(),
-                normal1: hit.normal1.into(),
-                normal2: hit.normal2.into(),
-            })
+            .and_then(|(_, (entity_index, hit))| self.composite_shape_hit(entity_index, &hit))
+    }
+
+    /// Casts a [shape](spatial_query#shape-casting) that moves with a constant linear and angular velocity
+    /// and computes the first [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// Unlike [`cast_shape`](Self::cast_shape), the shape rotates while it travels, and the time of impact
+    /// is the time at which the shape first hits a collider instead of the distance it has travelled.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape at the start of the cast.
+    /// - `local_center`: The point that the shape rotates around, in the local space of the shape.
+    /// - `linear_velocity`: The linear velocity of the shape.
+    /// - `angular_velocity`: The angular velocity of the shape in radians per second.
+    /// - `max_time_of_impact`: How long the shape moves for.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    /// shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    /// hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::cast_shape_nonlinear]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_nonlinear(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        local_center: Vector,
+        linear_velocity: Vector,
+        angular_velocity: AngularValue,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        self.cast_shape_nonlinear_with_isometry(
+            shape,
+            &utils::make_isometry(origin, &rotation),
+            local_center,
+            linear_velocity,
+            angular_velocity,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
+    /// Casts a [shape](spatial_query#shape-casting) that moves with a constant linear and angular velocity
+    /// from the given isometry and computes the first [hit](ShapeHitData) with a collider.
+    /// If there are no hits, `None` is returned.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn cast_shape_nonlinear_with_isometry(
+        &self,
+        shape: &Collider,
+        shape_isometry: &Isometry<Scalar>,
+        local_center: Vector,
+        linear_velocity: Vector,
+        angular_velocity: AngularValue,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        // The colliders in the pipeline are already in world space and don't move during the cast
+        let pipeline_motion = NonlinearRigidMotion::identity();
+        #[cfg_attr(feature = "2d", allow(clippy::useless_conversion))]
+        let shape_motion = NonlinearRigidMotion::new(
+            *shape_isometry,
+            local_center.into(),
+            linear_velocity.into(),
+            angular_velocity.into(),
+        );
+        let pipeline_shape = self.as_composite_shape(query_filter);
+        let mut visitor = NonlinearTOICompositeShapeShapeBestFirstVisitor::new(
+            &*self.dispatcher,
+            &pipeline_motion,
+            &pipeline_shape,
+            &shape_motion,
+            &**shape.get_shape(),
+            0.0,
+            max_time_of_impact,
+            !ignore_origin_penetration,
+        );
+
+        self.qbvh
+            .traverse_best_first(&mut visitor)
+            .and_then(|(_, (entity_index, hit))| self.composite_shape_hit(entity_index, &hit))
     }
 
     /// Casts a [shape](spatial_query#shape-casting) with a given rotation and computes computes all [hits](ShapeHitData)
@@ -446,17 +612,10 @@ impl SpatialQueryPipeline {
                 !ignore_origin_penetration,
             );
 
-            if let Some(hit) =
-                self.qbvh
-                    .traverse_best_first(&mut visitor)
-                    .map(|(_, (entity_index, hit))| ShapeHitData {
-                        entity: self.entity_from_index(entity_index),
-                        time_of_impact: hit.toi,
-                        point1: hit.witness1.into(),
-                        point2: hit.witness2.into(),
-                        normal1: hit.normal1.into(),
-                        normal2: hit.normal2.into(),
-                    })
+            if let Some(hit) = self
+                .qbvh
+                .traverse_best_first(&mut visitor)
+                .and_then(|(_, (entity_index, hit))| self.composite_shape_hit(entity_index, &hit))
             {
                 hits.push(hit);
                 query_filter.excluded_entities.insert(hit.entity);
@@ -503,6 +662,170 @@ impl SpatialQueryPipeline {
             })
     }
 
+    /// Finds up to `k` [colliders](Collider) closest to the given point within `max_distance`,
+    /// sorted by their distance to the point. Colliders that contain the point have a distance of zero.
+    ///
+    /// The colliders are found by traversing the `Qbvh` best-first, so only the colliders
+    /// that are closer than the `k`th closest collider need to be checked.
+    ///
+    /// ## Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance between the point and the colliders.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::nearest_colliders]
+    pub fn nearest_colliders(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<NearestColliderData> {
+        let point = point.into();
+
+        self.traverse_nearest(
+            k,
+            max_distance,
+            &query_filter,
+            |aabb| aabb.distance_to_local_point(&point, true),
+            |_, iso, collider| {
+                let projection = collider.get_shape().project_point(iso, &point, true);
+                let distance = if projection.is_inside {
+                    0.0
+                } else {
+                    (projection.point - point).norm()
+                };
+                Some((distance, projection.point.into()))
+            },
+        )
+        .into_iter()
+        .map(|(entity, distance, point)| NearestColliderData {
+            entity,
+            distance,
+            point,
+        })
+        .collect()
+    }
+
+    /// Finds all [colliders](Collider) within `radius` of the given point, sorted by their distance to the point.
+    /// Colliders that contain the point have a distance of zero.
+    ///
+    /// ## Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `radius`: The maximum distance between the point and the colliders.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::colliders_within_radius]
+    pub fn colliders_within_radius(
+        &self,
+        point: Vector,
+        radius: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<NearestColliderData> {
+        self.nearest_colliders(point, usize::MAX, radius, query_filter)
+    }
+
+    /// Traverses the `Qbvh` best-first and returns up to `k` colliders within `max_distance`
+    /// in the order of their distance, along with the data computed for them.
+    ///
+    /// `node_distance` returns a lower bound for the distance of the colliders in a node with the given AABB,
+    /// and `collider_distance` returns the exact distance of a collider, or `None` if it should be skipped.
+    fn traverse_nearest<T>(
+        &self,
+        k: usize,
+        max_distance: Scalar,
+        query_filter: &SpatialQueryFilter,
+        node_distance: impl Fn(&Aabb) -> Scalar,
+        mut collider_distance: impl FnMut(Entity, &Isometry<Scalar>, &Collider) -> Option<(Scalar, T)>,
+    ) -> Vec<(Entity, Scalar, T)> {
+        let mut nearest = vec![];
+        let nodes = self.qbvh.raw_nodes();
+        let proxies = self.qbvh.raw_proxies();
+
+        if k == 0 || nodes.is_empty() {
+            return nearest;
+        }
+
+        let mut queue = BinaryHeap::new();
+        queue.push(NearestCandidate {
+            distance: 0.0,
+            kind: NearestCandidateKind::Node(0),
+        });
+
+        // Nodes and colliders are visited in the order of their distance.
+        // When a collider is popped from the queue, it is closer than everything left in the queue.
+        while let Some(candidate) = queue.pop() {
+            if candidate.distance > max_distance {
+                break;
+            }
+
+            let node_index = match candidate.kind {
+                NearestCandidateKind::Collider(entity, data) => {
+                    nearest.push((entity, candidate.distance, data));
+                    if nearest.len() == k {
+                        break;
+                    }
+                    continue;
+                }
+                NearestCandidateKind::Node(node_index) => node_index,
+            };
+
+            let node = &nodes[node_index as usize];
+
+            for (lane, child) in node.children.iter().enumerate() {
+                if *child == u32::MAX {
+                    continue;
+                }
+
+                let aabb = node.simd_aabb.extract(lane);
+                if aabb.mins.x > aabb.maxs.x {
+                    // Empty lane
+                    continue;
+                }
+
+                if !node.is_leaf() {
+                    let distance = node_distance(&aabb);
+                    if distance <= max_distance {
+                        queue.push(NearestCandidate {
+                            distance,
+                            kind: NearestCandidateKind::Node(*child),
+                        });
+                    }
+                    continue;
+                }
+
+                let Some(proxy) = proxies.get(*child as usize) else {
+                    continue;
+                };
+                let Some(generation) = self.entity_generations.get(&proxy.data) else {
+                    continue;
+                };
+                let entity = utils::entity_from_index_and_gen(proxy.data, *generation);
+
+                let Some((iso, collider, layers)) = self.colliders.get(&entity) else {
+                    continue;
+                };
+                if !query_filter.test_collider(entity, *layers, &self.filter_data) {
+                    continue;
+                }
+
+                if let Some((distance, data)) = collider_distance(entity, iso, collider) {
+                    if distance <= max_distance {
+                        queue.push(NearestCandidate {
+                            distance,
+                            kind: NearestCandidateKind::Collider(entity, data),
+                        });
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that contains the given point.
     ///
@@ -544,7 +867,9 @@ impl SpatialQueryPipeline {
         let mut leaf_callback = &mut |entity_index: &u32| {
             let entity = self.entity_from_index(*entity_index);
             if let Some((isometry, shape, layers)) = self.colliders.get(&entity) {
-                if query_filter.test(entity, *layers) && shape.contains_point(isometry, &point) {
+                if query_filter.test_collider(entity, *layers, &self.filter_data)
+                    && shape.contains_point(isometry, &point)
+                {
                     intersections.push(entity);
                     return callback(entity);
                 }
@@ -658,7 +983,7 @@ impl SpatialQueryPipeline {
             let entity = self.entity_from_index(*entity_index);
 
             if let Some((collider_isometry, collider, layers)) = colliders.get(&entity) {
-                if query_filter.test(entity, *layers) {
+                if query_filter.test_collider(entity, *layers, &self.filter_data) {
                     let isometry = inverse_shape_isometry * collider_isometry;
 
                     if dispatcher.intersection_test(
@@ -681,6 +1006,374 @@ impl SpatialQueryPipeline {
 
         intersections
     }
+
+    /// Casts a [ray](spatial_query#ray-casting) against the collider of the given entity only.
+    /// If the ray doesn't hit the collider or the entity doesn't have a collider, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the ray is cast against.
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of the collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    ///
+    /// See also: [SpatialQuery::cast_ray_on]
+    pub fn cast_ray_on(
+        &self,
+        entity: Entity,
+        origin: Vector,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<RayHitData> {
+        let (iso, collider, _) = self.colliders.get(&entity)?;
+        let ray = parry::query::Ray::new(origin.into(), direction.into());
+
+        collider
+            .get_shape()
+            .cast_ray_and_get_normal(iso, &ray, max_time_of_impact, solid)
+            .map(|hit| RayHitData::new(entity, &ray, hit, collider, iso))
+    }
+
+    /// Casts a [shape](spatial_query#shape-casting) with a given rotation against the collider of the given entity only.
+    /// If the shape doesn't hit the collider or the entity doesn't have a collider, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the shape is cast against.
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating the collider at the
+    /// shape origin, the hit will be ignored. Otherwise, the initial hit will be returned.
+    ///
+    /// See also: [SpatialQuery::cast_shape_on]
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_on(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+    ) -> Option<ShapeHitData> {
+        let (collider_isometry, collider, _) = self.colliders.get(&entity)?;
+
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(origin, &rotation);
+        // The velocity of the collider relative to the cast shape, in the local space of the cast shape
+        let local_velocity = shape_isometry.inverse_transform_vector(&(-direction).into());
+
+        self.dispatcher
+            .time_of_impact(
+                &shape_isometry.inv_mul(collider_isometry),
+                &local_velocity,
+                &**shape.get_shape(),
+                &**collider.get_shape(),
+                max_time_of_impact,
+                !ignore_origin_penetration,
+            )
+            .ok()
+            .flatten()
+            .map(|hit| {
+                // The second witness point and normal are in the local space of the collider
+                ShapeHitData::new(
+                    entity,
+                    &hit,
+                    (collider_isometry * hit.witness2).into(),
+                    (collider_isometry * hit.normal2.into_inner()).into(),
+                    collider,
+                    collider_isometry,
+                )
+            })
+    }
+
+    /// Finds the [projection](spatial_query#point-projection) of a given point on the collider of the given entity only.
+    /// If the entity doesn't have a collider, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the point is projected on.
+    /// - `point`: The point that should be projected.
+    /// - `solid`: If true and the point is inside of the collider, the projection will be at the point.
+    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
+    ///
+    /// See also: [SpatialQuery::project_point_on]
+    pub fn project_point_on(
+        &self,
+        entity: Entity,
+        point: Vector,
+        solid: bool,
+    ) -> Option<PointProjection> {
+        let (iso, collider, _) = self.colliders.get(&entity)?;
+        let projection = collider
+            .get_shape()
+            .project_point(iso, &point.into(), solid);
+
+        Some(PointProjection {
+            entity,
+            point: projection.point.into(),
+            is_inside: projection.is_inside,
+        })
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that checks if the colliders of the two
+    /// given entities are intersecting. If either entity doesn't have a collider, false is returned.
+    ///
+    /// See also: [SpatialQuery::intersects]
+    pub fn intersects(&self, entity1: Entity, entity2: Entity) -> bool {
+        let (Some((iso1, collider1, _)), Some((iso2, collider2, _))) =
+            (self.colliders.get(&entity1), self.colliders.get(&entity2))
+        else {
+            return false;
+        };
+
+        self.dispatcher.intersection_test(
+            &iso1.inv_mul(iso2),
+            &**collider1.get_shape(),
+            &**collider2.get_shape(),
+        ) == Ok(true)
+    }
+
+    /// Computes the closest points between the colliders of the two given entities, along with the normal
+    /// and the distance between them. If the colliders are further apart than `max_distance`,
+    /// [`ClosestPointsData::Disjoint`] is returned.
+    ///
+    /// If either entity doesn't have a collider or the query isn't supported for the shapes, `None` is returned.
+    ///
+    /// See also: [SpatialQuery::closest_points]
+    pub fn closest_points(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        max_distance: Scalar,
+    ) -> Option<ClosestPointsData> {
+        let (iso1, collider1, _) = self.colliders.get(&entity1)?;
+        let (iso2, collider2, _) = self.colliders.get(&entity2)?;
+
+        self.dispatcher
+            .closest_points(
+                &iso1.inv_mul(iso2),
+                &**collider1.get_shape(),
+                &**collider2.get_shape(),
+                max_distance,
+            )
+            .ok()
+            .map(|closest_points| ClosestPointsData::from_parry(closest_points, iso1, iso2))
+    }
+
+    /// Finds the [collider](Collider) closest to the given shape, and computes the closest points between them,
+    /// along with the normal and the distance. If there are no colliders, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that distances are computed from, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::distance]
+    pub fn distance(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeDistanceData> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(shape_position, &rotation);
+        let shape_aabb = shape.get_shape().compute_aabb(&shape_isometry);
+
+        let (entity, _, _) = self
+            .traverse_nearest(
+                1,
+                Scalar::MAX,
+                &query_filter,
+                |aabb| aabb_distance(aabb, &shape_aabb),
+                |_, iso, collider| {
+                    self.dispatcher
+                        .distance(
+                            &shape_isometry.inv_mul(iso),
+                            &**shape.get_shape(),
+                            &**collider.get_shape(),
+                        )
+                        .ok()
+                        .map(|distance| (distance, ()))
+                },
+            )
+            .pop()?;
+
+        let (iso, collider, _) = self.colliders.get(&entity)?;
+
+        // The collider is already known to be the closest one, so the closest points are computed
+        // without a distance limit. Using `distance` as the limit could make the collider be reported
+        // as disjoint due to numerical differences between the distance and closest point queries.
+        self.dispatcher
+            .closest_points(
+                &shape_isometry.inv_mul(iso),
+                &**shape.get_shape(),
+                &**collider.get_shape(),
+                Scalar::MAX,
+            )
+            .ok()
+            .map(|closest_points| ShapeDistanceData {
+                entity,
+                closest_points: ClosestPointsData::from_parry(closest_points, &shape_isometry, iso),
+            })
+    }
+
+    /// Computes how the given shape penetrates each [collider](Collider) that it overlaps.
+    /// The results are sorted by entity.
+    ///
+    /// The [translation](PenetrationData::translation) of each result is the minimal translation
+    /// that moves the shape out of that collider. To move the shape out of all of the colliders at once,
+    /// use [`compute_depenetration`](Self::compute_depenetration).
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that is tested for penetration, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::compute_penetration]
+    pub fn compute_penetration(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<PenetrationData> {
+        let rotation: Rotation;
+        #[cfg(feature = "2d")]
+        {
+            rotation = Rotation::from_radians(shape_rotation);
+        }
+        #[cfg(feature = "3d")]
+        {
+            rotation = Rotation::from(shape_rotation);
+        }
+
+        let shape_isometry = utils::make_isometry(shape_position, &rotation);
+        let shape_aabb = shape.get_shape().compute_aabb(&shape_isometry);
+
+        let mut entity_indices = vec![];
+        self.qbvh.intersect_aabb(&shape_aabb, &mut entity_indices);
+
+        let mut penetrations: Vec<PenetrationData> = entity_indices
+            .into_iter()
+            .filter_map(|entity_index| {
+                let entity = self.entity_from_index(entity_index);
+                let (iso, collider, layers) = self.colliders.get(&entity)?;
+
+                if !query_filter.test_collider(entity, *layers, &self.filter_data) {
+                    return None;
+                }
+
+                let contact = self
+                    .dispatcher
+                    .contact(
+                        &shape_isometry.inv_mul(iso),
+                        &**shape.get_shape(),
+                        &**collider.get_shape(),
+                        0.0,
+                    )
+                    .ok()
+                    .flatten()
+                    .filter(|contact| contact.dist < 0.0)?;
+
+                // The contact normal points from the shape towards the collider,
+                // so the shape is pushed out in the opposite direction
+                let normal: Vector =
+                    (shape_isometry.rotation * -contact.normal1.into_inner()).into();
+
+                Some(PenetrationData {
+                    entity,
+                    normal,
+                    depth: -contact.dist,
+                })
+            })
+            .collect();
+
+        penetrations.sort_by_key(|penetration| penetration.entity);
+        penetrations
+    }
+
+    /// Computes a single translation that moves the given shape out of all of the [colliders](Collider) that it overlaps.
+    ///
+    /// The penetrations are resolved one after another, and they are recomputed at the new position
+    /// up to `max_iterations` times, until the shape no longer overlaps any colliders. Some penetration may remain
+    /// if the shape is wedged between colliders.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that should be moved out of the colliders, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `max_iterations`: The maximum number of times the penetrations are recomputed.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// See also: [SpatialQuery::compute_depenetration]
+    pub fn compute_depenetration(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        max_iterations: usize,
+        query_filter: SpatialQueryFilter,
+    ) -> Vector {
+        let mut translation = Vector::ZERO;
+
+        for _ in 0..max_iterations {
+            let penetrations = self.compute_penetration(
+                shape,
+                shape_position + translation,
+                shape_rotation,
+                query_filter.clone(),
+            );
+
+            if penetrations.is_empty() {
+                break;
+            }
+
+            let mut iteration_translation = Vector::ZERO;
+            for penetration in penetrations.iter() {
+                // Only apply the part of the penetration that hasn't been resolved by the previous ones
+                let remaining_depth =
+                    penetration.depth - iteration_translation.dot(penetration.normal);
+                if remaining_depth > 0.0 {
+                    iteration_translation += penetration.normal * remaining_depth;
+                }
+            }
+            translation += iteration_translation;
+        }
+
+        translation
+    }
 }
 
 pub(crate) struct QueryPipelineAsCompositeShape<'a> {
@@ -706,7 +1399,10 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
                     *self.pipeline.entity_generations.get(&shape_id).unwrap(),
                 ))
         {
-            if self.query_filter.test(*entity, *layers) {
+            if self
+                .query_filter
+                .test_collider(*entity, *layers, &self.pipeline.filter_data)
+            {
                 f(Some(iso), &**shape.get_shape());
             }
         }
@@ -725,6 +1421,159 @@ impl<'a> TypedSimdCompositeShape for QueryPipelineAsCompositeShape<'a> {
     }
 }
 
+/// The closest points between two shapes, computed by [`SpatialQuery::closest_points`] and [`SpatialQuery::distance`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClosestPointsData {
+    /// The shapes are intersecting.
+    Intersecting,
+    /// The shapes are not intersecting, but they are within the maximum distance.
+    WithinMargin {
+        /// The closest point on the first shape in world space.
+        point1: Vector,
+        /// The closest point on the second shape in world space.
+        point2: Vector,
+        /// The unit vector pointing from `point1` towards `point2`. Zero if the shapes are touching.
+        normal: Vector,
+        /// The distance between the shapes.
+        distance: Scalar,
+    },
+    /// The shapes are further apart than the maximum distance.
+    Disjoint,
+}
+
+impl ClosestPointsData {
+    /// Returns the distance between the shapes, or `None` if they are further apart than the maximum distance.
+    /// Intersecting shapes have a distance of zero.
+    pub fn distance(&self) -> Option<Scalar> {
+        match self {
+            Self::Intersecting => Some(0.0),
+            Self::WithinMargin { distance, .. } => Some(*distance),
+            Self::Disjoint => None,
+        }
+    }
+
+    /// Converts closest points in the local spaces of two shapes into world space.
+    fn from_parry(
+        closest_points: parry::query::ClosestPoints,
+        iso1: &Isometry<Scalar>,
+        iso2: &Isometry<Scalar>,
+    ) -> Self {
+        match closest_points.transform_by(iso1, iso2) {
+            parry::query::ClosestPoints::Intersecting => Self::Intersecting,
+            parry::query::ClosestPoints::WithinMargin(point1, point2) => {
+                let point1 = Vector::from(point1);
+                let point2 = Vector::from(point2);
+                let offset = point2 - point1;
+                Self::WithinMargin {
+                    point1,
+                    point2,
+                    normal: offset.normalize_or_zero(),
+                    distance: offset.length(),
+                }
+            }
+            parry::query::ClosestPoints::Disjoint => Self::Disjoint,
+        }
+    }
+}
+
+/// The penetration of a shape into a [collider](Collider), computed by [`SpatialQuery::compute_penetration`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PenetrationData {
+    /// The entity of the collider that the shape penetrates.
+    pub entity: Entity,
+    /// The unit vector in world space that the shape should be moved along to resolve the penetration.
+    pub normal: Vector,
+    /// The penetration depth, i.e. how far the shape should be moved along the `normal`.
+    pub depth: Scalar,
+}
+
+impl PenetrationData {
+    /// Returns the minimal translation that moves the shape out of the collider, the `normal` multiplied by the `depth`.
+    pub fn translation(&self) -> Vector {
+        self.normal * self.depth
+    }
+}
+
+/// The closest [collider](Collider) to a shape, found by [`SpatialQuery::distance`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeDistanceData {
+    /// The entity of the closest collider.
+    pub entity: Entity,
+    /// The closest points between the shape and the collider. The first point is on the shape,
+    /// and the second point is on the collider.
+    pub closest_points: ClosestPointsData,
+}
+
+/// Returns the distance between two AABBs, or zero if they are intersecting.
+fn aabb_distance(aabb1: &Aabb, aabb2: &Aabb) -> Scalar {
+    (aabb1.mins - aabb2.maxs)
+        .sup(&(aabb2.mins - aabb1.maxs))
+        .sup(&parry::math::Vector::zeros())
+        .norm()
+}
+
+/// A [collider](Collider) found by [`SpatialQuery::nearest_colliders`] or [`SpatialQuery::colliders_within_radius`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NearestColliderData {
+    /// The entity of the collider.
+    pub entity: Entity,
+    /// The distance between the query point and the collider. Zero if the collider contains the point.
+    pub distance: Scalar,
+    /// The closest point on the collider to the query point, or the query point itself if the collider contains it.
+    pub point: Vector,
+}
+
+/// A node or collider in the queue of the best-first traversals used by [`SpatialQueryPipeline::nearest_colliders`]
+/// and [`SpatialQueryPipeline::distance`], ordered so that the closest candidate is popped first.
+struct NearestCandidate<T> {
+    distance: Scalar,
+    kind: NearestCandidateKind<T>,
+}
+
+enum NearestCandidateKind<T> {
+    Node(u32),
+    Collider(Entity, T),
+}
+
+impl<T> PartialEq for NearestCandidate<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for NearestCandidate<T> {}
+
+impl<T> PartialOrd for NearestCandidate<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for NearestCandidate<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed so that the binary heap pops the closest candidate first.
+        // Colliders are popped before nodes at the same distance.
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| match (&self.kind, &other.kind) {
+                (
+                    NearestCandidateKind::Collider(entity1, _),
+                    NearestCandidateKind::Collider(entity2, _),
+                ) => entity2.cmp(entity1),
+                (NearestCandidateKind::Collider(..), NearestCandidateKind::Node(_)) => {
+                    Ordering::Greater
+                }
+                (NearestCandidateKind::Node(_), NearestCandidateKind::Collider(..)) => {
+                    Ordering::Less
+                }
+                (NearestCandidateKind::Node(node1), NearestCandidateKind::Node(node2)) => {
+                    node2.cmp(node1)
+                }
+            })
+    }
+}
+
 /// The result of a [point projection](spatial_query#point-projection) on a [collider](Collider).
 #[derive(Debug, Clone, PartialEq)]
 pub struct PointProjection {
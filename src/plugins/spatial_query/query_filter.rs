@@ -1,9 +1,18 @@
-use bevy::{prelude::*, utils::HashSet};
+use std::sync::Arc;
+
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
 
 use crate::prelude::*;
 
 /// Rules that determine which colliders are taken into account in [spatial queries](crate::spatial_query).
 ///
+/// Colliders can be filtered based on their [collision layers](CollisionLayers), their entities,
+/// the type of the [rigid body](RigidBody) they are attached to, whether they are [sensors](Sensor)
+/// or attached to [sleeping](Sleeping) bodies, and a custom predicate.
+///
 /// ## Example
 ///
 /// ```
@@ -16,10 +25,13 @@ use crate::prelude::*;
 /// fn setup(mut commands: Commands) {
 ///     let object = commands.spawn(Collider::ball(0.5)).id();
 ///
-///     // A query filter that has three collision masks and excludes the `object` entity
+///     // A query filter that has three collision masks, excludes the `object` entity
+///     // and skips sensors and the colliders of dynamic bodies
 ///     let query_filter = SpatialQueryFilter::new()
 ///         .with_masks_from_bits(0b1011)
-///         .without_entities([object]);
+///         .without_entities([object])
+///         .without_body_types([RigidBody::Dynamic])
+///         .with_sensors(QueryFilterMode::Exclude);
 ///
 ///     // Spawn a ray caster with the query filter
 ///     commands.spawn(RayCaster::default().with_query_filter(query_filter));
@@ -29,15 +41,63 @@ use crate::prelude::*;
 pub struct SpatialQueryFilter {
     /// Specifies which [collision groups](CollisionLayers) will be included in a [spatial query](crate::spatial_query).
     pub masks: u32,
+    /// Specifies which [collision groups](CollisionLayers) the query belongs to. Colliders are only included
+    /// in a [spatial query](crate::spatial_query) if their masks contain at least one of these groups.
+    ///
+    /// By default, the query belongs to all groups, and the masks of colliders are ignored.
+    /// This means that colliders whose masks are empty are still included.
+    pub groups: u32,
     /// Entities that will not be included in [spatial queries](crate::spatial_query).
     pub excluded_entities: HashSet<Entity>,
+    /// If set, only these entities will be included in [spatial queries](crate::spatial_query).
+    pub included_entities: Option<HashSet<Entity>>,
+    /// The [rigid body](RigidBody) types whose colliders will not be included
+    /// in [spatial queries](crate::spatial_query). Colliders that aren't attached to a rigid body are not affected.
+    pub excluded_body_types: Vec<RigidBody>,
+    /// Determines if [`Sensor`] colliders are included in [spatial queries](crate::spatial_query).
+    pub sensors: QueryFilterMode,
+    /// Determines if the colliders of [`Sleeping`] bodies are included in [spatial queries](crate::spatial_query).
+    pub sleeping: QueryFilterMode,
+    /// A custom predicate that must return true for an entity to be included
+    /// in [spatial queries](crate::spatial_query).
+    pub predicate: Option<Arc<dyn Fn(Entity) -> bool + Send + Sync>>,
 }
 
 impl Default for SpatialQueryFilter {
     fn default() -> Self {
         Self {
             masks: 0xffff_ffff,
+            groups: 0xffff_ffff,
             excluded_entities: default(),
+            included_entities: None,
+            excluded_body_types: vec![],
+            sensors: QueryFilterMode::Include,
+            sleeping: QueryFilterMode::Include,
+            predicate: None,
+        }
+    }
+}
+
+/// Determines if colliders with a given property, like [`Sensor`] colliders,
+/// are included in [spatial queries](crate::spatial_query).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum QueryFilterMode {
+    /// Colliders are included regardless of the property.
+    #[default]
+    Include,
+    /// Colliders with the property are skipped.
+    Exclude,
+    /// Only colliders with the property are included.
+    Only,
+}
+
+impl QueryFilterMode {
+    /// Tests if a collider that does or doesn't have the property should be included.
+    fn test(self, has_property: bool) -> bool {
+        match self {
+            Self::Include => true,
+            Self::Exclude => !has_property,
+            Self::Only => has_property,
         }
     }
 }
@@ -66,6 +126,25 @@ impl SpatialQueryFilter {
         self
     }
 
+    /// Sets the groups of the filter configuration using a bitmask. Only colliders whose
+    /// [collision masks](CollisionLayers) contain one of the groups will be included in the
+    /// [spatial query](crate::spatial_query).
+    pub fn with_groups_from_bits(mut self, groups: u32) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Sets the groups of the filter configuration using a list of [layers](PhysicsLayer).
+    /// Only colliders whose [collision masks](CollisionLayers) contain one of the groups
+    /// will be included in the [spatial query](crate::spatial_query).
+    pub fn with_groups(mut self, groups: impl IntoIterator<Item = impl PhysicsLayer>) -> Self {
+        self.groups = 0;
+        for group in groups.into_iter().map(|l| l.to_bits()) {
+            self.groups |= group;
+        }
+        self
+    }
+
     /// Excludes the given entities from [spatial queries](crate::spatial_query).
     #[doc(alias = "exclude_entities")]
     pub fn without_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
@@ -73,12 +152,113 @@ impl SpatialQueryFilter {
         self
     }
 
+    /// Only includes the given entities in [spatial queries](crate::spatial_query).
+    #[doc(alias = "include_entities")]
+    pub fn with_entities(mut self, entities: impl IntoIterator<Item = Entity>) -> Self {
+        self.included_entities = Some(HashSet::from_iter(entities));
+        self
+    }
+
+    /// Only includes the colliders of the given [rigid body](RigidBody) types in [spatial queries](crate::spatial_query).
+    /// Colliders that aren't attached to a rigid body are still included.
+    pub fn with_body_types(mut self, body_types: impl IntoIterator<Item = RigidBody>) -> Self {
+        let body_types = body_types.into_iter().collect::<Vec<_>>();
+        self.excluded_body_types = [RigidBody::Dynamic, RigidBody::Static, RigidBody::Kinematic]
+            .into_iter()
+            .filter(|body_type| !body_types.contains(body_type))
+            .collect();
+        self
+    }
+
+    /// Excludes the colliders of the given [rigid body](RigidBody) types from [spatial queries](crate::spatial_query).
+    pub fn without_body_types(mut self, body_types: impl IntoIterator<Item = RigidBody>) -> Self {
+        self.excluded_body_types = body_types.into_iter().collect();
+        self
+    }
+
+    /// Sets if [`Sensor`] colliders are included in [spatial queries](crate::spatial_query).
+    pub fn with_sensors(mut self, mode: QueryFilterMode) -> Self {
+        self.sensors = mode;
+        self
+    }
+
+    /// Sets if the colliders of [`Sleeping`] bodies are included in [spatial queries](crate::spatial_query).
+    pub fn with_sleeping(mut self, mode: QueryFilterMode) -> Self {
+        self.sleeping = mode;
+        self
+    }
+
+    /// Sets a custom predicate that must return true for an entity to be included
+    /// in [spatial queries](crate::spatial_query).
+    pub fn with_predicate(
+        mut self,
+        predicate: impl Fn(Entity) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.predicate = Some(Arc::new(predicate));
+        self
+    }
+
     /// Tests if an entity should be included in [spatial queries](crate::spatial_query) based on the
-    /// filter configuration.
+    /// entities, [collision layers](CollisionLayers) and predicate of the filter configuration.
+    ///
+    /// The rigid body type, [`Sensor`] and [`Sleeping`] rules are tested by the [`SpatialQueryPipeline`],
+    /// which keeps track of that information for each collider.
     pub fn test(&self, entity: Entity, layers: CollisionLayers) -> bool {
         !self.excluded_entities.contains(&entity)
-            && CollisionLayers::from_bits(0xffff_ffff, self.masks).interacts_with(
-                CollisionLayers::from_bits(layers.groups_bits(), 0xffff_ffff),
-            )
+            && self
+                .included_entities
+                .as_ref()
+                .map_or(true, |entities| entities.contains(&entity))
+            && self.test_layers(layers)
+            && self
+                .predicate
+                .as_ref()
+                .map_or(true, |predicate| predicate(entity))
     }
+
+    /// Tests if the given [collision layers](CollisionLayers) are included by the masks and groups
+    /// of the filter configuration. The masks of the layers are only tested if the groups have been changed.
+    fn test_layers(&self, layers: CollisionLayers) -> bool {
+        (self.masks & layers.groups_bits()) != 0
+            && (self.groups == 0xffff_ffff || (self.groups & layers.masks_bits()) != 0)
+    }
+
+    /// Tests if a collider should be included in [spatial queries](crate::spatial_query) based on the
+    /// whole filter configuration, using the rigid body and sensor information in `filter_data`.
+    pub(crate) fn test_collider(
+        &self,
+        entity: Entity,
+        layers: CollisionLayers,
+        filter_data: &QueryFilterData,
+    ) -> bool {
+        if !self.sensors.test(filter_data.sensors.contains(&entity)) {
+            return false;
+        }
+
+        let body = filter_data.collider_bodies.get(&entity);
+        let body_type = body.and_then(|body| filter_data.body_types.get(body));
+        if body_type.map_or(false, |body_type| {
+            self.excluded_body_types.contains(body_type)
+        }) || !self
+            .sleeping
+            .test(body.map_or(false, |body| filter_data.sleeping.contains(body)))
+        {
+            return false;
+        }
+
+        self.test(entity, layers)
+    }
+}
+
+/// The rigid body and sensor information of colliders that is used for testing [`SpatialQueryFilter`]s.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct QueryFilterData {
+    /// The rigid body that each collider is attached to.
+    pub(crate) collider_bodies: HashMap<Entity, Entity>,
+    /// The type of each rigid body.
+    pub(crate) body_types: HashMap<Entity, RigidBody>,
+    /// The rigid bodies that are sleeping.
+    pub(crate) sleeping: HashSet<Entity>,
+    /// The colliders that are sensors.
+    pub(crate) sensors: HashSet<Entity>,
 }
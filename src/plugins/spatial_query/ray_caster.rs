@@ -92,6 +92,9 @@ pub struct RayCaster {
     pub solid: bool,
     /// Rules that determine which colliders are taken into account in the query.
     pub query_filter: SpatialQueryFilter,
+    /// Controls if [`RayHitStarted`] and [`RayHitEnded`] events are sent when the ray caster
+    /// starts or stops hitting an entity. This is false by default.
+    pub hit_events: bool,
 }
 
 impl Default for RayCaster {
@@ -106,6 +109,7 @@ impl Default for RayCaster {
             max_hits: u32::MAX,
             solid: true,
             query_filter: SpatialQueryFilter::default(),
+            hit_events: false,
         }
     }
 }
@@ -161,6 +165,13 @@ impl RayCaster {
         self
     }
 
+    /// Sets if [`RayHitStarted`] and [`RayHitEnded`] events are sent when the ray caster
+    /// starts or stops hitting an entity.
+    pub fn with_hit_events(mut self, hit_events: bool) -> Self {
+        self.hit_events = hit_events;
+        self
+    }
+
     /// Enables the [`RayCaster`].
     pub fn enable(&mut self) {
         self.enabled = true;
@@ -192,73 +203,84 @@ impl RayCaster {
     }
 
     pub(crate) fn cast(&self, hits: &mut RayHits, query_pipeline: &SpatialQueryPipeline) {
-        hits.count = 0;
-        if self.max_hits == 1 {
-            let pipeline_shape = query_pipeline.as_composite_shape(self.query_filter.clone());
-            let ray =
-                parry::query::Ray::new(self.global_origin().into(), self.global_direction().into());
-            let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
-                &pipeline_shape,
-                &ray,
-                self.max_time_of_impact,
-                self.solid,
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| RayHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.toi,
-                    normal: hit.normal.into(),
-                },
-            ) {
-                if (hits.vector.len() as u32) < hits.count + 1 {
-                    hits.vector.push(hit);
-                } else {
-                    hits.vector[0] = hit;
-                }
-                hits.count = 1;
+        let ray =
+            parry::query::Ray::new(self.global_origin().into(), self.global_direction().into());
+        cast_ray_into_hits(
+            &ray,
+            self.max_time_of_impact,
+            self.max_hits,
+            self.solid,
+            &self.query_filter,
+            hits,
+            query_pipeline,
+        );
+    }
+}
+
+/// Casts the given ray and stores up to `max_hits` hits in `hits`.
+///
+/// This is shared by the [`RayCaster`] and [`MultiRayCaster`] components.
+pub(crate) fn cast_ray_into_hits(
+    ray: &parry::query::Ray,
+    max_time_of_impact: Scalar,
+    max_hits: u32,
+    solid: bool,
+    query_filter: &SpatialQueryFilter,
+    hits: &mut RayHits,
+    query_pipeline: &SpatialQueryPipeline,
+) {
+    hits.count = 0;
+    if max_hits == 1 {
+        let pipeline_shape = query_pipeline.as_composite_shape(query_filter.clone());
+        let mut visitor = RayCompositeShapeToiAndNormalBestFirstVisitor::new(
+            &pipeline_shape,
+            ray,
+            max_time_of_impact,
+            solid,
+        );
+
+        if let Some(hit) = query_pipeline
+            .qbvh
+            .traverse_best_first(&mut visitor)
+            .and_then(|(_, (entity_index, hit))| {
+                let entity = query_pipeline.entity_from_index(entity_index);
+                let (iso, shape, _) = query_pipeline.colliders.get(&entity)?;
+                Some(RayHitData::new(entity, ray, hit, shape, iso))
+            })
+        {
+            if (hits.vector.len() as u32) < hits.count + 1 {
+                hits.vector.push(hit);
+            } else {
+                hits.vector[0] = hit;
             }
-        } else {
-            let ray =
-                parry::query::Ray::new(self.global_origin().into(), self.global_direction().into());
-
-            let mut leaf_callback = &mut |entity_index: &u32| {
-                let entity = query_pipeline.entity_from_index(*entity_index);
-                if let Some((iso, shape, layers)) = query_pipeline.colliders.get(&entity) {
-                    if self.query_filter.test(entity, *layers) {
-                        if let Some(hit) = shape.cast_ray_and_get_normal(
-                            iso,
-                            &ray,
-                            self.max_time_of_impact,
-                            self.solid,
-                        ) {
-                            if (hits.vector.len() as u32) < hits.count + 1 {
-                                hits.vector.push(RayHitData {
-                                    entity,
-                                    time_of_impact: hit.toi,
-                                    normal: hit.normal.into(),
-                                });
-                            } else {
-                                hits.vector[hits.count as usize] = RayHitData {
-                                    entity,
-                                    time_of_impact: hit.toi,
-                                    normal: hit.normal.into(),
-                                };
-                            }
-
-                            hits.count += 1;
-
-                            return hits.count < self.max_hits;
+            hits.count = 1;
+        }
+    } else {
+        let mut leaf_callback = &mut |entity_index: &u32| {
+            let entity = query_pipeline.entity_from_index(*entity_index);
+            if let Some((iso, shape, layers)) = query_pipeline.colliders.get(&entity) {
+                if query_filter.test_collider(entity, *layers, &query_pipeline.filter_data) {
+                    if let Some(hit) =
+                        shape.cast_ray_and_get_normal(iso, ray, max_time_of_impact, solid)
+                    {
+                        let hit = RayHitData::new(entity, ray, hit, shape, iso);
+                        if (hits.vector.len() as u32) < hits.count + 1 {
+                            hits.vector.push(hit);
+                        } else {
+                            hits.vector[hits.count as usize] = hit;
                         }
+
+                        hits.count += 1;
+
+                        return hits.count < max_hits;
                     }
                 }
-                true
-            };
+            }
+            true
+        };
 
-            let mut visitor =
-                RayIntersectionsVisitor::new(&ray, self.max_time_of_impact, &mut leaf_callback);
-            query_pipeline.qbvh.traverse_depth_first(&mut visitor);
-        }
+        let mut visitor = RayIntersectionsVisitor::new(ray, max_time_of_impact, &mut leaf_callback);
+        query_pipeline.qbvh.traverse_depth_first(&mut visitor);
     }
 }
 
@@ -345,6 +367,29 @@ impl RayHits {
     }
 }
 
+/// An identifier of the feature of a collider's shape that was hit during a [ray cast](spatial_query#ray-casting)
+/// or [shape cast](spatial_query#shape-casting), for example a vertex, an edge or a face.
+///
+/// For triangle meshes, `FeatureId::Face` contains the index of the hit triangle, also when its back face
+/// was hit. Heightfields identify the hit cell or triangle, and polylines the index of the hit segment.
+///
+/// For compound shapes, the feature is the local feature of the hit sub-shape and doesn't identify
+/// the sub-shape itself. The sub-shape can be found by passing the hit point to [`Collider::compound_sub_shape_at`].
+pub type FeatureId = parry::shape::FeatureId;
+
+/// An event that is sent when a [`RayCaster`] with `hit_events` enabled starts hitting an entity.
+///
+/// The first entity is the ray caster and the second entity is the entity that was hit.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RayHitStarted(pub Entity, pub Entity);
+
+/// An event that is sent when a [`RayCaster`] with `hit_events` enabled stops hitting an entity.
+/// It is also sent for the remaining hits when the ray caster is removed or its `hit_events` are disabled.
+///
+/// The first entity is the ray caster and the second entity is the entity that was hit.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RayHitEnded(pub Entity, pub Entity);
+
 /// Data related to a hit during a [ray cast](spatial_query#ray-casting).
 #[derive(Clone, Copy, Debug)]
 pub struct RayHitData {
@@ -352,6 +397,57 @@ pub struct RayHitData {
     pub entity: Entity,
     /// How long the ray travelled, i.e. the distance between the ray origin and the point of intersection.
     pub time_of_impact: Scalar,
+    /// The point of intersection in world space.
+    pub point: Vector,
     /// The normal at the point of intersection.
     pub normal: Vector,
+    /// The [feature](FeatureId) of the collider's shape that was hit,
+    /// for example the index of the hit triangle of a triangle mesh.
+    ///
+    /// For triangle meshes, this is always the index of the hit triangle, even if the ray hit its back face.
+    pub feature: FeatureId,
+}
+
+impl RayHitData {
+    /// Creates the hit data for a ray intersection with a collider that has the given isometry.
+    pub(crate) fn new(
+        entity: Entity,
+        ray: &parry::query::Ray,
+        hit: parry::query::RayIntersection,
+        collider: &Collider,
+        isometry: &Isometry<Scalar>,
+    ) -> Self {
+        let point = ray.point_at(hit.toi).into();
+        Self {
+            entity,
+            time_of_impact: hit.toi,
+            point,
+            normal: hit.normal.into(),
+            feature: ray_hit_feature(collider, isometry, point, hit.feature),
+        }
+    }
+}
+
+/// Maps the feature reported by a ray cast on a collider to the [`FeatureId`] stored in hit data.
+pub(crate) fn ray_hit_feature(
+    collider: &Collider,
+    isometry: &Isometry<Scalar>,
+    point: Vector,
+    feature: FeatureId,
+) -> FeatureId {
+    let shape = collider.get_shape();
+
+    if shape.as_polyline().is_some() {
+        // Ray casts on polylines report the feature of the hit segment instead of the segment itself
+        return shape
+            .project_point_and_get_feature(isometry, &point.into())
+            .1;
+    }
+
+    if let (Some(trimesh), FeatureId::Face(face)) = (shape.as_trimesh(), feature) {
+        // Back faces of triangles are reported with the triangle index offset by the number of triangles
+        return FeatureId::Face(face % trimesh.indices().len() as u32);
+    }
+
+    feature
 }
@@ -1,6 +1,5 @@
 use crate::prelude::*;
 use bevy::prelude::*;
-use parry::query::details::TOICompositeShapeShapeBestFirstVisitor;
 
 /// A component used for [shape casting](spatial_query#shape-casting).
 ///
@@ -12,6 +11,10 @@ use parry::query::details::TOICompositeShapeShapeBestFirstVisitor;
 /// a local `direction`. The [`ShapeCaster`] will find each hit and add them to the [`ShapeHits`] component in
 /// the order of the time of impact.
 ///
+/// Instead of travelling along the `direction`, the shape can also move with a linear and angular velocity
+/// by setting the `mode` to [`ShapeCastMode::Nonlinear`]. This can be used for sweeping rotating shapes
+/// like swinging weapons.
+///
 /// Computing lots of hits can be expensive, especially against complex geometry, so the maximum number of hits
 /// is one by default. This can be configured through the `max_hits` property.
 ///
@@ -85,6 +88,8 @@ pub struct ShapeCaster {
     global_direction: Vector,
     /// The maximum distance the shape can travel. By default this is infinite, so the shape will travel
     /// until a hit is found.
+    ///
+    /// With [`ShapeCastMode::Nonlinear`], this is the maximum time that the shape moves for instead.
     pub max_time_of_impact: Scalar,
     /// The maximum number of hits allowed. By default this is one and only the first hit is returned.
     pub max_hits: u32,
@@ -97,6 +102,15 @@ pub struct ShapeCaster {
     pub ignore_origin_penetration: bool,
     /// Rules that determine which colliders are taken into account in the query.
     pub query_filter: SpatialQueryFilter,
+    /// Controls if [`ShapeHitStarted`] and [`ShapeHitEnded`] events are sent when the shape caster
+    /// starts or stops hitting an entity. This is false by default.
+    pub hit_events: bool,
+    /// Determines how the shape moves during the shape cast. By default the shape travels along the `direction`.
+    ///
+    /// To get the global mode, use the `global_mode` method.
+    pub mode: ShapeCastMode,
+    /// The mode with velocities in global space.
+    global_mode: ShapeCastMode,
 }
 
 impl Default for ShapeCaster {
@@ -120,6 +134,9 @@ impl Default for ShapeCaster {
             max_hits: 1,
             ignore_origin_penetration: false,
             query_filter: SpatialQueryFilter::default(),
+            hit_events: false,
+            mode: ShapeCastMode::Linear,
+            global_mode: ShapeCastMode::Linear,
         }
     }
 }
@@ -195,6 +212,19 @@ impl ShapeCaster {
         self
     }
 
+    /// Sets the [mode](ShapeCastMode) that determines how the shape moves during the shape cast.
+    pub fn with_mode(mut self, mode: ShapeCastMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets if [`ShapeHitStarted`] and [`ShapeHitEnded`] events are sent when the shape caster
+    /// starts or stops hitting an entity.
+    pub fn with_hit_events(mut self, hit_events: bool) -> Self {
+        self.hit_events = hit_events;
+        self
+    }
+
     /// Enables the [`ShapeCaster`].
     pub fn enable(&mut self) {
         self.enabled = true;
@@ -227,6 +257,11 @@ impl ShapeCaster {
         self.global_direction
     }
 
+    /// Returns the mode with velocities in global space.
+    pub fn global_mode(&self) -> ShapeCastMode {
+        self.global_mode
+    }
+
     /// Sets the global origin of the ray.
     pub(crate) fn set_global_origin(&mut self, global_origin: Vector) {
         self.global_origin = global_origin;
@@ -249,6 +284,11 @@ impl ShapeCaster {
         self.global_direction = global_direction;
     }
 
+    /// Sets the mode with velocities in global space.
+    pub(crate) fn set_global_mode(&mut self, global_mode: ShapeCastMode) {
+        self.global_mode = global_mode;
+    }
+
     pub(crate) fn cast(&self, hits: &mut ShapeHits, query_pipeline: &SpatialQueryPipeline) {
         hits.count = 0;
         let shape_rotation: Rotation;
@@ -262,31 +302,33 @@ impl ShapeCaster {
         }
 
         let shape_isometry = utils::make_isometry(self.global_origin(), &shape_rotation);
-        let shape_direction = self.global_direction().into();
 
         let mut query_filter = self.query_filter.clone();
         while hits.count < self.max_hits {
-            let pipeline_shape = query_pipeline.as_composite_shape(query_filter.clone());
-            let mut visitor = TOICompositeShapeShapeBestFirstVisitor::new(
-                &*query_pipeline.dispatcher,
-                &shape_isometry,
-                &shape_direction,
-                &pipeline_shape,
-                &**self.shape.get_shape(),
-                self.max_time_of_impact,
-                !self.ignore_origin_penetration,
-            );
-
-            if let Some(hit) = query_pipeline.qbvh.traverse_best_first(&mut visitor).map(
-                |(_, (entity_index, hit))| ShapeHitData {
-                    entity: query_pipeline.entity_from_index(entity_index),
-                    time_of_impact: hit.toi,
-                    point1: hit.witness1.into(),
-                    point2: hit.witness2.into(),
-                    normal1: hit.normal1.into(),
-                    normal2: hit.normal2.into(),
-                },
-            ) {
+            if let Some(hit) = match self.global_mode {
+                ShapeCastMode::Linear => query_pipeline.cast_shape_with_isometry(
+                    &self.shape,
+                    &shape_isometry,
+                    self.global_direction(),
+                    self.max_time_of_impact,
+                    self.ignore_origin_penetration,
+                    query_filter.clone(),
+                ),
+                ShapeCastMode::Nonlinear {
+                    linear_velocity,
+                    angular_velocity,
+                    local_center,
+                } => query_pipeline.cast_shape_nonlinear_with_isometry(
+                    &self.shape,
+                    &shape_isometry,
+                    local_center,
+                    linear_velocity,
+                    angular_velocity,
+                    self.max_time_of_impact,
+                    self.ignore_origin_penetration,
+                    query_filter.clone(),
+                ),
+            } {
                 if (hits.vector.len() as u32) < hits.count + 1 {
                     hits.vector.push(hit);
                 } else {
@@ -302,6 +344,53 @@ impl ShapeCaster {
     }
 }
 
+/// Determines how the shape of a [`ShapeCaster`] moves during a [shape cast](spatial_query#shape-casting).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShapeCastMode {
+    /// The shape travels along a straight line in the shape caster's `direction` without rotating.
+    /// The time of impact is the distance that the shape has travelled.
+    #[default]
+    Linear,
+    /// The shape moves with a constant linear and angular velocity, and the shape caster's `direction`
+    /// is ignored. The time of impact is the time at which the shape first hits a collider, and
+    /// `max_time_of_impact` is how long the shape moves for.
+    ///
+    /// The velocities are relative to the [`Rotation`] of the shape caster entity or its parent.
+    Nonlinear {
+        /// The linear velocity of the shape.
+        linear_velocity: Vector,
+        /// The angular velocity of the shape in radians per second.
+        #[cfg(feature = "2d")]
+        angular_velocity: Scalar,
+        /// The angular velocity of the shape in radians per second.
+        #[cfg(feature = "3d")]
+        angular_velocity: Vector,
+        /// The point that the shape rotates around, in the local space of the shape.
+        local_center: Vector,
+    },
+}
+
+impl ShapeCastMode {
+    /// Returns the mode with its velocities rotated by the given rotation.
+    pub(crate) fn rotated(self, rotation: &Rotation) -> Self {
+        match self {
+            Self::Linear => Self::Linear,
+            Self::Nonlinear {
+                linear_velocity,
+                angular_velocity,
+                local_center,
+            } => Self::Nonlinear {
+                linear_velocity: rotation.rotate(linear_velocity),
+                #[cfg(feature = "2d")]
+                angular_velocity,
+                #[cfg(feature = "3d")]
+                angular_velocity: rotation.rotate(angular_velocity),
+                local_center,
+            },
+        }
+    }
+}
+
 /// Contains the hits of a shape cast by a [`ShapeCaster`]. The hits are in the order of time of impact.
 ///
 /// The maximum number of hits depends on the value of `max_hits` in [`ShapeCaster`]. By default only
@@ -363,14 +452,32 @@ impl ShapeHits {
     }
 }
 
+/// An event that is sent when a [`ShapeCaster`] with `hit_events` enabled starts hitting an entity.
+///
+/// The first entity is the shape caster and the second entity is the entity that was hit.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeHitStarted(pub Entity, pub Entity);
+
+/// An event that is sent when a [`ShapeCaster`] with `hit_events` enabled stops hitting an entity.
+/// It is also sent for the remaining hits when the shape caster is removed or its `hit_events` are disabled.
+///
+/// The first entity is the shape caster and the second entity is the entity that was hit.
+#[derive(Event, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShapeHitEnded(pub Entity, pub Entity);
+
 /// Data related to a hit during a [shape cast](spatial_query#shape-casting).
 #[derive(Component, Clone, Copy, Debug)]
 pub struct ShapeHitData {
     /// The entity of the collider that was hit by the ray.
     pub entity: Entity,
-    /// How long the shape travelled before the initial hit,
-    /// i.e. the distance between the origin and the point of intersection.
+    /// How long the shape travelled before the initial hit.
+    ///
+    /// For linear shape casts, this is the distance between the origin and the point of intersection.
+    /// For [nonlinear](ShapeCastMode::Nonlinear) shape casts, this is the time in seconds
+    /// at which the shape first hit the collider.
     pub time_of_impact: Scalar,
+    /// The point of impact on the collider that was hit, in world space.
+    pub point: Vector,
     /// The closest point on the cast shape, at the time of impact,
     /// expressed in the local space of the cast shape.
     pub point1: Vector,
@@ -383,4 +490,72 @@ pub struct ShapeHitData {
     /// The outward normal on the collider that was hit by the shape cast, at the time of impact,
     /// expressed in the local space of the collider shape.
     pub normal2: Vector,
+    /// The [feature](FeatureId) of the collider's shape that was hit,
+    /// for example the index of the hit triangle of a triangle mesh.
+    ///
+    /// For triangle meshes, this is always the index of the hit triangle, even if the shape hit its back face.
+    pub feature: FeatureId,
+}
+
+impl ShapeHitData {
+    /// Creates the hit data for a shape cast hit on a collider that has the given isometry.
+    /// `point` and `normal` are the point of impact and the outward normal on the collider in world space.
+    pub(crate) fn new(
+        entity: Entity,
+        hit: &parry::query::TOI,
+        point: Vector,
+        normal: Vector,
+        collider: &Collider,
+        isometry: &Isometry<Scalar>,
+    ) -> Self {
+        Self {
+            entity,
+            time_of_impact: hit.toi,
+            point,
+            point1: hit.witness1.into(),
+            point2: hit.witness2.into(),
+            normal1: hit.normal1.into(),
+            normal2: hit.normal2.into(),
+            feature: shape_hit_feature(collider, isometry, point, normal),
+        }
+    }
+}
+
+/// How far above the point of impact the ray used for finding the hit feature starts,
+/// relative to the size of the collider or the distance of the point from the world origin,
+/// whichever is larger. This keeps the ray outside of the surface despite floating point error
+/// while staying close enough that it doesn't hit other parts of the shape.
+const FEATURE_PROBE_FRACTION: Scalar = 1.0e-4;
+
+/// Finds the feature of a collider at a world-space point of impact.
+///
+/// A short ray is cast towards the surface along the hit normal so that the feature
+/// matches the one a [ray cast](spatial_query#ray-casting) at the same point would report.
+fn shape_hit_feature(
+    collider: &Collider,
+    isometry: &Isometry<Scalar>,
+    point: Vector,
+    normal: Vector,
+) -> FeatureId {
+    let shape_radius = collider.get_shape().compute_local_bounding_sphere().radius;
+    let probe_distance = FEATURE_PROBE_FRACTION * shape_radius.max(point.length());
+    let ray = parry::query::Ray::new((point + normal * probe_distance).into(), (-normal).into());
+    match collider
+        .get_shape()
+        .cast_ray_and_get_normal(isometry, &ray, 2.0 * probe_distance, true)
+    {
+        Some(hit) => ray_hit_feature(
+            collider,
+            isometry,
+            ray.point_at(hit.toi).into(),
+            hit.feature,
+        ),
+        // The normal can be degenerate, for example when the shapes are initially penetrating
+        None => {
+            collider
+                .get_shape()
+                .project_point_and_get_feature(isometry, &point.into())
+                .1
+        }
+    }
 }
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashSet};
 
 type ColliderChangedFilter = (
     Or<(
@@ -19,7 +19,8 @@ type ColliderChangedFilter = (
 /// [`ray_hits`](SpatialQuery#method.ray_hits), [`ray_hits_callback`](SpatialQuery#method.ray_hits_callback)
 /// - [Shape casting](spatial_query#shape-casting): [`cast_shape`](SpatialQuery#method.cast_shape),
 /// [`shape_hits`](SpatialQuery#method.shape_hits), [`shape_hits_callback`](SpatialQuery#method.shape_hits_callback)
-/// - [Point projection](spatial_query#point-projection): [`project_point`](SpatialQuery#method.project_point)
+/// - [Point projection](spatial_query#point-projection): [`project_point`](SpatialQuery#method.project_point),
+/// [`nearest_colliders`](SpatialQuery#method.nearest_colliders), [`colliders_within_radius`](SpatialQuery#method.colliders_within_radius)
 /// - [Intersection tests](spatial_query#intersection-tests)
 ///     - Point intersections: [`point_intersections`](SpatialQuery#method.point_intersections),
 /// [`point_intersections_callback`](SpatialQuery#method.point_intersections_callback)
@@ -71,7 +72,7 @@ type ColliderChangedFilter = (
 /// ```
 #[derive(SystemParam)]
 pub struct SpatialQuery<'w, 's> {
-    pub(crate) colliders: Query<
+    pub(crate) changed_colliders: Query<
         'w,
         's,
         (
@@ -81,10 +82,12 @@ pub struct SpatialQuery<'w, 's> {
             &'static Collider,
             Option<&'static CollisionLayers>,
         ),
+        ColliderChangedFilter,
     >,
-    pub(crate) added_colliders: Query<'w, 's, Entity, Added<Collider>>,
-    pub(crate) changed_colliders: Query<'w, 's, Entity, ColliderChangedFilter>,
     pub(crate) removed_colliders: ResMut<'w, spatial_query::RemovedColliders>,
+    pub(crate) collider_hierarchy:
+        Query<'w, 's, (Option<&'static ColliderParent>, Option<&'static Children>)>,
+    pub(crate) joint_connections: Res<'w, spatial_query::JointConnections>,
     /// The [`SpatialQueryPipeline`].
     pub query_pipeline: ResMut<'w, SpatialQueryPipeline>,
 }
@@ -95,14 +98,22 @@ impl<'w, 's> SpatialQuery<'w, 's> {
     /// call this to make sure the data is up to date when performing spatial queries using [`SpatialQuery`].
     pub fn update_pipeline(&mut self) {
         self.query_pipeline.update_incremental(
-            self.colliders.iter(),
-            self.added_colliders.iter(),
             self.changed_colliders.iter(),
             self.removed_colliders.drain(),
             true,
         );
     }
 
+    /// Updates the poses of the changed colliders in the pipeline when the broad phase maintains
+    /// its acceleration structure, see [`BroadPhaseBackend::SpatialQueryPipeline`].
+    pub(crate) fn update_pipeline_poses(&mut self) {
+        let colliders = self
+            .changed_colliders
+            .iter()
+            .map(|(entity, position, rotation, ..)| (entity, position, rotation));
+        self.query_pipeline.update_poses(colliders);
+    }
+
     /// Casts a [ray](spatial_query#ray-casting) and computes the closest [hit](RayHitData) with a collider.
     /// If there are no hits, `None` is returned.
     ///
@@ -277,6 +288,37 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a batch of [rays](spatial_query#ray-casting) and computes the closest [hit](RayHitData)
+    /// for each of them. The results are in the same order as the requests.
+    ///
+    /// With the `parallel` feature, the rays are cast in parallel.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     let requests = [
+    ///         RayRequest::new(Vec3::ZERO, Vec3::X).with_max_time_of_impact(100.0),
+    ///         RayRequest::new(Vec3::ZERO, Vec3::NEG_X).with_max_time_of_impact(100.0),
+    ///     ];
+    ///
+    ///     // Cast rays and print the first hit of each ray
+    ///     for (request, hit) in requests.iter().zip(spatial_query.cast_rays(&requests)) {
+    ///         println!("Ray in direction {}: {:?}", request.direction, hit);
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_rays(&self, requests: &[RayRequest]) -> Vec<Option<RayHitData>> {
+        self.query_pipeline.cast_rays(requests)
+    }
+
     /// Casts a [shape](spatial_query#shape-casting) with a given rotation and computes the closest [hit](ShapeHits)
     /// with a collider. If there are no hits, `None` is returned.
     ///
@@ -341,6 +383,200 @@ impl<'w, 's> SpatialQuery<'w, 's> {
         )
     }
 
+    /// Casts a [shape](spatial_query#shape-casting) that moves with a constant linear and angular velocity
+    /// and computes the first [hit](ShapeHitData) with a collider. If there are no hits, `None` is returned.
+    ///
+    /// Unlike [`cast_shape`](Self::cast_shape), the shape rotates while it travels, and the time of impact
+    /// is the time at which the shape first hits a collider instead of the distance it has travelled.
+    /// This is useful for things like swinging weapons, rotating doors and tumbling debris.
+    ///
+    /// For a more ECS-based approach, consider using the [`ShapeCaster`] component
+    /// with [`ShapeCastMode::Nonlinear`] instead.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape at the start of the cast.
+    /// - `local_center`: The point that the shape rotates around, in the local space of the shape.
+    /// - `linear_velocity`: The linear velocity of the shape.
+    /// - `angular_velocity`: The angular velocity of the shape in radians per second.
+    /// - `max_time_of_impact`: How long the shape moves for.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating a collider at the
+    /// shape origin, the hit will be ignored and only the next hit will be computed. Otherwise, the initial
+    /// hit will be returned.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_hits(spatial_query: SpatialQuery) {
+    ///     // Swing a blade around its handle for half a second and print the first hit
+    ///     if let Some(first_hit) = spatial_query.cast_shape_nonlinear(
+    ///         &Collider::cuboid(0.1, 2.0, 0.1), // Shape
+    ///         Vec3::ZERO,                       // Origin
+    ///         Quat::default(),                  // Shape rotation
+    ///         Vec3::new(0.0, -1.0, 0.0),        // Local center of rotation
+    ///         Vec3::ZERO,                       // Linear velocity
+    ///         Vec3::new(0.0, 0.0, -6.0),        // Angular velocity
+    ///         0.5,                              // Maximum time of impact
+    ///         true,                             // Should initial penetration at the origin be ignored
+    ///         SpatialQueryFilter::default(),    // Query filter
+    ///     ) {
+    ///         println!("First hit: {:?}", first_hit);
+    ///     }
+    /// }
+    /// ```
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_nonlinear(
+        &self,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        local_center: Vector,
+        linear_velocity: Vector,
+        angular_velocity: AngularValue,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeHitData> {
+        self.query_pipeline.cast_shape_nonlinear(
+            shape,
+            origin,
+            shape_rotation,
+            local_center,
+            linear_velocity,
+            angular_velocity,
+            max_time_of_impact,
+            ignore_origin_penetration,
+            query_filter,
+        )
+    }
+
+    /// Sweeps the [colliders](Collider) of the given rigid body in the given direction and computes
+    /// the closest [hit](ShapeHitData). If there are no hits or the entity doesn't have any colliders, `None` is returned.
+    ///
+    /// The body's own collider and all of the child colliders attached to it are swept from their current positions.
+    /// The body itself and the bodies it is connected to with joints are excluded automatically,
+    /// and each collider only hits colliders that are included in its [`CollisionLayers`] masks.
+    /// Custom joints are only taken into account if [`update_joint_connections`] is registered for them.
+    ///
+    /// Colliders that the body is already penetrating are hit at a time of impact of zero.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The rigid body whose colliders are swept.
+    /// - `direction`: What direction the colliders are swept in.
+    /// - `max_time_of_impact`: The maximum distance that the colliders can travel.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn move_player(
+    ///     spatial_query: SpatialQuery,
+    ///     mut players: Query<(Entity, &mut Position), With<Player>>,
+    /// ) {
+    ///     let movement = Vec3::new(0.0, 0.0, -0.1);
+    ///
+    ///     for (entity, mut position) in &mut players {
+    ///         // Only move as far as possible without hitting anything
+    ///         let distance = spatial_query
+    ///             .cast_body(entity, movement.normalize(), movement.length())
+    ///             .map_or(movement.length(), |hit| hit.time_of_impact);
+    ///         position.0 += movement.normalize() * distance;
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_body(
+        &self,
+        entity: Entity,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+    ) -> Option<ShapeHitData> {
+        let colliders = self.body_colliders(entity);
+
+        // Exclude the body and everything that is attached or jointed to it
+        let mut excluded_entities: HashSet<Entity> = colliders.iter().copied().collect();
+        excluded_entities.insert(entity);
+        for jointed_entity in self.joint_connections.get(entity) {
+            excluded_entities.insert(jointed_entity);
+            excluded_entities.extend(self.body_colliders(jointed_entity));
+        }
+
+        colliders
+            .iter()
+            .filter_map(|collider_entity| {
+                let (isometry, collider, layers) =
+                    self.query_pipeline.colliders.get(collider_entity)?;
+                let query_filter = SpatialQueryFilter {
+                    masks: layers.masks_bits(),
+                    groups: layers.groups_bits(),
+                    excluded_entities: excluded_entities.clone(),
+                    ..default()
+                };
+                self.query_pipeline.cast_shape_with_isometry(
+                    collider,
+                    isometry,
+                    direction,
+                    max_time_of_impact,
+                    false,
+                    query_filter,
+                )
+            })
+            .min_by(|hit1, hit2| hit1.time_of_impact.total_cmp(&hit2.time_of_impact))
+    }
+
+    /// Returns the colliders attached to the given rigid body, including the body's own collider.
+    fn body_colliders(&self, body: Entity) -> Vec<Entity> {
+        let mut colliders = vec![];
+        let mut stack = vec![body];
+
+        while let Some(entity) = stack.pop() {
+            let Ok((collider_parent, children)) = self.collider_hierarchy.get(entity) else {
+                continue;
+            };
+
+            // Descendants attached to other rigid bodies don't belong to this body
+            if collider_parent.map_or(false, |parent| parent.get() != body) {
+                continue;
+            }
+
+            if self.query_pipeline.colliders.contains_key(&entity) {
+                colliders.push(entity);
+            }
+            if let Some(children) = children {
+                stack.extend(children.iter());
+            }
+        }
+
+        colliders
+    }
+
+    /// Casts a batch of [shapes](spatial_query#shape-casting) and computes the closest [hit](ShapeHitData)
+    /// for each of them. The results are in the same order as the requests.
+    ///
+    /// With the `parallel` feature, the shapes are cast in parallel.
+    pub fn cast_shapes(&self, requests: &[ShapeCastRequest]) -> Vec<Option<ShapeHitData>> {
+        self.query_pipeline.cast_shapes(requests)
+    }
+
     /// Casts a [shape](spatial_query#shape-casting) with a given rotation and computes computes all [hits](ShapeHitData)
     /// in the order of the time of impact until `max_hits` is reached.
     ///
@@ -527,6 +763,80 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             .project_point(point, solid, query_filter)
     }
 
+    /// Finds up to `k` [colliders](Collider) closest to the given point within `max_distance`,
+    /// sorted by their distance to the point. Colliders that contain the point have a distance of zero.
+    ///
+    /// ## Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `k`: The maximum number of colliders to return.
+    /// - `max_distance`: The maximum distance between the point and the colliders.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_nearest_colliders(spatial_query: SpatialQuery) {
+    ///     // Find the three closest colliders within a distance of 10
+    ///     let nearest = spatial_query.nearest_colliders(
+    ///         Vec3::ZERO,                    // Point
+    ///         3,                             // Maximum number of colliders
+    ///         10.0,                          // Maximum distance
+    ///         SpatialQueryFilter::default(), // Query filter
+    ///     );
+    ///
+    ///     for collider in nearest.iter() {
+    ///         println!("{:?} is {} units away", collider.entity, collider.distance);
+    ///     }
+    /// }
+    /// ```
+    pub fn nearest_colliders(
+        &self,
+        point: Vector,
+        k: usize,
+        max_distance: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<NearestColliderData> {
+        self.query_pipeline
+            .nearest_colliders(point, k, max_distance, query_filter)
+    }
+
+    /// Finds all [colliders](Collider) within `radius` of the given point, sorted by their distance to the point.
+    /// Colliders that contain the point have a distance of zero.
+    ///
+    /// ## Arguments
+    ///
+    /// - `point`: The point that distances are computed from.
+    /// - `radius`: The maximum distance between the point and the colliders.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    pub fn colliders_within_radius(
+        &self,
+        point: Vector,
+        radius: Scalar,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<NearestColliderData> {
+        self.query_pipeline
+            .colliders_within_radius(point, radius, query_filter)
+    }
+
+    /// Finds the [projection](spatial_query#point-projection) of each point in a batch on the closest collider.
+    /// The results are in the same order as the requests.
+    ///
+    /// With the `parallel` feature, the points are projected in parallel.
+    pub fn project_points(
+        &self,
+        requests: &[PointProjectionRequest],
+    ) -> Vec<Option<PointProjection>> {
+        self.query_pipeline.project_points(requests)
+    }
+
     /// An [intersection test](spatial_query#intersection-tests) that finds all entities with a [collider](Collider)
     /// that contains the given point.
     ///
@@ -775,4 +1085,273 @@ impl<'w, 's> SpatialQuery<'w, 's> {
             callback,
         )
     }
+
+    /// Casts a [ray](spatial_query#ray-casting) against the collider of the given entity only.
+    /// If the ray doesn't hit the collider or the entity doesn't have a collider, `None` is returned.
+    ///
+    /// This is useful for checking a single known collider without filtering out every other entity.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the ray is cast against.
+    /// - `origin`: Where the ray is cast from.
+    /// - `direction`: What direction the ray is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the ray can travel.
+    /// - `solid`: If true and the ray origin is inside of the collider, the hit point will be the ray origin itself.
+    /// Otherwise, the collider will be treated as hollow, and the hit point will be at the collider's boundary.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Door;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn print_door_hit(spatial_query: SpatialQuery, doors: Query<Entity, With<Door>>) {
+    ///     for door in &doors {
+    ///         // Cast ray against the door's collider
+    ///         if let Some(hit) = spatial_query.cast_ray_on(
+    ///             door,       // Entity
+    ///             Vec3::ZERO, // Origin
+    ///             Vec3::X,    // Direction
+    ///             100.0,      // Maximum time of impact (travel distance)
+    ///             true,       // Does the ray treat the collider as "solid"
+    ///         ) {
+    ///             println!("Door hit: {:?}", hit);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn cast_ray_on(
+        &self,
+        entity: Entity,
+        origin: Vector,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        solid: bool,
+    ) -> Option<RayHitData> {
+        self.query_pipeline
+            .cast_ray_on(entity, origin, direction, max_time_of_impact, solid)
+    }
+
+    /// Casts a [shape](spatial_query#shape-casting) with a given rotation against the collider of the given entity only.
+    /// If the shape doesn't hit the collider or the entity doesn't have a collider, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the shape is cast against.
+    /// - `shape`: The shape being cast represented as a [`Collider`].
+    /// - `origin`: Where the shape is cast from.
+    /// - `shape_rotation`: The rotation of the shape being cast.
+    /// - `direction`: What direction the shape is cast in.
+    /// - `max_time_of_impact`: The maximum distance that the shape can travel.
+    /// - `ignore_origin_penetration`: If true and the shape is already penetrating the collider at the
+    /// shape origin, the hit will be ignored. Otherwise, the initial hit will be returned.
+    #[allow(clippy::too_many_arguments)]
+    pub fn cast_shape_on(
+        &self,
+        entity: Entity,
+        shape: &Collider,
+        origin: Vector,
+        shape_rotation: RotationValue,
+        direction: Vector,
+        max_time_of_impact: Scalar,
+        ignore_origin_penetration: bool,
+    ) -> Option<ShapeHitData> {
+        self.query_pipeline.cast_shape_on(
+            entity,
+            shape,
+            origin,
+            shape_rotation,
+            direction,
+            max_time_of_impact,
+            ignore_origin_penetration,
+        )
+    }
+
+    /// Finds the [projection](spatial_query#point-projection) of a given point on the collider of the given entity only.
+    /// If the entity doesn't have a collider, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `entity`: The entity whose collider the point is projected on.
+    /// - `point`: The point that should be projected.
+    /// - `solid`: If true and the point is inside of the collider, the projection will be at the point.
+    /// Otherwise, the collider will be treated as hollow, and the projection will be at the collider's boundary.
+    pub fn project_point_on(
+        &self,
+        entity: Entity,
+        point: Vector,
+        solid: bool,
+    ) -> Option<PointProjection> {
+        self.query_pipeline.project_point_on(entity, point, solid)
+    }
+
+    /// An [intersection test](spatial_query#intersection-tests) that checks if the colliders of the two
+    /// given entities are intersecting. If either entity doesn't have a collider, false is returned.
+    pub fn intersects(&self, entity1: Entity, entity2: Entity) -> bool {
+        self.query_pipeline.intersects(entity1, entity2)
+    }
+
+    /// Computes the closest points between the colliders of the two given entities, along with the normal
+    /// and the distance between them. If the colliders are further apart than `max_distance`,
+    /// [`ClosestPointsData::Disjoint`] is returned.
+    ///
+    /// If either entity doesn't have a collider or the query isn't supported for the shapes, `None` is returned.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// #[derive(Component)]
+    /// struct ProximitySensor;
+    ///
+    /// fn print_proximity(
+    ///     spatial_query: SpatialQuery,
+    ///     player: Query<Entity, With<Player>>,
+    ///     sensors: Query<Entity, With<ProximitySensor>>,
+    /// ) {
+    ///     let Ok(player) = player.get_single() else {
+    ///         return;
+    ///     };
+    ///
+    ///     for sensor in &sensors {
+    ///         match spatial_query.closest_points(sensor, player, 5.0) {
+    ///             Some(ClosestPointsData::Intersecting) => println!("The player is at the sensor"),
+    ///             Some(ClosestPointsData::WithinMargin { distance, .. }) => {
+    ///                 println!("The player is {} units away from the sensor", distance);
+    ///             }
+    ///             _ => {}
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn closest_points(
+        &self,
+        entity1: Entity,
+        entity2: Entity,
+        max_distance: Scalar,
+    ) -> Option<ClosestPointsData> {
+        self.query_pipeline
+            .closest_points(entity1, entity2, max_distance)
+    }
+
+    /// Finds the [collider](Collider) closest to the given shape, and computes the closest points between them,
+    /// along with the normal and the distance. If there are no colliders, `None` is returned.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that distances are computed from, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    pub fn distance(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Option<ShapeDistanceData> {
+        self.query_pipeline
+            .distance(shape, shape_position, shape_rotation, query_filter)
+    }
+
+    /// Computes how the given shape penetrates each [collider](Collider) that it overlaps.
+    /// The results are sorted by entity.
+    ///
+    /// The [translation](PenetrationData::translation) of each result is the minimal translation
+    /// that moves the shape out of that collider. To move the shape out of all of the colliders at once,
+    /// use [`compute_depenetration`](SpatialQuery#method.compute_depenetration).
+    ///
+    /// This doesn't depend on the simulation, so it can be used for resolving overlaps after teleports
+    /// or for placing spawned entities without running a physics step.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that is tested for penetration, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    pub fn compute_penetration(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        query_filter: SpatialQueryFilter,
+    ) -> Vec<PenetrationData> {
+        self.query_pipeline
+            .compute_penetration(shape, shape_position, shape_rotation, query_filter)
+    }
+
+    /// Computes a single translation that moves the given shape out of all of the [colliders](Collider) that it overlaps.
+    ///
+    /// The penetrations are resolved one after another, and they are recomputed at the new position
+    /// up to `max_iterations` times, until the shape no longer overlaps any colliders. Some penetration may remain
+    /// if the shape is wedged between colliders.
+    ///
+    /// ## Arguments
+    ///
+    /// - `shape`: The shape that should be moved out of the colliders, represented as a [`Collider`].
+    /// - `shape_position`: The position of the shape.
+    /// - `shape_rotation`: The rotation of the shape.
+    /// - `max_iterations`: The maximum number of times the penetrations are recomputed.
+    /// - `query_filter`: A [`SpatialQueryFilter`] that determines which colliders are taken into account in the query.
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use bevy::prelude::*;
+    /// # #[cfg(feature = "2d")]
+    /// # use bevy_xpbd_2d::prelude::*;
+    /// # #[cfg(feature = "3d")]
+    /// use bevy_xpbd_3d::prelude::*;
+    ///
+    /// # #[cfg(all(feature = "3d", feature = "f32"))]
+    /// fn spawn_box(mut commands: Commands, spatial_query: SpatialQuery) {
+    ///     let collider = Collider::cuboid(1.0, 1.0, 1.0);
+    ///
+    ///     // Move the spawn position out of any colliders
+    ///     let position = Vec3::new(0.0, 0.5, 0.0);
+    ///     let position = position
+    ///         + spatial_query.compute_depenetration(
+    ///             &collider,                     // Shape
+    ///             position,                      // Shape position
+    ///             Quat::default(),               // Shape rotation
+    ///             4,                             // Maximum number of iterations
+    ///             SpatialQueryFilter::default(), // Query filter
+    ///         );
+    ///
+    ///     commands.spawn((RigidBody::Dynamic, collider, Position(position)));
+    /// }
+    /// ```
+    pub fn compute_depenetration(
+        &self,
+        shape: &Collider,
+        shape_position: Vector,
+        shape_rotation: RotationValue,
+        max_iterations: usize,
+        query_filter: SpatialQueryFilter,
+    ) -> Vector {
+        self.query_pipeline.compute_depenetration(
+            shape,
+            shape_position,
+            shape_rotation,
+            max_iterations,
+            query_filter,
+        )
+    }
 }
@@ -208,7 +208,11 @@ type PosToTransformComponents = (
     Option<&'static Parent>,
 );
 
-type PosToTransformFilter = Or<(Changed<Position>, Changed<Rotation>)>;
+type PosToTransformFilter = (
+    Or<(Changed<Position>, Changed<Rotation>)>,
+    // Colliders attached to ancestor bodies are moved by the `Transform` hierarchy
+    Or<(With<RigidBody>, Without<ColliderParent>)>,
+);
 
 type ParentComponents = (
     &'static GlobalTransform,
@@ -220,7 +224,11 @@ type ParentComponents = (
 /// This allows users and the engine to use these components for moving and positioning bodies.
 ///
 /// Nested rigid bodies move independently of each other, so the `Transform`s of child entities are updated
-/// based on their own and their parent's [`Position`] and [`Rotation`].
+/// based on their own and their parent's [`Position`] and [`Rotation`]. The translation of a child is given
+/// in the scaled space of its parent, and the scale of the `Transform` is kept.
+///
+/// Colliders attached to ancestor bodies are skipped, because they already follow their body
+/// through the `Transform` hierarchy.
 #[cfg(feature = "2d")]
 fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
@@ -246,7 +254,8 @@ fn position_to_transform(
                 // computed from the its global transform and its parents global transform
                 let new_transform = GlobalTransform::from(
                     Transform::from_translation(pos.as_f32().extend(transform.translation.z))
-                        .with_rotation(Quaternion::from(*rot).as_f32()),
+                        .with_rotation(Quaternion::from(*rot).as_f32())
+                        .with_scale(parent_scale * transform.scale),
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
@@ -264,7 +273,11 @@ fn position_to_transform(
 /// This allows users and the engine to use these components for moving and positioning bodies.
 ///
 /// Nested rigid bodies move independently of each other, so the `Transform`s of child entities are updated
-/// based on their own and their parent's [`Position`] and [`Rotation`].
+/// based on their own and their parent's [`Position`] and [`Rotation`]. The translation of a child is given
+/// in the scaled space of its parent, and the scale of the `Transform` is kept.
+///
+/// Colliders attached to ancestor bodies are skipped, because they already follow their body
+/// through the `Transform` hierarchy.
 #[cfg(feature = "3d")]
 fn position_to_transform(
     mut query: Query<PosToTransformComponents, PosToTransformFilter>,
@@ -286,7 +299,9 @@ fn position_to_transform(
                 // The new local transform of the child body,
                 // computed from the its global transform and its parents global transform
                 let new_transform = GlobalTransform::from(
-                    Transform::from_translation(pos.as_f32()).with_rotation(rot.as_f32()),
+                    Transform::from_translation(pos.as_f32())
+                        .with_rotation(rot.as_f32())
+                        .with_scale(parent_scale * transform.scale),
                 )
                 .reparented_to(&GlobalTransform::from(parent_transform));
 
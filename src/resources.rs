@@ -1,6 +1,6 @@
 //! Resources used in the simulation.
 
-use bevy::prelude::Resource;
+use bevy::{prelude::Resource, utils::HashSet};
 
 use crate::prelude::*;
 
@@ -150,10 +150,22 @@ impl Default for IterationCount {
 }
 
 /// A list of entity pairs for potential collisions collected during the broad phase.
+///
+/// Pairs that persist across frames keep their position and entity order in the list,
+/// and new pairs are added to the end. See [`BroadPhasePairStarted`] and [`BroadPhasePairEnded`].
 #[derive(Reflect, Resource, Default, Debug)]
 #[reflect(Resource)]
 pub struct BroadCollisionPairs(pub Vec<(Entity, Entity)>);
 
+/// A set of [broad phase collision pairs](BroadCollisionPairs) that have been flagged as sensor-only
+/// by a [`CollisionPairFilter`] during the current frame.
+///
+/// Collisions between the entities of these pairs are detected and reported normally,
+/// but the [solver] doesn't resolve them, just like collisions with [sensors](Sensor).
+#[derive(Reflect, Resource, Default, Debug)]
+#[reflect(Resource)]
+pub struct SensorCollisionPairs(pub HashSet<(Entity, Entity)>);
+
 /// A threshold that indicates the maximum linear and angular velocity allowed for a body to be deactivated.
 ///
 /// Setting a negative sleeping threshold disables sleeping entirely.
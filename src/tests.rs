@@ -1,6 +1,8 @@
 use crate::prelude::*;
 use approx::assert_relative_eq;
-use bevy::{log::LogPlugin, prelude::*, time::TimeUpdateStrategy, utils::Instant};
+use bevy::{
+    ecs::system::SystemState, log::LogPlugin, prelude::*, time::TimeUpdateStrategy, utils::Instant,
+};
 #[cfg(feature = "enhanced-determinism")]
 use insta::assert_debug_snapshot;
 use std::time::Duration;
@@ -190,3 +192,2312 @@ fn cubes_simulation_is_locally_deterministic() {
         assert_eq!(a, b);
     }
 }
+
+#[test]
+fn fast_body_with_swept_ccd_does_not_tunnel_through_thin_wall() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // move right at 600 units per second, which is much more than the
+        // thickness of the wall and the bullet per substep
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_X * 5.4),
+            Collider::ball(0.1),
+            LinearVelocity(Vector::X * 600.0),
+            SweptCcd,
+            Id(0),
+        ));
+
+        // thin wall at the origin
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(0.2, 8.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(0.2, 8.0, 8.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Position)>();
+
+    let (_, bullet_position) = app_query.single(&app.world);
+
+    assert!(bullet_position.x < 0.0, "bullet tunnelled through the wall");
+}
+
+#[test]
+fn fast_body_with_child_collider_and_swept_ccd_does_not_tunnel_through_thin_wall() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // the only collider of the bullet is on a child entity
+        commands
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::NEG_X * 5.4),
+                LinearVelocity(Vector::X * 600.0),
+                SweptCcd,
+                Id(0),
+            ))
+            .with_children(|children| {
+                children.spawn((SpatialBundle::default(), Collider::ball(0.1)));
+            });
+
+        // thin wall at the origin
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(0.2, 8.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(0.2, 8.0, 8.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Position)>();
+
+    let (_, bullet_position) = app_query.single(&app.world);
+
+    assert!(bullet_position.x < 0.0, "bullet tunnelled through the wall");
+}
+
+#[test]
+fn contacts_can_be_removed_in_post_process_collisions() {
+    let mut app = create_app();
+
+    app.get_schedule_mut(SubstepSchedule)
+        .expect("add SubstepSchedule first")
+        .add_systems(
+            (|mut collisions: ResMut<Collisions>| collisions.retain(|_| false))
+                .in_set(SubstepSet::PostProcessCollisions),
+        );
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // ball resting on the ground
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y * 1.0),
+            Collider::ball(0.5),
+            Id(0),
+        ));
+
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Position)>();
+
+    let (_, ball_position) = app_query.single(&app.world);
+
+    assert!(
+        ball_position.y < -1.0,
+        "ball didn't fall through the ground"
+    );
+}
+
+#[test]
+fn collision_pair_filter_can_flag_pairs_as_sensors() {
+    use bevy::ecs::system::SystemParamItem;
+
+    #[derive(Component)]
+    struct Ghost;
+
+    struct GhostFilter;
+
+    impl CollisionPairFilter for GhostFilter {
+        type Param = Query<'static, 'static, (), With<Ghost>>;
+
+        fn filter(
+            ghosts: &SystemParamItem<Self::Param>,
+            entity1: Entity,
+            entity2: Entity,
+        ) -> PairFilterResult {
+            if ghosts.contains(entity1) || ghosts.contains(entity2) {
+                PairFilterResult::Sensor
+            } else {
+                PairFilterResult::Collide
+            }
+        }
+    }
+
+    let mut app = create_app();
+
+    app.add_plugins(CollisionPairFilterPlugin::<GhostFilter>::default());
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // ghost ball that passes through the ground
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::NEG_X * 2.0 + Vector::Y),
+            Collider::ball(0.5),
+            Ghost,
+            Id(0),
+        ));
+
+        // normal ball that stays on the ground
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 2.0 + Vector::Y),
+            Collider::ball(0.5),
+            Id(1),
+        ));
+
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut app_query = app.world.query::<(&Id, &Position)>();
+
+    for (id, position) in app_query.iter(&app.world) {
+        if id.0 == 0 {
+            assert!(position.y < -1.0, "ghost didn't pass through the ground");
+        } else {
+            assert!(position.y > 0.0, "ball fell through the ground");
+        }
+    }
+}
+
+#[test]
+fn child_colliders_are_attached_to_ancestor_body() {
+    let mut app = create_app();
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // dynamic body with two ball colliders on child entities
+        commands
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::Y * 2.0),
+                Id(0),
+            ))
+            .with_children(|children| {
+                for x in [-1.0, 1.0] {
+                    children.spawn((
+                        SpatialBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                        Collider::ball(0.5),
+                    ));
+                }
+            });
+
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let mut body_query = app.world.query::<(&Id, &Position, &Mass, &CenterOfMass)>();
+    let (_, body_position, mass, center_of_mass) = body_query.single(&app.world);
+
+    let ball_mass = ColliderMassProperties::new_computed(&Collider::ball(0.5), 1.0)
+        .mass
+        .0;
+    assert_relative_eq!(mass.0, 2.0 * ball_mass, epsilon = 0.0001);
+    assert!(center_of_mass.length() < 0.0001);
+    assert!(body_position.y > 0.5, "body fell through the ground");
+
+    // collisions are reported for the child colliders instead of the body
+    let mut child_query = app
+        .world
+        .query_filtered::<Entity, (With<ColliderParent>, With<Parent>)>();
+    let children: Vec<Entity> = child_query.iter(&app.world).collect();
+    assert_eq!(children.len(), 2);
+
+    let mut ground_query = app
+        .world
+        .query_filtered::<&CollidingEntities, Without<Parent>>();
+    let colliding_entities = ground_query.single(&app.world);
+    assert!(!colliding_entities.is_empty());
+    assert!(colliding_entities
+        .iter()
+        .all(|entity| children.contains(entity)));
+}
+
+#[test]
+fn removed_child_colliders_are_removed_from_body_mass() {
+    let mut app = create_app();
+
+    // The first update has no time step
+    tick_60_fps(&mut app);
+
+    let body = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Dynamic))
+        .id();
+    let children = [-1.0, 1.0].map(|x| {
+        app.world
+            .spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+                Collider::ball(0.5),
+            ))
+            .set_parent(body)
+            .id()
+    });
+
+    // Colliders are attached on their first frame
+    tick_60_fps(&mut app);
+
+    let ball_mass = ColliderMassProperties::new_computed(&Collider::ball(0.5), 1.0)
+        .mass
+        .0;
+    let mass = |app: &App| app.world.get::<Mass>(body).unwrap().0;
+    let center_of_mass = |app: &App| app.world.get::<CenterOfMass>(body).unwrap().0;
+    assert_relative_eq!(mass(&app), 2.0 * ball_mass, epsilon = 0.0001);
+    let body_position = app.world.get::<Position>(body).unwrap().0;
+    let child_position = app.world.get::<Position>(children[1]).unwrap().0;
+    assert_relative_eq!(child_position.x, body_position.x + 1.0, epsilon = 0.0001);
+
+    // Despawning a child collider removes its mass from the body
+    app.world.despawn(children[1]);
+    tick_60_fps(&mut app);
+
+    assert_relative_eq!(mass(&app), ball_mass, epsilon = 0.0001);
+    assert_relative_eq!(center_of_mass(&app).x, -1.0, epsilon = 0.0001);
+
+    // So does removing the collider and detaching it from the body
+    app.world.entity_mut(children[0]).remove::<Collider>();
+    tick_60_fps(&mut app);
+
+    assert!(mass(&app).abs() < 0.0001);
+    assert!(app.world.get::<ColliderParent>(children[0]).is_none());
+}
+
+#[test]
+fn child_collider_is_attached_to_body_added_to_its_parent() {
+    let mut app = create_app();
+
+    // The first update has no time step
+    tick_60_fps(&mut app);
+
+    let parent = app.world.spawn(SpatialBundle::default()).id();
+    let collider = app
+        .world
+        .spawn((SpatialBundle::default(), Collider::ball(0.5)))
+        .set_parent(parent)
+        .id();
+
+    tick_60_fps(&mut app);
+
+    assert!(app.world.get::<ColliderParent>(collider).is_none());
+
+    app.world.entity_mut(parent).insert(RigidBody::Dynamic);
+    tick_60_fps(&mut app);
+
+    let collider_parent = app.world.get::<ColliderParent>(collider).unwrap();
+    assert_eq!(collider_parent.get(), parent);
+}
+
+#[test]
+fn child_collider_is_attached_to_next_body_when_body_is_removed_from_its_parent() {
+    let mut app = create_app();
+
+    // The first update has no time step
+    tick_60_fps(&mut app);
+
+    let outer_body = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Dynamic))
+        .id();
+    let inner_body = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Dynamic))
+        .set_parent(outer_body)
+        .id();
+    let collider = app
+        .world
+        .spawn((SpatialBundle::default(), Collider::ball(0.5)))
+        .set_parent(inner_body)
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let collider_parent = app.world.get::<ColliderParent>(collider).unwrap();
+    assert_eq!(collider_parent.get(), inner_body);
+
+    app.world.entity_mut(inner_body).remove::<RigidBody>();
+    tick_60_fps(&mut app);
+
+    let collider_parent = app.world.get::<ColliderParent>(collider).unwrap();
+    assert_eq!(collider_parent.get(), outer_body);
+}
+
+#[test]
+fn descendant_collider_is_moved_to_new_body_when_its_ancestor_is_reparented() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    // The first update has no time step
+    tick_60_fps(&mut app);
+
+    let bodies = [0, 1].map(|_| {
+        app.world
+            .spawn((SpatialBundle::default(), RigidBody::Dynamic))
+            .id()
+    });
+    let ancestor = app
+        .world
+        .spawn(SpatialBundle::default())
+        .set_parent(bodies[0])
+        .id();
+    let collider = app
+        .world
+        .spawn((SpatialBundle::default(), Collider::ball(0.5)))
+        .set_parent(ancestor)
+        .id();
+
+    tick_60_fps(&mut app);
+
+    let ball_mass = ColliderMassProperties::new_computed(&Collider::ball(0.5), 1.0)
+        .mass
+        .0;
+    let mass = |app: &App, body: Entity| app.world.get::<Mass>(body).unwrap().0;
+    assert_relative_eq!(mass(&app, bodies[0]), ball_mass, epsilon = 0.0001);
+
+    // Only the ancestor of the collider is moved to the other body
+    app.world.entity_mut(ancestor).set_parent(bodies[1]);
+    tick_60_fps(&mut app);
+
+    let collider_parent = app.world.get::<ColliderParent>(collider).unwrap();
+    assert_eq!(collider_parent.get(), bodies[1]);
+    assert!(mass(&app, bodies[0]).abs() < 0.0001);
+    assert_relative_eq!(mass(&app, bodies[1]), ball_mass, epsilon = 0.0001);
+}
+
+#[test]
+fn collider_is_scaled_by_transform_scale() {
+    let mut app = create_app();
+
+    app.insert_resource(Gravity::ZERO);
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        commands.spawn((
+            SpatialBundle::from_transform(Transform::from_scale(Vec3::splat(2.0))),
+            RigidBody::Dynamic,
+            Collider::ball(0.5),
+            Id(0),
+        ));
+    });
+
+    tick_60_fps(&mut app);
+
+    let mut app_query = app.world.query::<(&Id, &Collider, &Mass)>();
+    let (_, collider, mass) = app_query.single(&app.world);
+
+    let expected_mass = ColliderMassProperties::new_computed(&Collider::ball(1.0), 1.0)
+        .mass
+        .0;
+    assert_eq!(collider.scale(), Vector::splat(2.0));
+    assert_relative_eq!(mass.0, expected_mass, epsilon = 0.0001);
+
+    // rescaling the transform non-uniformly rescales the collider
+    let mut transform_query = app.world.query::<&mut Transform>();
+    transform_query.single_mut(&mut app.world).scale = Vec3::new(2.0, 1.0, 1.0);
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let (_, collider, mass) = app_query.single(&app.world);
+    #[cfg(feature = "2d")]
+    assert_eq!(collider.scale(), Vector::new(2.0, 1.0));
+    #[cfg(feature = "3d")]
+    assert_eq!(collider.scale(), Vector::new(2.0, 1.0, 1.0));
+    assert!(mass.0 < expected_mass);
+}
+
+#[test]
+fn child_collider_offsets_are_scaled_with_body() {
+    let mut app = create_app();
+
+    // The first update has no time step
+    tick_60_fps(&mut app);
+
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_scale(Vec3::splat(2.0))),
+            RigidBody::Static,
+        ))
+        .id();
+    let child = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+            Collider::ball(0.5),
+        ))
+        .set_parent(body)
+        .id();
+
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+
+    // The offset and the shape are both scaled, and the local transform is kept
+    let child_position = app.world.get::<Position>(child).unwrap().0;
+    assert_relative_eq!(child_position.x, 2.0, epsilon = 0.0001);
+    let collider = app.world.get::<Collider>(child).unwrap();
+    assert_eq!(collider.scale(), Vector::splat(2.0));
+    let child_transform = app.world.get::<Transform>(child).unwrap();
+    assert_relative_eq!(child_transform.translation.x, 1.0, epsilon = 0.0001);
+
+    // Shapes modified through the deprecated mutable accessor are rescaled
+    #[allow(deprecated)]
+    {
+        *app.world
+            .get_mut::<Collider>(child)
+            .unwrap()
+            .get_shape_mut() = parry::shape::SharedShape::ball(1.0);
+    }
+    tick_60_fps(&mut app);
+
+    let collider = app.world.get::<Collider>(child).unwrap();
+    assert_relative_eq!(
+        collider.get_shape().as_ball().unwrap().radius,
+        2.0,
+        epsilon = 0.0001
+    );
+}
+
+#[test]
+fn broad_phase_backends_find_same_pairs() {
+    fn run_with_backend(backend: BroadPhaseBackend) -> Vec<(Id, Id)> {
+        let mut app = create_app();
+
+        app.insert_resource(BroadPhaseConfig { backend });
+
+        app.add_systems(Startup, |mut commands: Commands| {
+            let mut next_id = 0;
+
+            // stacks of overlapping balls resting on a large static floor
+            for x in 0..4 {
+                for y in 0..8 {
+                    commands.spawn((
+                        SpatialBundle::default(),
+                        RigidBody::Dynamic,
+                        Position(Vector::X * x as Scalar * 0.9 + Vector::Y * y as Scalar * 0.9),
+                        Collider::ball(0.5),
+                        Id(next_id),
+                    ));
+                    next_id += 1;
+                }
+            }
+
+            commands.spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(Vector::NEG_Y),
+                #[cfg(feature = "2d")]
+                Collider::cuboid(100.0, 1.0),
+                #[cfg(feature = "3d")]
+                Collider::cuboid(100.0, 1.0, 100.0),
+                Id(next_id),
+            ));
+        });
+
+        tick_60_fps(&mut app);
+        tick_60_fps(&mut app);
+
+        let mut ids = app.world.query::<&Id>();
+        let mut pairs: Vec<(Id, Id)> = app
+            .world
+            .resource::<BroadCollisionPairs>()
+            .0
+            .iter()
+            .map(|(entity1, entity2)| {
+                let id1 = *ids.get(&app.world, *entity1).unwrap();
+                let id2 = *ids.get(&app.world, *entity2).unwrap();
+                (id1.min(id2), id1.max(id2))
+            })
+            .collect();
+        pairs.sort();
+        pairs
+    }
+
+    let sweep_and_prune_pairs = run_with_backend(BroadPhaseBackend::SweepAndPrune);
+    assert!(!sweep_and_prune_pairs.is_empty());
+    assert_eq!(
+        run_with_backend(BroadPhaseBackend::DynamicAabbTree),
+        sweep_and_prune_pairs
+    );
+    assert_eq!(
+        run_with_backend(BroadPhaseBackend::UniformGrid { cell_size: 1.0 }),
+        sweep_and_prune_pairs
+    );
+    // Invalid cell sizes fall back to sweep and prune
+    assert_eq!(
+        run_with_backend(BroadPhaseBackend::UniformGrid { cell_size: 0.0 }),
+        sweep_and_prune_pairs
+    );
+    assert_eq!(
+        run_with_backend(BroadPhaseBackend::UniformGrid {
+            cell_size: Scalar::NAN
+        }),
+        sweep_and_prune_pairs
+    );
+    assert_eq!(
+        run_with_backend(BroadPhaseBackend::SpatialQueryPipeline),
+        sweep_and_prune_pairs
+    );
+}
+
+#[test]
+fn spatial_query_pipeline_updates_changed_colliders() {
+    let mut app = create_app();
+
+    let spawn_ball = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(position),
+                Collider::ball(0.5),
+            ))
+            .id()
+    };
+    let entity1 = spawn_ball(&mut app, Vector::X * 5.0);
+    let entity2 = spawn_ball(&mut app, Vector::NEG_X * 5.0);
+
+    tick_60_fps(&mut app);
+
+    let cast_ray = |app: &App, direction: Vector| {
+        app.world.resource::<SpatialQueryPipeline>().cast_ray(
+            Vector::ZERO,
+            direction,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+    };
+
+    // Only the moved collider is updated, and the other one is kept as it is
+    app.world
+        .entity_mut(entity1)
+        .insert(Position(Vector::X * 8.0));
+    tick_60_fps(&mut app);
+
+    let hit = cast_ray(&app, Vector::X).expect("ray should hit the moved collider");
+    assert_eq!(hit.entity, entity1);
+    assert_relative_eq!(hit.time_of_impact, 7.5, epsilon = 0.001);
+
+    let hit = cast_ray(&app, Vector::NEG_X).expect("ray should hit the unchanged collider");
+    assert_eq!(hit.entity, entity2);
+    assert_relative_eq!(hit.time_of_impact, 4.5, epsilon = 0.001);
+
+    // Removed colliders are removed from the pipeline
+    app.world.despawn(entity2);
+    tick_60_fps(&mut app);
+
+    assert!(cast_ray(&app, Vector::NEG_X).is_none());
+    assert!(cast_ray(&app, Vector::X).is_some());
+}
+
+#[test]
+fn batched_spatial_queries_match_single_queries() {
+    let mut app = create_app();
+
+    for i in 0..8 {
+        app.world.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * (i as Scalar * 3.0 + 2.0) + Vector::Y * (i % 3) as Scalar),
+            Collider::ball(0.5 + 0.1 * i as Scalar),
+        ));
+    }
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    let ray_requests: Vec<RayRequest> = (0..100)
+        .map(|i| {
+            RayRequest::new(Vector::Y * (i % 7) as Scalar * 0.5, Vector::X)
+                .with_max_time_of_impact(i as Scalar)
+        })
+        .collect();
+    let ray_hits: Vec<_> = pipeline
+        .cast_rays(&ray_requests)
+        .into_iter()
+        .map(|hit| hit.map(|hit| (hit.entity, hit.time_of_impact)))
+        .collect();
+    let single_ray_hits: Vec<_> = ray_requests
+        .iter()
+        .map(|request| {
+            pipeline
+                .cast_ray(
+                    request.origin,
+                    request.direction,
+                    request.max_time_of_impact,
+                    request.solid,
+                    request.query_filter.clone(),
+                )
+                .map(|hit| (hit.entity, hit.time_of_impact))
+        })
+        .collect();
+    assert!(ray_hits.iter().any(Option::is_some));
+    assert_eq!(ray_hits, single_ray_hits);
+
+    let point_requests: Vec<PointProjectionRequest> = (0..100)
+        .map(|i| PointProjectionRequest::new(Vector::X * i as Scalar * 0.3))
+        .collect();
+    let projections = pipeline.project_points(&point_requests);
+    let single_projections: Vec<_> = point_requests
+        .iter()
+        .map(|request| {
+            pipeline.project_point(request.point, request.solid, request.query_filter.clone())
+        })
+        .collect();
+    assert_eq!(projections, single_projections);
+}
+
+#[test]
+fn nearest_colliders_are_sorted_by_distance() {
+    let mut app = create_app();
+
+    // Balls at distances 1.5, 2.5, ... from the origin
+    let entities: Vec<Entity> = (0..6)
+        .map(|i| {
+            app.world
+                .spawn((
+                    SpatialBundle::default(),
+                    RigidBody::Static,
+                    Position(Vector::X * (i as Scalar + 2.0) * if i % 2 == 0 { 1.0 } else { -1.0 }),
+                    Collider::ball(0.5),
+                ))
+                .id()
+        })
+        .collect();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    let nearest =
+        pipeline.nearest_colliders(Vector::ZERO, 3, Scalar::MAX, SpatialQueryFilter::default());
+    assert_eq!(
+        nearest.iter().map(|data| data.entity).collect::<Vec<_>>(),
+        entities[0..3]
+    );
+    assert_relative_eq!(nearest[0].distance, 1.5, epsilon = 0.001);
+    assert_relative_eq!(nearest[2].distance, 3.5, epsilon = 0.001);
+    assert_relative_eq!(nearest[1].point.x, -2.5, epsilon = 0.001);
+
+    let within_radius =
+        pipeline.colliders_within_radius(Vector::ZERO, 4.0, SpatialQueryFilter::default());
+    assert_eq!(
+        within_radius
+            .iter()
+            .map(|data| data.entity)
+            .collect::<Vec<_>>(),
+        entities[0..3]
+    );
+
+    let filtered = pipeline.nearest_colliders(
+        Vector::ZERO,
+        1,
+        Scalar::MAX,
+        SpatialQueryFilter::default().without_entities([entities[0]]),
+    );
+    assert_eq!(filtered.len(), 1);
+    assert_eq!(filtered[0].entity, entities[1]);
+}
+
+#[test]
+fn entity_targeted_spatial_queries() {
+    let mut app = create_app();
+
+    let spawn_ball = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(position),
+                Collider::ball(0.5),
+            ))
+            .id()
+    };
+    let near = spawn_ball(&mut app, Vector::X * 2.0);
+    let far = spawn_ball(&mut app, Vector::X * 5.0);
+    let overlapping = spawn_ball(&mut app, Vector::X * 5.5);
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    // The ray hits the targeted collider even though another collider is in the way
+    let hit = pipeline
+        .cast_ray_on(far, Vector::ZERO, Vector::X, 100.0, true)
+        .expect("ray should hit the targeted collider");
+    assert_eq!(hit.entity, far);
+    assert_relative_eq!(hit.time_of_impact, 4.5, epsilon = 0.001);
+    assert!(pipeline
+        .cast_ray_on(far, Vector::ZERO, Vector::NEG_X, 100.0, true)
+        .is_none());
+
+    #[cfg(feature = "2d")]
+    let shape_rotation = 0.0;
+    #[cfg(feature = "3d")]
+    let shape_rotation = Quaternion::IDENTITY;
+    let hit = pipeline
+        .cast_shape_on(
+            far,
+            &Collider::ball(0.5),
+            Vector::ZERO,
+            shape_rotation,
+            Vector::X,
+            100.0,
+            false,
+        )
+        .expect("shape should hit the targeted collider");
+    assert_eq!(hit.entity, far);
+    assert_relative_eq!(hit.time_of_impact, 4.0, epsilon = 0.001);
+
+    let projection = pipeline
+        .project_point_on(far, Vector::ZERO, true)
+        .expect("entity should have a collider");
+    assert_eq!(projection.entity, far);
+    assert_relative_eq!(projection.point.x, 4.5, epsilon = 0.001);
+    assert!(!projection.is_inside);
+
+    assert!(pipeline.intersects(far, overlapping));
+    assert!(!pipeline.intersects(near, far));
+}
+
+#[test]
+fn closest_points_and_distance_between_colliders() {
+    let mut app = create_app();
+
+    let spawn_ball = |app: &mut App, position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Static,
+                Position(position),
+                Collider::ball(0.5),
+            ))
+            .id()
+    };
+    let ball1 = spawn_ball(&mut app, Vector::ZERO);
+    let ball2 = spawn_ball(&mut app, Vector::X * 3.0);
+    let ball3 = spawn_ball(&mut app, Vector::X * 3.5);
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    let Some(ClosestPointsData::WithinMargin {
+        point1,
+        point2,
+        normal,
+        distance,
+    }) = pipeline.closest_points(ball1, ball2, 5.0)
+    else {
+        panic!("balls should be within the maximum distance");
+    };
+    assert_relative_eq!(point1.x, 0.5, epsilon = 0.001);
+    assert_relative_eq!(point2.x, 2.5, epsilon = 0.001);
+    assert_relative_eq!(normal.x, 1.0, epsilon = 0.001);
+    assert_relative_eq!(distance, 2.0, epsilon = 0.001);
+
+    assert_eq!(
+        pipeline.closest_points(ball1, ball2, 1.0),
+        Some(ClosestPointsData::Disjoint)
+    );
+    assert_eq!(
+        pipeline.closest_points(ball2, ball3, 1.0),
+        Some(ClosestPointsData::Intersecting)
+    );
+
+    #[cfg(feature = "2d")]
+    let shape_rotation = 0.0;
+    #[cfg(feature = "3d")]
+    let shape_rotation = Quaternion::IDENTITY;
+    let closest = pipeline
+        .distance(
+            &Collider::ball(0.5),
+            Vector::X * 6.0,
+            shape_rotation,
+            SpatialQueryFilter::default(),
+        )
+        .expect("there should be a closest collider");
+    assert_eq!(closest.entity, ball3);
+    assert_relative_eq!(
+        closest.closest_points.distance().unwrap(),
+        1.5,
+        epsilon = 0.001
+    );
+
+    // The closest collider is found regardless of how far away it is
+    let closest = pipeline
+        .distance(
+            &Collider::ball(0.5),
+            Vector::X * 1000.0,
+            shape_rotation,
+            SpatialQueryFilter::default(),
+        )
+        .expect("there should be a closest collider");
+    assert_eq!(closest.entity, ball3);
+    assert!(closest.closest_points.distance().unwrap() > 990.0);
+}
+
+#[test]
+fn shape_penetration_is_resolved() {
+    let mut app = create_app();
+
+    #[cfg(feature = "2d")]
+    let floor_collider = Collider::cuboid(10.0, 1.0);
+    #[cfg(feature = "3d")]
+    let floor_collider = Collider::cuboid(10.0, 1.0, 10.0);
+    #[cfg(feature = "2d")]
+    let wall_collider = Collider::cuboid(1.0, 10.0);
+    #[cfg(feature = "3d")]
+    let wall_collider = Collider::cuboid(1.0, 10.0, 10.0);
+
+    // A floor with its top at y = 0 and a wall with its side at x = 0
+    let floor = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 0.5),
+            floor_collider,
+        ))
+        .id();
+    let wall = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::NEG_X * 0.5),
+            wall_collider,
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+
+    #[cfg(feature = "2d")]
+    let shape_rotation = 0.0;
+    #[cfg(feature = "3d")]
+    let shape_rotation = Quaternion::IDENTITY;
+    let shape = Collider::ball(0.5);
+
+    // The ball penetrates the floor by 0.2 and the wall by 0.1
+    let position = Vector::X * 0.4 + Vector::Y * 0.3;
+    let penetrations = pipeline.compute_penetration(
+        &shape,
+        position,
+        shape_rotation,
+        SpatialQueryFilter::default(),
+    );
+    assert_eq!(penetrations.len(), 2);
+
+    let floor_penetration = penetrations.iter().find(|p| p.entity == floor).unwrap();
+    assert_relative_eq!(floor_penetration.depth, 0.2, epsilon = 0.001);
+    assert_relative_eq!(floor_penetration.translation().y, 0.2, epsilon = 0.001);
+
+    let wall_penetration = penetrations.iter().find(|p| p.entity == wall).unwrap();
+    assert_relative_eq!(wall_penetration.depth, 0.1, epsilon = 0.001);
+    assert_relative_eq!(wall_penetration.translation().x, 0.1, epsilon = 0.001);
+
+    // After depenetration, the ball no longer overlaps anything
+    let translation = pipeline.compute_depenetration(
+        &shape,
+        position,
+        shape_rotation,
+        4,
+        SpatialQueryFilter::default(),
+    );
+    assert!(pipeline
+        .compute_penetration(
+            &shape,
+            position + translation * 1.001,
+            shape_rotation,
+            SpatialQueryFilter::default(),
+        )
+        .is_empty());
+}
+
+#[test]
+fn cast_body_sweeps_attached_colliders_and_ignores_jointed_bodies() {
+    let mut app = create_app();
+
+    // A kinematic body with its own collider and a child collider in front of it
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Kinematic,
+            Collider::ball(0.5),
+        ))
+        .with_children(|children| {
+            children.spawn((
+                SpatialBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0)),
+                Collider::ball(0.5),
+            ));
+        })
+        .id();
+
+    // A body jointed to the first body, blocking the way
+    let jointed_body = app
+        .world
+        .spawn((
+            SpatialBundle::from_transform(Transform::from_xyz(3.0, 0.0, 0.0)),
+            RigidBody::Kinematic,
+            Position(Vector::X * 3.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+    let joint = app.world.spawn(FixedJoint::new(body, jointed_body)).id();
+
+    let wall = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 6.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let mut system_state: SystemState<SpatialQuery> = SystemState::new(&mut app.world);
+    let spatial_query = system_state.get_mut(&mut app.world);
+
+    // The child collider hits the wall first
+    let hit = spatial_query
+        .cast_body(body, Vector::X, 100.0)
+        .expect("body should hit the wall");
+    assert_eq!(hit.entity, wall);
+    assert_relative_eq!(hit.time_of_impact, 4.0, epsilon = 0.001);
+
+    assert!(spatial_query
+        .cast_body(body, Vector::NEG_X, 100.0)
+        .is_none());
+
+    // Coupling joints also connect the bodies
+    app.world
+        .entity_mut(joint)
+        .remove::<FixedJoint>()
+        .insert(GearJoint::new(body, jointed_body));
+    tick_60_fps(&mut app);
+
+    let spatial_query = system_state.get_mut(&mut app.world);
+    let hit = spatial_query
+        .cast_body(body, Vector::X, 100.0)
+        .expect("body should hit the wall");
+    assert_eq!(hit.entity, wall);
+
+    // The connection is removed with the joint, even if physics doesn't run on the frame it is despawned
+    app.world.resource_mut::<PhysicsLoop>().pause();
+    app.world.despawn(joint);
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+    app.world.resource_mut::<PhysicsLoop>().resume();
+    tick_60_fps(&mut app);
+
+    let spatial_query = system_state.get_mut(&mut app.world);
+    let hit = spatial_query
+        .cast_body(body, Vector::X, 100.0)
+        .expect("body should hit the other body");
+    assert_eq!(hit.entity, jointed_body);
+}
+
+#[test]
+fn hits_contain_world_points_and_features() {
+    let mut app = create_app();
+
+    // Two triangles, in the XY plane in 2D and facing up on the ground in 3D
+    #[cfg(feature = "2d")]
+    let point = |x: Scalar, h: Scalar| Vector::X * x + Vector::Y * h;
+    #[cfg(feature = "3d")]
+    let point = |x: Scalar, h: Scalar| Vector::X * x + Vector::Z * h;
+    let trimesh = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 10.0),
+            Collider::trimesh(
+                vec![
+                    point(0.0, 0.0),
+                    point(1.0, 0.0),
+                    point(0.5, 1.0),
+                    point(2.0, 0.0),
+                    point(3.0, 0.0),
+                    point(2.5, 1.0),
+                ],
+                vec![[0, 2, 1], [3, 5, 4]],
+            ),
+        ))
+        .id();
+    let compound_position = Vector::Y * 10.0;
+    let compound_collider = Collider::compound(vec![
+        (Vector::NEG_X, Rotation::default(), Collider::ball(0.5)),
+        (Vector::X, Rotation::default(), Collider::ball(0.5)),
+    ]);
+    let compound = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(compound_position),
+            compound_collider.clone(),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let origin = point(12.6, 0.2) + Vector::Y * 5.0;
+
+    // The ray hits the second triangle
+    let hit = pipeline
+        .cast_ray(
+            origin,
+            Vector::NEG_Y,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("ray should hit the triangle mesh");
+    assert_eq!(hit.entity, trimesh);
+    assert_eq!(hit.feature, FeatureId::Face(1));
+    assert_relative_eq!(hit.point.x, 12.6, epsilon = 0.001);
+    assert_relative_eq!(hit.point.y, origin.y - hit.time_of_impact, epsilon = 0.001);
+
+    // Hits on the other side of the triangle report the same triangle index
+    let hit = pipeline
+        .cast_ray(
+            point(12.6, 0.2) - Vector::Y * 5.0,
+            Vector::Y,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("ray should hit the triangle mesh");
+    assert_eq!(hit.entity, trimesh);
+    assert_eq!(hit.feature, FeatureId::Face(1));
+
+    // Shape hits report the same feature
+    #[cfg(feature = "2d")]
+    let shape_rotation = 0.0;
+    #[cfg(feature = "3d")]
+    let shape_rotation = Quaternion::IDENTITY;
+    let hit = pipeline
+        .cast_shape(
+            &Collider::ball(0.1),
+            origin,
+            shape_rotation,
+            Vector::NEG_Y,
+            100.0,
+            false,
+            SpatialQueryFilter::default(),
+        )
+        .expect("shape should hit the triangle mesh");
+    assert_eq!(hit.entity, trimesh);
+    assert_eq!(hit.feature, FeatureId::Face(1));
+    assert_relative_eq!(hit.point.x, 12.6, epsilon = 0.1);
+
+    // The hit point can be mapped back to the sub-shape of a compound collider
+    let hit = pipeline
+        .cast_ray(
+            Vector::X + Vector::Y * 20.0,
+            Vector::NEG_Y,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+        .expect("ray should hit the compound collider");
+    assert_eq!(hit.entity, compound);
+    assert_relative_eq!(hit.point.y, 10.5, epsilon = 0.001);
+    assert_eq!(
+        compound_collider.compound_sub_shape_at(compound_position, Rotation::default(), hit.point),
+        Some(1)
+    );
+    assert_eq!(
+        Collider::ball(0.5).compound_sub_shape_at(Vector::ZERO, Rotation::default(), hit.point),
+        None
+    );
+}
+
+#[test]
+fn spatial_query_filter_rules_are_honored() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let spawn_ball = |app: &mut App, x: Scalar, rb: RigidBody| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                rb,
+                Position(Vector::X * x),
+                Collider::ball(0.5),
+            ))
+            .id()
+    };
+    let dynamic = spawn_ball(&mut app, 2.0, RigidBody::Dynamic);
+    let kinematic = spawn_ball(&mut app, 4.0, RigidBody::Kinematic);
+    let sensor = spawn_ball(&mut app, 6.0, RigidBody::Static);
+    let sleeping = spawn_ball(&mut app, 8.0, RigidBody::Static);
+    let layered = spawn_ball(&mut app, 10.0, RigidBody::Static);
+    app.world.entity_mut(sensor).insert(Sensor);
+    app.world
+        .entity_mut(layered)
+        .insert(CollisionLayers::from_bits(0b01, 0b10));
+
+    let ray_caster = app
+        .world
+        .spawn(
+            RayCaster::new(Vector::ZERO, Vector::X).with_query_filter(
+                SpatialQueryFilter::new()
+                    .without_body_types([RigidBody::Dynamic])
+                    .with_sensors(QueryFilterMode::Exclude)
+                    .with_groups_from_bits(0b01),
+            ),
+        )
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+    app.world.entity_mut(sleeping).insert(Sleeping);
+    tick_60_fps(&mut app);
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hit_entities = |query_filter: SpatialQueryFilter| {
+        let mut hits = pipeline.ray_hits(Vector::ZERO, Vector::X, 100.0, 10, true, query_filter);
+        hits.sort_by(|a, b| a.time_of_impact.total_cmp(&b.time_of_impact));
+        hits.into_iter().map(|hit| hit.entity).collect::<Vec<_>>()
+    };
+
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new()),
+        vec![dynamic, kinematic, sensor, sleeping, layered]
+    );
+    assert_eq!(
+        hit_entities(
+            SpatialQueryFilter::new()
+                .without_body_types([RigidBody::Dynamic, RigidBody::Kinematic])
+        ),
+        vec![sensor, sleeping, layered]
+    );
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_body_types([RigidBody::Kinematic])),
+        vec![kinematic]
+    );
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_sensors(QueryFilterMode::Only)),
+        vec![sensor]
+    );
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_sleeping(QueryFilterMode::Exclude)),
+        vec![dynamic, kinematic, sensor, layered]
+    );
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_entities([kinematic, layered])),
+        vec![kinematic, layered]
+    );
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_predicate(move |entity| entity != dynamic)),
+        vec![kinematic, sensor, sleeping, layered]
+    );
+    // The masks of the layered collider don't contain the query's groups
+    assert_eq!(
+        hit_entities(SpatialQueryFilter::new().with_groups_from_bits(0b01)),
+        vec![dynamic, kinematic, sensor, sleeping]
+    );
+
+    // Ray casters use the same rules
+    let ray_hits = app.world.get::<RayHits>(ray_caster).unwrap();
+    assert_eq!(
+        ray_hits
+            .iter_sorted()
+            .map(|hit| hit.entity)
+            .collect::<Vec<_>>(),
+        vec![kinematic, sleeping]
+    );
+}
+
+#[test]
+fn default_spatial_query_filter_ignores_collider_masks() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    // The collider doesn't interact with any groups, but the default filter still includes it
+    let collider = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 2.0),
+            Collider::ball(0.5),
+            CollisionLayers::from_bits(0b01, 0),
+            Sensor,
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let cast_ray = |app: &App, query_filter: SpatialQueryFilter| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(Vector::ZERO, Vector::X, 100.0, true, query_filter)
+            .map(|hit| hit.entity)
+    };
+
+    assert_eq!(cast_ray(&app, SpatialQueryFilter::new()), Some(collider));
+    assert_eq!(
+        cast_ray(&app, SpatialQueryFilter::new().with_groups_from_bits(0b01)),
+        None
+    );
+    assert_eq!(
+        cast_ray(
+            &app,
+            SpatialQueryFilter::new().with_sensors(QueryFilterMode::Exclude)
+        ),
+        None
+    );
+
+    // The filter data is updated when the collider stops being a sensor
+    app.world.entity_mut(collider).remove::<Sensor>();
+    tick_60_fps(&mut app);
+
+    assert_eq!(
+        cast_ray(
+            &app,
+            SpatialQueryFilter::new().with_sensors(QueryFilterMode::Exclude)
+        ),
+        Some(collider)
+    );
+}
+
+#[test]
+fn query_filter_data_is_updated_for_removals_on_frames_without_physics() {
+    let mut app = create_app();
+    app.insert_resource(Gravity(Vector::ZERO));
+
+    let collider = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 2.0),
+            Collider::ball(0.5),
+            Sensor,
+            Sleeping,
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let cast_ray = |app: &App, query_filter: SpatialQueryFilter| {
+        app.world
+            .resource::<SpatialQueryPipeline>()
+            .cast_ray(Vector::ZERO, Vector::X, 100.0, true, query_filter)
+            .map(|hit| hit.entity)
+    };
+    let sensor_filter = SpatialQueryFilter::new().with_sensors(QueryFilterMode::Exclude);
+    let sleeping_filter = SpatialQueryFilter::new().with_sleeping(QueryFilterMode::Exclude);
+
+    assert_eq!(cast_ray(&app, sensor_filter.clone()), None);
+    assert_eq!(cast_ray(&app, sleeping_filter.clone()), None);
+
+    // Remove the components while physics is paused for longer than removal events are kept
+    app.world.resource_mut::<PhysicsLoop>().pause();
+    app.world
+        .entity_mut(collider)
+        .remove::<Sensor>()
+        .remove::<Sleeping>();
+    for _ in 0..3 {
+        tick_60_fps(&mut app);
+    }
+    app.world.resource_mut::<PhysicsLoop>().resume();
+    tick_60_fps(&mut app);
+
+    assert_eq!(cast_ray(&app, sensor_filter), Some(collider));
+    assert_eq!(cast_ray(&app, sleeping_filter), Some(collider));
+}
+
+#[test]
+fn spatial_queries_use_broad_phase_pipeline() {
+    let mut app = create_app();
+
+    app.insert_resource(BroadPhaseConfig {
+        backend: BroadPhaseBackend::SpatialQueryPipeline,
+    });
+
+    let entity = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 5.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let cast_ray = |app: &App| {
+        app.world.resource::<SpatialQueryPipeline>().cast_ray(
+            Vector::ZERO,
+            Vector::X,
+            100.0,
+            true,
+            SpatialQueryFilter::default(),
+        )
+    };
+
+    let hit = cast_ray(&app).expect("ray should hit the collider");
+    assert_eq!(hit.entity, entity);
+    assert_relative_eq!(hit.time_of_impact, 4.5, epsilon = 0.001);
+
+    // Moved colliders are found at their new position
+    app.world
+        .entity_mut(entity)
+        .insert(Position(Vector::X * 10.0));
+    tick_60_fps(&mut app);
+
+    let hit = cast_ray(&app).expect("ray should hit the moved collider");
+    assert_eq!(hit.entity, entity);
+    assert_relative_eq!(hit.time_of_impact, 9.5, epsilon = 0.001);
+
+    // Removed colliders are removed from the pipeline
+    app.world.despawn(entity);
+    tick_60_fps(&mut app);
+
+    assert!(cast_ray(&app).is_none());
+}
+
+#[test]
+fn broad_phase_pairs_persist_until_aabbs_separate() {
+    #[derive(Resource, Default)]
+    struct PairEventCounts {
+        started: usize,
+        ended: usize,
+    }
+
+    let mut app = create_app();
+
+    app.init_resource::<PairEventCounts>();
+
+    app.add_systems(
+        PostUpdate,
+        (|mut counts: ResMut<PairEventCounts>,
+          mut started: EventReader<BroadPhasePairStarted>,
+          mut ended: EventReader<BroadPhasePairEnded>| {
+            counts.started += started.iter().count();
+            counts.ended += ended.iter().count();
+        })
+        .after(PhysicsSet::StepSimulation),
+    );
+
+    app.add_systems(Startup, |mut commands: Commands| {
+        // ball resting on the ground
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::Y * 1.0),
+            Collider::ball(0.5),
+            Id(0),
+        ));
+
+        commands.spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            #[cfg(feature = "2d")]
+            Collider::cuboid(10.0, 1.0),
+            #[cfg(feature = "3d")]
+            Collider::cuboid(10.0, 1.0, 10.0),
+        ));
+    });
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let counts = app.world.resource::<PairEventCounts>();
+    assert_eq!(counts.started, 1);
+    assert_eq!(counts.ended, 0);
+    assert_eq!(app.world.resource::<BroadCollisionPairs>().0.len(), 1);
+
+    // move the ball far away from the ground
+    let mut ball_query = app.world.query_filtered::<&mut Position, With<Id>>();
+    ball_query.single_mut(&mut app.world).0 = Vector::Y * 100.0;
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let counts = app.world.resource::<PairEventCounts>();
+    assert_eq!(counts.started, 1);
+    assert_eq!(counts.ended, 1);
+    assert!(app.world.resource::<BroadCollisionPairs>().0.is_empty());
+}
+
+#[test]
+fn nonlinear_shape_casts_follow_rotation() {
+    let mut app = create_app();
+
+    let ball = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::Y * 1.5),
+            Collider::ball(0.25),
+        ))
+        .id();
+    let lower_ball = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::NEG_Y * 1.5),
+            Collider::ball(0.25),
+        ))
+        .id();
+
+    // A thin stick along the X axis that rotates counterclockwise around its left end
+    #[cfg(feature = "2d")]
+    let stick = Collider::cuboid(2.0, 0.1);
+    #[cfg(feature = "3d")]
+    let stick = Collider::cuboid(2.0, 0.1, 0.1);
+    #[cfg(feature = "2d")]
+    let (shape_rotation, angular_velocity) = (0.0, 1.0);
+    #[cfg(feature = "3d")]
+    let (shape_rotation, angular_velocity) = (Quaternion::IDENTITY, Vector::Z);
+    let mode = ShapeCastMode::Nonlinear {
+        linear_velocity: Vector::ZERO,
+        angular_velocity,
+        local_center: Vector::NEG_X,
+    };
+
+    // The shape caster is turned upside down, so the stick swings into the lower ball
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::from_radians(PI);
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(Quaternion::from_rotation_z(PI));
+    let caster = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            Position(Vector::ZERO),
+            rotation,
+            ShapeCaster::new(stick.clone(), Vector::X, shape_rotation, Vector::X)
+                .with_mode(mode)
+                .with_max_time_of_impact(2.0),
+        ))
+        .id();
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    // The upper edge of the stick touches the ball when the distance
+    // from the ball's center to the stick's axis is 0.3
+    let expected_time_of_impact = PI / 2.0 - (0.3 as Scalar / 1.5).asin();
+
+    let pipeline = app.world.resource::<SpatialQueryPipeline>();
+    let hit = pipeline
+        .cast_shape_nonlinear(
+            &stick,
+            Vector::X,
+            shape_rotation,
+            Vector::NEG_X,
+            Vector::ZERO,
+            angular_velocity,
+            2.0,
+            false,
+            SpatialQueryFilter::default(),
+        )
+        .expect("rotating stick should hit the ball");
+    assert_eq!(hit.entity, ball);
+    assert_relative_eq!(hit.time_of_impact, expected_time_of_impact, epsilon = 0.01);
+    assert_relative_eq!(hit.point.length(), 1.5, epsilon = 0.05);
+
+    // The stick doesn't rotate far enough to reach the ball
+    assert!(pipeline
+        .cast_shape_nonlinear(
+            &stick,
+            Vector::X,
+            shape_rotation,
+            Vector::NEG_X,
+            Vector::ZERO,
+            angular_velocity,
+            1.0,
+            false,
+            SpatialQueryFilter::default(),
+        )
+        .is_none());
+
+    // A linear shape cast along the stick misses both balls
+    assert!(pipeline
+        .cast_shape(
+            &stick,
+            Vector::X,
+            shape_rotation,
+            Vector::X,
+            100.0,
+            false,
+            SpatialQueryFilter::default(),
+        )
+        .is_none());
+
+    let hits = app.world.entity(caster).get::<ShapeHits>().unwrap();
+    assert_eq!(hits.len(), 1);
+    let hit = hits.iter().next().unwrap();
+    assert_eq!(hit.entity, lower_ball);
+    assert_relative_eq!(hit.time_of_impact, expected_time_of_impact, epsilon = 0.01);
+}
+
+#[test]
+fn casters_send_hit_events() {
+    #[derive(Resource, Default)]
+    struct HitEvents(Vec<(&'static str, Entity, Entity)>);
+
+    let mut app = create_app();
+
+    app.init_resource::<HitEvents>();
+
+    app.add_systems(
+        PostUpdate,
+        (|mut events: ResMut<HitEvents>,
+          mut ray_started: EventReader<RayHitStarted>,
+          mut ray_ended: EventReader<RayHitEnded>,
+          mut shape_started: EventReader<ShapeHitStarted>,
+          mut shape_ended: EventReader<ShapeHitEnded>| {
+            for event in ray_started.iter() {
+                events.0.push(("ray started", event.0, event.1));
+            }
+            for event in ray_ended.iter() {
+                events.0.push(("ray ended", event.0, event.1));
+            }
+            for event in shape_started.iter() {
+                events.0.push(("shape started", event.0, event.1));
+            }
+            for event in shape_ended.iter() {
+                events.0.push(("shape ended", event.0, event.1));
+            }
+        })
+        .after(PhysicsSet::StepSimulation),
+    );
+
+    let target = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Kinematic,
+            Position(Vector::X * 5.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+    let ray_caster = app
+        .world
+        .spawn(RayCaster::new(Vector::ZERO, Vector::X).with_hit_events(true))
+        .id();
+    #[cfg(feature = "2d")]
+    let shape_rotation = 0.0;
+    #[cfg(feature = "3d")]
+    let shape_rotation = Quaternion::IDENTITY;
+    let shape_caster = app
+        .world
+        .spawn(
+            ShapeCaster::new(Collider::ball(0.1), Vector::ZERO, shape_rotation, Vector::X)
+                .with_hit_events(true),
+        )
+        .id();
+    // Casters without hit events enabled don't send events
+    app.world.spawn(RayCaster::new(Vector::ZERO, Vector::X));
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    assert_eq!(
+        std::mem::take(&mut app.world.resource_mut::<HitEvents>().0),
+        vec![
+            ("ray started", ray_caster, target),
+            ("shape started", shape_caster, target)
+        ]
+    );
+
+    // Move the target out of the way of the casters
+    app.world
+        .entity_mut(target)
+        .insert(Position(Vector::Y * 5.0));
+
+    tick_60_fps(&mut app);
+
+    assert_eq!(
+        std::mem::take(&mut app.world.resource_mut::<HitEvents>().0),
+        vec![
+            ("ray ended", ray_caster, target),
+            ("shape ended", shape_caster, target)
+        ]
+    );
+
+    // Removed casters end their remaining hits
+    app.world
+        .entity_mut(target)
+        .insert(Position(Vector::X * 5.0));
+
+    tick_60_fps(&mut app);
+
+    app.world.despawn(ray_caster);
+
+    tick_60_fps(&mut app);
+
+    assert_eq!(
+        std::mem::take(&mut app.world.resource_mut::<HitEvents>().0),
+        vec![
+            ("ray started", ray_caster, target),
+            ("shape started", shape_caster, target),
+            ("ray ended", ray_caster, target)
+        ]
+    );
+}
+
+#[test]
+fn multi_ray_caster_follows_parent() {
+    let mut app = create_app();
+
+    let ball = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::NEG_X * 3.0 + Vector::Y * 3.0),
+            Collider::ball(0.5),
+        ))
+        .id();
+
+    // Three rays 45 degrees apart around the X axis
+    #[cfg(feature = "2d")]
+    let pattern = RayPattern::Fan {
+        direction: Vector::X,
+        angle: PI / 2.0,
+        count: 3,
+    };
+    #[cfg(feature = "3d")]
+    let pattern = RayPattern::Fan {
+        direction: Vector::X,
+        axis: Vector::Z,
+        angle: PI / 2.0,
+        count: 3,
+    };
+
+    // The parent is rotated by 90 degrees, so the last ray points up and to the left
+    #[cfg(feature = "2d")]
+    let rotation = Rotation::from_radians(PI / 2.0);
+    #[cfg(feature = "3d")]
+    let rotation = Rotation(Quaternion::from_rotation_z(PI / 2.0));
+    let mut caster = Entity::PLACEHOLDER;
+    app.world
+        .spawn((SpatialBundle::default(), Position(Vector::ZERO), rotation))
+        .with_children(|parent| {
+            caster = parent
+                .spawn(MultiRayCaster::new(Vector::ZERO, pattern).with_max_hits(1))
+                .id();
+        });
+
+    tick_60_fps(&mut app);
+    tick_60_fps(&mut app);
+
+    let global_rays = app
+        .world
+        .entity(caster)
+        .get::<MultiRayCaster>()
+        .unwrap()
+        .global_rays();
+    assert_eq!(global_rays.len(), 3);
+    assert_relative_eq!(global_rays[1].1.y, 1.0, epsilon = 0.0001);
+
+    let hits = app.world.entity(caster).get::<MultiRayHits>().unwrap();
+    assert_eq!(hits.len(), 3);
+    assert!(hits.get(0).unwrap().is_empty());
+    assert!(hits.get(1).unwrap().is_empty());
+    let ray_hits = hits.get(2).unwrap();
+    assert_eq!(ray_hits.len(), 1);
+    assert_eq!(ray_hits.iter().next().unwrap().entity, ball);
+}
+
+#[test]
+fn joint_motors_drive_bodies() {
+    let mut app = create_app();
+
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_body = |density: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), density),
+            ))
+            .id()
+    };
+    let wheel = spawn_body(1.0);
+    let heavy_wheel = spawn_body(100.0);
+    let slider = spawn_body(1.0);
+
+    app.world
+        .spawn(RevoluteJoint::new(anchor, wheel).with_motor(JointMotor::velocity(2.0)));
+    app.world.spawn(
+        RevoluteJoint::new(anchor, heavy_wheel)
+            .with_motor(JointMotor::velocity(2.0).with_max_force(1.0)),
+    );
+    app.world.spawn(
+        PrismaticJoint::new(anchor, slider)
+            .with_free_axis(Vector::X)
+            .with_motor(JointMotor::position(1.0)),
+    );
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let angular_speed = |entity: Entity| {
+        let angular_velocity = app.world.entity(entity).get::<AngularVelocity>().unwrap();
+        #[cfg(feature = "2d")]
+        {
+            angular_velocity.0
+        }
+        #[cfg(feature = "3d")]
+        {
+            angular_velocity.z
+        }
+    };
+
+    // The motor spins the wheel at the target velocity
+    assert_relative_eq!(angular_speed(wheel), 2.0, epsilon = 0.01);
+
+    // The torque of the motor is limited, so it accelerates the heavy wheel slowly
+    assert!(angular_speed(heavy_wheel) > 0.0);
+    assert!(angular_speed(heavy_wheel) < 0.5);
+
+    // The slider is moved to the target position along the free axis
+    let slider_position = app.world.entity(slider).get::<Position>().unwrap().0;
+    assert_relative_eq!(slider_position.x, 1.0, epsilon = 0.01);
+    assert_relative_eq!(slider_position.y, 0.0, epsilon = 0.01);
+}
+
+#[test]
+fn joints_break_when_force_exceeds_threshold() {
+    #[derive(Resource, Default)]
+    struct BrokenJoints(Vec<JointBroken>);
+
+    let mut app = create_app();
+
+    app.init_resource::<BrokenJoints>();
+
+    app.add_systems(
+        PostUpdate,
+        (|mut broken_joints: ResMut<BrokenJoints>, mut events: EventReader<JointBroken>| {
+            broken_joints.0.extend(events.iter().copied());
+        })
+        .after(PhysicsSet::StepSimulation),
+    );
+
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_body = || {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(Vector::NEG_Y),
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            ))
+            .id()
+    };
+    let weak_body = spawn_body();
+    let strong_body = spawn_body();
+
+    let weak_joint = app
+        .world
+        .spawn(
+            SphericalJoint::new(anchor, weak_body)
+                .with_local_anchor_2(Vector::Y)
+                .with_break_force(0.1),
+        )
+        .id();
+    let strong_joint = app
+        .world
+        .spawn(
+            SphericalJoint::new(anchor, strong_body)
+                .with_local_anchor_2(Vector::Y)
+                .with_break_force(100.0),
+        )
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // The weak joint can't hold the weight of the body, so it breaks and is removed
+    let broken_joints = &app.world.resource::<BrokenJoints>().0;
+    assert_eq!(broken_joints.len(), 1);
+    assert_eq!(broken_joints[0].joint, weak_joint);
+    assert_eq!(broken_joints[0].entity1, anchor);
+    assert_eq!(broken_joints[0].entity2, weak_body);
+    assert!(broken_joints[0].force.length() > 0.1);
+    assert!(!app.world.entity(weak_joint).contains::<SphericalJoint>());
+
+    // The weak body falls, while the strong joint keeps holding its body
+    assert!(app.world.entity(weak_body).get::<Position>().unwrap().y < -1.5);
+    assert!(app.world.entity(strong_joint).contains::<SphericalJoint>());
+    assert_relative_eq!(
+        app.world.entity(strong_body).get::<Position>().unwrap().y,
+        -1.0,
+        epsilon = 0.01
+    );
+}
+
+#[test]
+fn d6_joint_axes_can_be_locked_limited_or_free() {
+    let mut app = create_app();
+
+    let anchor = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_body = || {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            ))
+            .id()
+    };
+    let locked_body = spawn_body();
+    let free_body = spawn_body();
+    let limited_body = spawn_body();
+
+    // All axes are locked by default
+    app.world.spawn(D6Joint::new(anchor, locked_body));
+
+    // The body can fall along the free y-axis and spin around the z-axis with the drive
+    let free_joint = D6Joint::new(anchor, free_body)
+        .with_linear_motion(D6Axis::Y, AxisMotion::Free)
+        .with_linear_velocity_damping(0.0)
+        .with_angular_velocity_damping(0.0);
+    #[cfg(feature = "2d")]
+    let free_joint = free_joint
+        .with_angular_motion(AxisMotion::Free)
+        .with_angular_drive(JointMotor::velocity(2.0));
+    #[cfg(feature = "3d")]
+    let free_joint = free_joint
+        .with_angular_motion(D6Axis::Z, AxisMotion::Free)
+        .with_angular_drive(D6Axis::Z, JointMotor::velocity(2.0));
+    app.world.spawn(free_joint);
+
+    // The body can only fall until the limit of the y-axis
+    app.world
+        .spawn(D6Joint::new(anchor, limited_body).with_linear_motion(
+            D6Axis::Y,
+            AxisMotion::Limited(DistanceLimit::new(-1.0, 0.0)),
+        ));
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = |entity: Entity| app.world.entity(entity).get::<Position>().unwrap().0;
+    let angular_velocity =
+        |entity: Entity| app.world.entity(entity).get::<AngularVelocity>().unwrap().0;
+
+    assert_relative_eq!(position(locked_body), Vector::ZERO, epsilon = 0.01);
+
+    assert!(position(free_body).y < -3.0);
+    assert_relative_eq!(position(free_body).x, 0.0, epsilon = 0.01);
+    #[cfg(feature = "2d")]
+    assert_relative_eq!(angular_velocity(free_body), 2.0, epsilon = 0.01);
+    #[cfg(feature = "3d")]
+    assert_relative_eq!(angular_velocity(free_body), Vector::Z * 2.0, epsilon = 0.01);
+
+    assert_relative_eq!(position(limited_body), Vector::NEG_Y, epsilon = 0.01);
+}
+
+#[test]
+fn gear_and_pulley_joints_couple_motion() {
+    let mut app = create_app();
+
+    let frame = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_body = |position: Vector, density: Scalar| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), density),
+            ))
+            .id()
+    };
+    let small_gear = spawn_body(Vector::Y * 5.0, 1.0);
+    let large_gear = spawn_body(Vector::Y * 5.0 + Vector::X * 3.0, 1.0);
+    let load = spawn_body(Vector::X * -2.0, 2.0);
+    let counterweight = spawn_body(Vector::X * 2.0, 1.0);
+
+    // Spin the small gear, which turns the large gear in the opposite direction at half the speed
+    #[cfg(feature = "2d")]
+    app.world
+        .entity_mut(small_gear)
+        .insert(AngularVelocity(2.0));
+    #[cfg(feature = "3d")]
+    app.world
+        .entity_mut(small_gear)
+        .insert(AngularVelocity(Vector::Z * 2.0));
+    for (gear, anchor) in [
+        (small_gear, Vector::Y * 5.0),
+        (large_gear, Vector::Y * 5.0 + Vector::X * 3.0),
+    ] {
+        app.world.spawn(
+            RevoluteJoint::new(frame, gear)
+                .with_local_anchor_1(anchor)
+                .with_angular_velocity_damping(0.0),
+        );
+    }
+    let gear_joint = app
+        .world
+        .spawn(GearJoint::new(small_gear, large_gear).with_ratio(2.0))
+        .id();
+
+    // The heavier load pulls the counterweight up
+    app.world.spawn(
+        PulleyJoint::new(load, counterweight)
+            .with_pulley_anchors(Vector::X * -2.0 + Vector::Y, Vector::X * 2.0 + Vector::Y),
+    );
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = |entity: Entity| app.world.entity(entity).get::<Position>().unwrap().0;
+    let angular_speed = |entity: Entity| {
+        let angular_velocity = app.world.entity(entity).get::<AngularVelocity>().unwrap();
+        #[cfg(feature = "2d")]
+        {
+            angular_velocity.0
+        }
+        #[cfg(feature = "3d")]
+        {
+            angular_velocity.z
+        }
+    };
+
+    assert_relative_eq!(
+        angular_speed(large_gear),
+        -0.5 * angular_speed(small_gear),
+        epsilon = 0.01
+    );
+    assert!(angular_speed(small_gear) > 0.5);
+    let gear_joint = app.world.entity(gear_joint).get::<GearJoint>().unwrap();
+    assert_relative_eq!(
+        gear_joint.angle1 + 2.0 * gear_joint.angle2,
+        0.0,
+        epsilon = 0.01
+    );
+
+    // The bodies accelerate at a third of gravity, because the load is twice as heavy
+    assert!(position(load).y < -0.3);
+    assert_relative_eq!(position(counterweight).y, -position(load).y, epsilon = 0.01);
+    let rope_length = (position(load) - (Vector::X * -2.0 + Vector::Y)).length()
+        + (position(counterweight) - (Vector::X * 2.0 + Vector::Y)).length();
+    assert_relative_eq!(rope_length, 2.0, epsilon = 0.01);
+}
+
+#[test]
+fn coupling_joints_break_when_force_or_torque_exceeds_threshold() {
+    #[derive(Resource, Default)]
+    struct BrokenJoints(Vec<JointBroken>);
+
+    let mut app = create_app();
+
+    app.init_resource::<BrokenJoints>();
+
+    app.add_systems(
+        PostUpdate,
+        (|mut broken_joints: ResMut<BrokenJoints>, mut events: EventReader<JointBroken>| {
+            broken_joints.0.extend(events.iter().copied());
+        })
+        .after(PhysicsSet::StepSimulation),
+    );
+
+    let frame = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_body = |position: Vector| {
+        app.world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            ))
+            .id()
+    };
+    let gear1 = spawn_body(Vector::Y * 5.0);
+    let gear2 = spawn_body(Vector::Y * 5.0 + Vector::X * 3.0);
+    let load = spawn_body(Vector::X * -2.0);
+    let counterweight = spawn_body(Vector::X * 2.0);
+
+    #[cfg(feature = "2d")]
+    app.world.entity_mut(gear1).insert(AngularVelocity(2.0));
+    #[cfg(feature = "3d")]
+    app.world
+        .entity_mut(gear1)
+        .insert(AngularVelocity(Vector::Z * 2.0));
+    for (gear, anchor) in [
+        (gear1, Vector::Y * 5.0),
+        (gear2, Vector::Y * 5.0 + Vector::X * 3.0),
+    ] {
+        app.world.spawn(
+            RevoluteJoint::new(frame, gear)
+                .with_local_anchor_1(anchor)
+                .with_angular_velocity_damping(0.0),
+        );
+    }
+
+    // The gear joint can't exert the torque needed for spinning up the second gear
+    let gear_joint = app
+        .world
+        .spawn(GearJoint::new(gear1, gear2).with_break_torque(0.01))
+        .id();
+
+    // The pulley joint can't hold the weight of the bodies
+    let pulley_joint = app
+        .world
+        .spawn(
+            PulleyJoint::new(load, counterweight)
+                .with_pulley_anchors(Vector::X * -2.0 + Vector::Y, Vector::X * 2.0 + Vector::Y)
+                .with_break_force(1.0),
+        )
+        .id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let broken_joints = &app.world.resource::<BrokenJoints>().0;
+    assert_eq!(broken_joints.len(), 2);
+    assert!(broken_joints
+        .iter()
+        .any(|broken| broken.joint == gear_joint));
+    assert!(broken_joints
+        .iter()
+        .any(|broken| broken.joint == pulley_joint));
+    assert!(!app.world.entity(gear_joint).contains::<GearJoint>());
+    assert!(!app.world.entity(pulley_joint).contains::<PulleyJoint>());
+
+    // Both bodies of the broken pulley fall
+    let position = |entity: Entity| app.world.entity(entity).get::<Position>().unwrap().0;
+    assert!(position(load).y < -0.5);
+    assert!(position(counterweight).y < -0.5);
+}
+
+#[test]
+fn gear_joint_angles_restart_when_entities_change() {
+    let mut app = create_app();
+
+    let frame = app
+        .world
+        .spawn((SpatialBundle::default(), RigidBody::Static))
+        .id();
+    let mut spawn_gear = |position: Vector| {
+        let gear = app
+            .world
+            .spawn((
+                SpatialBundle::default(),
+                RigidBody::Dynamic,
+                Position(position),
+                MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+            ))
+            .id();
+        app.world.spawn(
+            RevoluteJoint::new(frame, gear)
+                .with_local_anchor_1(position)
+                .with_angular_velocity_damping(0.0),
+        );
+        gear
+    };
+    let gear1 = spawn_gear(Vector::ZERO);
+    let gear2 = spawn_gear(Vector::X * 3.0);
+    let gear3 = spawn_gear(Vector::X * -3.0);
+
+    app.insert_resource(Gravity::ZERO);
+
+    #[cfg(feature = "2d")]
+    app.world.entity_mut(gear1).insert(AngularVelocity(2.0));
+    #[cfg(feature = "3d")]
+    app.world
+        .entity_mut(gear1)
+        .insert(AngularVelocity(Vector::Z * 2.0));
+    let gear_joint = app.world.spawn(GearJoint::new(gear1, gear2)).id();
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    // Move the joint to the third gear, which hasn't rotated yet
+    app.world
+        .entity_mut(gear_joint)
+        .get_mut::<GearJoint>()
+        .unwrap()
+        .entity2 = gear3;
+
+    for _ in 0..30 {
+        tick_60_fps(&mut app);
+    }
+
+    let angular_speed = |entity: Entity| {
+        let angular_velocity = app.world.entity(entity).get::<AngularVelocity>().unwrap();
+        #[cfg(feature = "2d")]
+        {
+            angular_velocity.0
+        }
+        #[cfg(feature = "3d")]
+        {
+            angular_velocity.z
+        }
+    };
+
+    // The third gear is coupled to the current rotation of the first gear instead of
+    // catching up with the rotation that the first gear had before the third gear was attached
+    let gear_joint = app.world.entity(gear_joint).get::<GearJoint>().unwrap();
+    assert_relative_eq!(gear_joint.angle1 + gear_joint.angle2, 0.0, epsilon = 0.01);
+    assert!(gear_joint.angle1 < 0.4);
+    assert_relative_eq!(angular_speed(gear3), -angular_speed(gear1), epsilon = 0.01);
+    assert!(angular_speed(gear1) > 0.2);
+}
+
+#[test]
+fn joints_can_be_attached_to_world_body() {
+    let mut app = create_app();
+
+    let world_body = app.world.resource::<WorldBody>().entity();
+    let body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Dynamic,
+            Position(Vector::X * 2.0),
+            MassPropertiesBundle::new_computed(&Collider::ball(0.5), 1.0),
+        ))
+        .id();
+    let other_body = app
+        .world
+        .spawn((
+            SpatialBundle::default(),
+            RigidBody::Static,
+            Position(Vector::X * 2.0 + Vector::Y * 1.5),
+        ))
+        .id();
+
+    // Hang the body from a world point one meter above it
+    let joint = app
+        .world
+        .spawn(
+            SphericalJoint::new(body, world_body)
+                .with_local_anchor_1(Vector::Y)
+                .with_local_anchor_2(Vector::X * 2.0 + Vector::Y * 2.0),
+        )
+        .id();
+
+    for _ in 0..60 {
+        tick_60_fps(&mut app);
+    }
+
+    let position = |app: &App| app.world.entity(body).get::<Position>().unwrap().0;
+    assert_relative_eq!(position(&app).x, 2.0, epsilon = 0.01);
+    assert_relative_eq!(position(&app).y, 1.0, epsilon = 0.01);
+
+    // Move the joint from the world to another body without respawning it
+    let mut joint_component = app.world.get_mut::<SphericalJoint>(joint).unwrap();
+    joint_component.entity2 = other_body;
+    joint_component.local_anchor2 = Vector::ZERO;
+
+    for _ in 0..120 {
+        tick_60_fps(&mut app);
+    }
+
+    let anchor = position(&app) + Vector::Y;
+    assert_relative_eq!(anchor.x, 2.0, epsilon = 0.01);
+    assert_relative_eq!(anchor.y, 1.5, epsilon = 0.01);
+
+    // A despawned world body is replaced by a new one
+    app.world.despawn(world_body);
+    tick_60_fps(&mut app);
+
+    let new_world_body = app.world.resource::<WorldBody>().entity();
+    assert_ne!(new_world_body, world_body);
+    assert_eq!(
+        app.world.get::<RigidBody>(new_world_body),
+        Some(&RigidBody::Static)
+    );
+}